@@ -0,0 +1,27 @@
+//! Time key generation, signing and verification.
+//!
+//! Run with `cargo bench --bench chains`, and compare the backends with
+//! `--features sha256-core` or `--features multi-buffer`.
+use std::time::{Duration, Instant};
+use wots_rs::Keypair;
+
+/// Run `f` repeatedly for about a second and print the mean time per call.
+fn bench<T>(name: &str, mut f: impl FnMut() -> T) {
+    let start = Instant::now();
+    let mut runs = 0u32;
+    while start.elapsed() < Duration::from_secs(1) {
+        std::hint::black_box(f());
+        runs += 1;
+    }
+
+    println!("{:<8} {:>10.1?}", name, start.elapsed() / runs);
+}
+
+fn main() {
+    let keypair = Keypair::from_seed([7u8; 32]);
+    let signature = keypair.sign_unchecked(b"hello");
+
+    bench("keygen", || Keypair::from_seed([7u8; 32]));
+    bench("sign", || keypair.sign_unchecked(b"hello"));
+    bench("verify", || keypair.verify(b"hello", signature));
+}
@@ -0,0 +1,102 @@
+#ifndef WOTS_H
+#define WOTS_H
+
+/* Generated by cbindgen from src/ffi.rs. Do not edit. */
+
+#include <stddef.h>
+#include <stdint.h>
+
+// The length of keys and signatures in bytes.
+//
+// Spelled out so that `cbindgen` can copy it into the header.
+#define WOTS_KEY_LENGTH 1088
+
+// The call succeeded; for `wots_verify`, the signature is valid.
+#define WOTS_OK 0
+
+// A required pointer was null.
+#define WOTS_ERR_NULL_POINTER -1
+
+// A buffer did not have the expected length.
+#define WOTS_ERR_LENGTH -2
+
+// The operating system's random number generator failed.
+#define WOTS_ERR_RNG -3
+
+// The signature does not verify.
+#define WOTS_ERR_INVALID_SIGNATURE -4
+
+#ifdef __cplusplus
+extern "C" {
+#endif // __cplusplus
+
+#if defined(WOTS_STD)
+// Generate a keypair from the operating system's random number generator.
+//
+// Writes the secret and public keys, `WOTS_KEY_LENGTH` bytes each.
+//
+// # Safety
+//
+// `secret_key` and `public_key` must be valid for writes of
+// `secret_key_len` and `public_key_len` bytes.
+int32_t wots_keypair_generate(uint8_t *secret_key,
+                              size_t secret_key_len,
+                              uint8_t *public_key,
+                              size_t public_key_len);
+#endif
+
+// Derive a keypair from the 32-byte `seed`, see [`Keypair::from_seed`].
+//
+// Writes the secret and public keys, `WOTS_KEY_LENGTH` bytes each.
+//
+// # Safety
+//
+// `seed` must be valid for reads of `seed_len` bytes, and `secret_key`
+// and `public_key` must be valid for writes of `secret_key_len` and
+// `public_key_len` bytes.
+int32_t wots_keypair_from_seed(const uint8_t *seed,
+                               size_t seed_len,
+                               uint8_t *secret_key,
+                               size_t secret_key_len,
+                               uint8_t *public_key,
+                               size_t public_key_len);
+
+// Sign `message` with `secret_key`, writing `WOTS_KEY_LENGTH` bytes to
+// `signature`.
+//
+// A secret key must sign only one message.
+//
+// # Safety
+//
+// `secret_key` and `message` must be valid for reads of `secret_key_len`
+// and `message_len` bytes, and `signature` for writes of `signature_len`
+// bytes. `message` may be null if `message_len` is zero.
+int32_t wots_sign(const uint8_t *secret_key,
+                  size_t secret_key_len,
+                  const uint8_t *message,
+                  size_t message_len,
+                  uint8_t *signature,
+                  size_t signature_len);
+
+// Verify a `signature` on `message` against `public_key`.
+//
+// Returns `WOTS_OK` if the signature is valid and
+// `WOTS_ERR_INVALID_SIGNATURE` if it is not.
+//
+// # Safety
+//
+// `public_key`, `message` and `signature` must be valid for reads of
+// `public_key_len`, `message_len` and `signature_len` bytes. `message` may
+// be null if `message_len` is zero.
+int32_t wots_verify(const uint8_t *public_key,
+                    size_t public_key_len,
+                    const uint8_t *message,
+                    size_t message_len,
+                    const uint8_t *signature,
+                    size_t signature_len);
+
+#ifdef __cplusplus
+}  // extern "C"
+#endif  // __cplusplus
+
+#endif  /* WOTS_H */
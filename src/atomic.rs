@@ -0,0 +1,135 @@
+//! One-time keypairs shared between threads.
+use crate::{
+    error::WotsError, keypair::Keypair, public::PublicKey, secret::SecretKey, signature::Signature,
+};
+use core::{
+    cell::UnsafeCell,
+    sync::atomic::{AtomicBool, Ordering},
+};
+
+/// An WOTS keypair that signs at most once, even when shared between
+/// threads.
+///
+/// [`try_sign`](Self::try_sign) takes `&self`, so an `AtomicSigner` can be
+/// put in an `Arc` and handed to every worker. An atomic flag picks the
+/// first caller; it signs and then wipes the secret key, and every other
+/// call returns `WotsError::KeyAlreadyUsed`.
+///
+/// Like [`OneTimeKeypair`](crate::OneTimeKeypair), the guard covers only
+/// this value, not copies of the key kept elsewhere.
+pub struct AtomicSigner {
+    used: AtomicBool,
+    secret: UnsafeCell<SecretKey>,
+    public: PublicKey,
+}
+
+// SAFETY: `secret` is only accessed by the single call of `try_sign` that
+// flips `used` from false to true.
+unsafe impl Sync for AtomicSigner {}
+
+impl AtomicSigner {
+    /// Wrap a `keypair` that has not signed anything yet.
+    pub fn new(keypair: Keypair) -> Self {
+        AtomicSigner {
+            used: AtomicBool::new(false),
+            secret: UnsafeCell::new(keypair.secret),
+            public: keypair.public,
+        }
+    }
+
+    /// The public key which verifies the signature of this keypair.
+    pub fn public_key(&self) -> &PublicKey {
+        &self.public
+    }
+
+    /// Check whether this keypair has signed, or started signing, a
+    /// message.
+    pub fn is_used(&self) -> bool {
+        self.used.load(Ordering::Acquire)
+    }
+
+    /// Sign a `message` if no other call has claimed this keypair.
+    ///
+    /// # Errors
+    ///
+    /// Returns `WotsError::KeyAlreadyUsed` on every call after the first.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use std::sync::Arc;
+    /// use wots_rs::{AtomicSigner, Keypair, WotsError};
+    ///
+    /// let signer = Arc::new(AtomicSigner::new(Keypair::from_seed(&[7u8; 32])));
+    ///
+    /// let worker = Arc::clone(&signer);
+    /// let signature = std::thread::spawn(move || worker.try_sign(b"hello"))
+    ///     .join()
+    ///     .unwrap()
+    ///     .unwrap();
+    ///
+    /// assert!(signer.public_key().verify(b"hello", signature));
+    /// assert_eq!(signer.try_sign(b"world").err(), Some(WotsError::KeyAlreadyUsed));
+    ///
+    /// ```
+    pub fn try_sign(&self, message: &[u8]) -> Result<Signature, WotsError> {
+        if self.used.swap(true, Ordering::AcqRel) {
+            return Err(WotsError::KeyAlreadyUsed);
+        }
+
+        // SAFETY: only the call that flipped `used` reaches this point, so
+        // no other reference to the secret key exists.
+        let secret = unsafe { &mut *self.secret.get() };
+        let signature = secret.sign(message);
+        secret.wipe();
+
+        Ok(signature)
+    }
+}
+
+impl From<Keypair> for AtomicSigner {
+    fn from(value: Keypair) -> Self {
+        AtomicSigner::new(value)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::{sync::Arc, thread, vec::Vec};
+
+    #[test]
+    fn signs_once_across_threads() {
+        let keypair = Keypair::from_seed([7u8; 32]);
+        let expected = keypair.sign_unchecked(b"hello");
+        let signer = Arc::new(AtomicSigner::new(keypair));
+
+        let results: Vec<_> = (0..8)
+            .map(|_| {
+                let signer = Arc::clone(&signer);
+                thread::spawn(move || signer.try_sign(b"hello"))
+            })
+            .collect::<Vec<_>>()
+            .into_iter()
+            .map(|handle| handle.join().unwrap())
+            .collect();
+
+        let signatures: Vec<_> = results.iter().filter_map(|result| result.ok()).collect();
+        assert_eq!(signatures.len(), 1);
+        assert!(signatures[0] == expected);
+        assert!(results
+            .iter()
+            .filter_map(|result| result.err())
+            .all(|error| error == WotsError::KeyAlreadyUsed));
+        assert!(signer.is_used());
+    }
+
+    #[test]
+    fn wipes_the_secret_key() {
+        let signer = AtomicSigner::new(Keypair::from_seed([7u8; 32]));
+        signer.try_sign(b"hello").unwrap();
+
+        let secret = signer.secret.into_inner();
+        assert!(secret.as_bytes().iter().all(|&byte| byte == 0));
+    }
+}
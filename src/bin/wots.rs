@@ -0,0 +1,318 @@
+//! Generate WOTS keys and sign and verify files from the command line.
+//!
+//! Keys and signatures are stored as their flat 1088-byte representation.
+//! Signing records the use of a secret key in a state file beside it, and
+//! a key that has already signed is refused.
+use std::{
+    fs::{self, File},
+    io::{self, BufReader, Write},
+    path::{Path, PathBuf},
+    process::ExitCode,
+};
+use wots_rs::{FileStore, Keypair, PublicKey, SecretKey, Signature, StateStore};
+
+const USAGE: &str = "\
+usage:
+  wots keygen [--key sk.bin] [--pub pk.bin]
+  wots sign --key sk.bin [--out FILE.sig] FILE
+  wots verify --pub pk.bin --sig FILE.sig FILE
+
+A WOTS key must sign only one message: signing a second one lets anyone
+forge signatures with it. `sign` records the use of KEY in KEY.used and
+refuses to sign with it again.";
+
+/// A parsed command line.
+#[derive(Debug, PartialEq)]
+enum Command {
+    Keygen {
+        key: PathBuf,
+        public: PathBuf,
+    },
+    Sign {
+        key: PathBuf,
+        out: PathBuf,
+        file: PathBuf,
+    },
+    Verify {
+        public: PathBuf,
+        sig: PathBuf,
+        file: PathBuf,
+    },
+}
+
+/// Parse the arguments after the program name.
+fn parse(args: &[String]) -> Result<Command, String> {
+    let (command, rest) = args.split_first().ok_or("missing command")?;
+
+    let mut options: Vec<(&str, &str)> = Vec::new();
+    let mut files = Vec::new();
+    let mut rest = rest.iter();
+    while let Some(arg) = rest.next() {
+        match arg.as_str() {
+            option @ ("--key" | "--pub" | "--sig" | "--out") => {
+                let value = rest
+                    .next()
+                    .ok_or_else(|| format!("{} needs a value", option))?;
+                options.push((option, value));
+            }
+            option if option.starts_with("--") => {
+                return Err(format!("unknown option {}", option));
+            }
+            file => files.push(PathBuf::from(file)),
+        }
+    }
+
+    let option = |name: &str| {
+        options
+            .iter()
+            .rev()
+            .find(|(option, _)| *option == name)
+            .map(|(_, value)| PathBuf::from(value))
+    };
+    let required = |name: &str| option(name).ok_or_else(|| format!("missing {}", name));
+    let file = || match files.as_slice() {
+        [file] => Ok(file.clone()),
+        _ => Err(String::from("expected exactly one FILE")),
+    };
+
+    match command.as_str() {
+        "keygen" if files.is_empty() => Ok(Command::Keygen {
+            key: option("--key").unwrap_or_else(|| PathBuf::from("sk.bin")),
+            public: option("--pub").unwrap_or_else(|| PathBuf::from("pk.bin")),
+        }),
+        "keygen" => Err(String::from("keygen takes no FILE")),
+        "sign" => {
+            let file = file()?;
+            let mut default_out = file.clone().into_os_string();
+            default_out.push(".sig");
+
+            Ok(Command::Sign {
+                key: required("--key")?,
+                out: option("--out").unwrap_or_else(|| PathBuf::from(default_out)),
+                file,
+            })
+        }
+        "verify" => Ok(Command::Verify {
+            public: required("--pub")?,
+            sig: required("--sig")?,
+            file: file()?,
+        }),
+        command => Err(format!("unknown command {}", command)),
+    }
+}
+
+/// Read a file holding a flat key or signature.
+fn read_flat(path: &Path) -> Result<Vec<u8>, String> {
+    fs::read(path).map_err(|error| format!("{}: {}", path.display(), error))
+}
+
+/// The state file recording whether the secret key at `key` has signed.
+fn state_path(key: &Path) -> PathBuf {
+    let mut path = key.as_os_str().to_owned();
+    path.push(".used");
+
+    PathBuf::from(path)
+}
+
+/// Write a secret key readable only by its owner.
+fn write_secret(path: &Path, bytes: &[u8]) -> io::Result<()> {
+    let mut options = fs::OpenOptions::new();
+    options.write(true).create_new(true);
+    #[cfg(unix)]
+    std::os::unix::fs::OpenOptionsExt::mode(&mut options, 0o600);
+
+    let mut file = options.open(path)?;
+    file.write_all(bytes)?;
+    file.sync_all()
+}
+
+/// Run a `command`, returning whether a verification succeeded.
+fn run(command: Command) -> Result<bool, String> {
+    match command {
+        Command::Keygen { key, public } => {
+            let keypair = Keypair::generate(&mut rand::rngs::OsRng);
+
+            write_secret(&key, &keypair.secret.to_flat_bytes())
+                .map_err(|error| format!("{}: {}", key.display(), error))?;
+
+            // A new key was written, so any state left by an old key at the
+            // same path no longer applies.
+            let state = state_path(&key);
+            match fs::remove_file(&state) {
+                Err(error) if error.kind() != io::ErrorKind::NotFound => {
+                    return Err(format!("{}: {}", state.display(), error));
+                }
+                _ => {}
+            }
+            fs::write(&public, keypair.public.to_flat_bytes())
+                .map_err(|error| format!("{}: {}", public.display(), error))?;
+
+            Ok(true)
+        }
+        Command::Sign { key, out, file } => {
+            let secret_key = SecretKey::from_bytes(&read_flat(&key)?)
+                .map_err(|error| format!("{}: {}", key.display(), error))?;
+
+            let input =
+                File::open(&file).map_err(|error| format!("{}: {}", file.display(), error))?;
+
+            // The use is recorded durably before signing, so a crash burns
+            // the key rather than allowing a second signature.
+            let mut store = FileStore::new(state_path(&key));
+            let uses = store
+                .reserve()
+                .map_err(|error| format!("{}: {}", store.path().display(), error))?;
+            if uses > 0 {
+                return Err(format!(
+                    "{}: key has already signed a file, generate a new one",
+                    key.display()
+                ));
+            }
+
+            let signature = secret_key
+                .sign_stream(BufReader::new(input))
+                .map_err(|error| format!("{}: {}", file.display(), error))?;
+
+            fs::write(&out, signature.to_flat_bytes())
+                .map_err(|error| format!("{}: {}", out.display(), error))?;
+
+            Ok(true)
+        }
+        Command::Verify { public, sig, file } => {
+            let public_key = PublicKey::from_bytes(&read_flat(&public)?)
+                .map_err(|error| format!("{}: {}", public.display(), error))?;
+            let signature = Signature::from_bytes(&read_flat(&sig)?)
+                .map_err(|error| format!("{}: {}", sig.display(), error))?;
+
+            let input =
+                File::open(&file).map_err(|error| format!("{}: {}", file.display(), error))?;
+            public_key
+                .verify_stream(BufReader::new(input), &signature)
+                .map_err(|error| format!("{}: {}", file.display(), error))
+        }
+    }
+}
+
+fn main() -> ExitCode {
+    let args: Vec<String> = std::env::args().skip(1).collect();
+
+    let command = match parse(&args) {
+        Ok(command) => command,
+        Err(error) => {
+            eprintln!("wots: {}\n\n{}", error, USAGE);
+            return ExitCode::from(2);
+        }
+    };
+    let verify = matches!(command, Command::Verify { .. });
+
+    match run(command) {
+        Ok(true) if verify => {
+            println!("OK");
+            ExitCode::SUCCESS
+        }
+        Ok(true) => ExitCode::SUCCESS,
+        Ok(false) => {
+            eprintln!("wots: signature does not verify");
+            ExitCode::FAILURE
+        }
+        Err(error) => {
+            eprintln!("wots: {}", error);
+            ExitCode::from(2)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn args(line: &str) -> Vec<String> {
+        line.split_whitespace().map(String::from).collect()
+    }
+
+    #[test]
+    fn parses_commands() {
+        assert_eq!(
+            parse(&args("keygen")),
+            Ok(Command::Keygen {
+                key: "sk.bin".into(),
+                public: "pk.bin".into()
+            })
+        );
+        assert_eq!(
+            parse(&args("sign --key sk.bin image.bin")),
+            Ok(Command::Sign {
+                key: "sk.bin".into(),
+                out: "image.bin.sig".into(),
+                file: "image.bin".into()
+            })
+        );
+        assert_eq!(
+            parse(&args("verify image.bin --sig a.sig --pub pk.bin")),
+            Ok(Command::Verify {
+                public: "pk.bin".into(),
+                sig: "a.sig".into(),
+                file: "image.bin".into()
+            })
+        );
+
+        assert!(parse(&args("sign image.bin")).is_err());
+        assert!(parse(&args("verify --pub pk.bin --sig a.sig")).is_err());
+        assert!(parse(&args("sign --key")).is_err());
+        assert!(parse(&args("frobnicate")).is_err());
+    }
+
+    #[test]
+    fn signs_and_verifies_files() {
+        let dir = std::env::temp_dir().join(format!("wots-cli-{}", std::process::id()));
+        let _ = fs::remove_dir_all(&dir);
+        fs::create_dir_all(&dir).unwrap();
+        let path = |name: &str| dir.join(name);
+        fs::write(path("file"), b"firmware").unwrap();
+
+        run(Command::Keygen {
+            key: path("sk.bin"),
+            public: path("pk.bin"),
+        })
+        .unwrap();
+        run(Command::Sign {
+            key: path("sk.bin"),
+            out: path("file.sig"),
+            file: path("file"),
+        })
+        .unwrap();
+
+        let verify = || {
+            run(Command::Verify {
+                public: path("pk.bin"),
+                sig: path("file.sig"),
+                file: path("file"),
+            })
+        };
+        assert_eq!(verify(), Ok(true));
+
+        fs::write(path("file"), b"tampered").unwrap();
+        assert_eq!(verify(), Ok(false));
+
+        let sign_again = || {
+            run(Command::Sign {
+                key: path("sk.bin"),
+                out: path("other.sig"),
+                file: path("file"),
+            })
+        };
+        assert!(sign_again().unwrap_err().contains("already signed"));
+        assert!(!path("other.sig").exists());
+
+        fs::remove_file(path("sk.bin")).unwrap();
+        fs::remove_file(path("pk.bin")).unwrap();
+        run(Command::Keygen {
+            key: path("sk.bin"),
+            public: path("pk.bin"),
+        })
+        .unwrap();
+        assert_eq!(sign_again(), Ok(true));
+
+        fs::remove_dir_all(&dir).unwrap();
+    }
+}
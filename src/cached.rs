@@ -0,0 +1,169 @@
+//! WOTS keypairs with precomputed chain nodes for faster signing.
+use crate::{
+    checksum::with_checksum,
+    constants::{steps, CHAINS, CHAIN_LENGTH, MESSAGE_CHAINS},
+    hash::{iterate, sha256},
+    keypair::Keypair,
+    public::PublicKey,
+    secret::SecretKey,
+    signature::Signature,
+};
+use alloc::vec::Vec;
+#[cfg(feature = "zeroize")]
+use zeroize::{Zeroize, ZeroizeOnDrop};
+
+/// An WOTS keypair that keeps every `stride`-th node of each chain.
+///
+/// Signing with a [`Keypair`] walks each chain from its start, up to 256
+/// hashes per chain. A `CachedKeypair` walks each chain once when it is
+/// built and keeps the nodes at positions `0, stride, 2 * stride, ...`, so
+/// signing starts from the nearest kept node and hashes at most
+/// `stride - 1` times per chain. The cache holds `34 * (256 / stride + 1)`
+/// nodes of 32 bytes; a stride of 16 is about 19 KB.
+///
+/// Signatures are identical to those of the wrapped keypair. The cached
+/// nodes are as secret as the key, and are wiped on drop with the
+/// `zeroize` feature enabled.
+pub struct CachedKeypair {
+    keypair: Keypair,
+    stride: usize,
+    nodes: Vec<[u8; 32]>,
+}
+
+impl CachedKeypair {
+    /// Build the cache of `secret` with nodes every `stride` steps.
+    ///
+    /// The public key is found by the same walk, so this costs the same as
+    /// [`Keypair::from`]. `stride` is clamped to `1..=256`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use wots_rs::{CachedKeypair, Keypair, SecretKey};
+    ///
+    /// let keypair = Keypair::from_seed(&[7u8; 32]);
+    /// let cached = CachedKeypair::new(SecretKey::from_seed(&[7u8; 32]), 16);
+    ///
+    /// assert!(cached.public_key() == &keypair.public);
+    /// assert!(cached.sign(b"hello") == keypair.sign_unchecked(b"hello"));
+    ///
+    /// ```
+    pub fn new(secret: SecretKey, stride: usize) -> Self {
+        let stride = stride.clamp(1, CHAIN_LENGTH);
+        let per_chain = CHAIN_LENGTH / stride + 1;
+        let mut nodes = Vec::with_capacity(CHAINS * per_chain);
+        let mut public = [[0u8; 32]; CHAINS];
+
+        for (start, end) in secret.as_ref().iter().zip(public.iter_mut()) {
+            let mut node = *start;
+            for position in 0..CHAIN_LENGTH {
+                if position.is_multiple_of(stride) {
+                    nodes.push(node);
+                }
+                node = sha256(&node);
+            }
+            if CHAIN_LENGTH.is_multiple_of(stride) {
+                nodes.push(node);
+            }
+            *end = node;
+        }
+
+        CachedKeypair {
+            keypair: Keypair {
+                secret,
+                public: PublicKey::from(public),
+            },
+            stride,
+            nodes,
+        }
+    }
+
+    /// The distance in steps between the kept nodes of a chain.
+    pub fn stride(&self) -> usize {
+        self.stride
+    }
+
+    /// The public key which verifies the signatures of this keypair.
+    pub fn public_key(&self) -> &PublicKey {
+        &self.keypair.public
+    }
+
+    /// The wrapped keypair.
+    pub fn keypair(&self) -> &Keypair {
+        &self.keypair
+    }
+
+    /// Sign a `message`, starting each chain from its nearest kept node.
+    ///
+    /// The signature is identical to [`Keypair::sign`].
+    pub fn sign(&self, message: &[u8]) -> Signature {
+        self.sign_digits(&sha256(message))
+    }
+
+    /// Sign message `digits` directly, like [`Keypair::sign_digits`].
+    pub fn sign_digits(&self, digits: &[u8; MESSAGE_CHAINS]) -> Signature {
+        let digits = with_checksum(digits);
+        let per_chain = CHAIN_LENGTH / self.stride + 1;
+        let mut signature = [[0u8; 32]; CHAINS];
+
+        for (i, chain) in signature.iter_mut().enumerate() {
+            let position = CHAIN_LENGTH - steps(digits[i]);
+            let node = self.nodes[i * per_chain + position / self.stride];
+            *chain = iterate(node, position % self.stride);
+        }
+
+        Signature::from(signature)
+    }
+}
+
+impl Keypair {
+    /// Precompute the chains of this keypair every `stride` steps, for
+    /// faster signing.
+    ///
+    /// See [`CachedKeypair`].
+    pub fn precompute(self, stride: usize) -> CachedKeypair {
+        CachedKeypair::new(self.secret, stride)
+    }
+}
+
+/// Overwrite the cached nodes of a `CachedKeypair` with zeros.
+#[cfg(feature = "zeroize")]
+impl Zeroize for CachedKeypair {
+    fn zeroize(&mut self) {
+        self.keypair.zeroize();
+        self.nodes.zeroize();
+    }
+}
+
+/// Wipe a `CachedKeypair` when it goes out of scope.
+#[cfg(feature = "zeroize")]
+impl Drop for CachedKeypair {
+    fn drop(&mut self) {
+        self.nodes.zeroize();
+    }
+}
+
+#[cfg(feature = "zeroize")]
+impl ZeroizeOnDrop for CachedKeypair {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn matches_uncached_signing() {
+        let keypair = Keypair::from_seed([7u8; 32]);
+        let digits: [u8; MESSAGE_CHAINS] = core::array::from_fn(|i| (i * 37) as u8);
+
+        for stride in [0, 1, 3, 16, 255, 256, 1000] {
+            let cached = Keypair::from_seed([7u8; 32]).precompute(stride);
+            assert!(cached.public_key() == &keypair.public);
+            assert!(cached.sign(b"hello") == keypair.sign_unchecked(b"hello"));
+            assert!(cached.sign_digits(&digits) == keypair.sign_digits(&digits));
+            assert!(
+                cached.sign_digits(&[0; MESSAGE_CHAINS])
+                    == keypair.sign_digits(&[0; MESSAGE_CHAINS])
+            );
+        }
+    }
+}
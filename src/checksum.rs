@@ -0,0 +1,79 @@
+//! The Winternitz checksum.
+//!
+//! Chains can only be advanced, so anyone holding a signature can lower the
+//! digits it signs. The checksum `sum(255 - d_i)` rises whenever a message
+//! digit is lowered, which would force one of its own digits to be lowered
+//! too, so a signature can't be adapted to another message.
+use crate::constants::{CHAINS, CHECKSUM_CHAINS, MESSAGE_CHAINS};
+
+// The largest checksum must be representable by the checksum chains.
+const _: () = assert!(MESSAGE_CHAINS * 255 < 1 << (8 * CHECKSUM_CHAINS));
+
+/// Compute the checksum of the message `digits`.
+pub(crate) fn checksum<const M: usize>(digits: &[u8; M]) -> u16 {
+    digits
+        .iter()
+        .map(|&digit| u16::from(u8::MAX - digit))
+        .fold(0u16, |sum, digit| {
+            sum.checked_add(digit).expect("checksum overflow")
+        })
+}
+
+/// Extend the message `digits` with their big-endian checksum digits.
+pub(crate) fn with_checksum(digits: &[u8; MESSAGE_CHAINS]) -> [u8; CHAINS] {
+    append_checksum(digits)
+}
+
+/// Extend `M` message `digits` of any length to `L` digits with their
+/// big-endian checksum.
+pub(crate) fn append_checksum<const M: usize, const L: usize>(digits: &[u8; M]) -> [u8; L] {
+    const {
+        assert!(L == M + CHECKSUM_CHAINS);
+        assert!(M * 255 < 1 << (8 * CHECKSUM_CHAINS));
+    }
+
+    let mut all = [0u8; L];
+    all[..M].copy_from_slice(digits);
+    all[M..].copy_from_slice(&checksum(digits).to_be_bytes());
+
+    all
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn maximal_checksum() {
+        let digits = [0u8; MESSAGE_CHAINS];
+
+        assert_eq!(checksum(&digits), 8160);
+        assert_eq!(with_checksum(&digits)[MESSAGE_CHAINS..], [0x1f, 0xe0]);
+    }
+
+    #[test]
+    fn minimal_checksum() {
+        let digits = [255u8; MESSAGE_CHAINS];
+
+        assert_eq!(checksum(&digits), 0);
+        assert_eq!(with_checksum(&digits)[MESSAGE_CHAINS..], [0, 0]);
+    }
+
+    #[test]
+    fn lowering_a_digit_raises_the_checksum() {
+        let mut digits = [100u8; MESSAGE_CHAINS];
+        let before = checksum(&digits);
+
+        digits[3] -= 1;
+        assert_eq!(checksum(&digits), before + 1);
+    }
+
+    #[test]
+    fn checksum_of_longer_digests() {
+        let digits = [0u8; 64];
+        let all: [u8; 66] = append_checksum(&digits);
+
+        assert_eq!(checksum(&digits), 64 * 255);
+        assert_eq!(all[64..], (64u16 * 255).to_be_bytes());
+    }
+}
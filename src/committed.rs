@@ -0,0 +1,93 @@
+//! WOTS signatures bundled with a commitment to their public key.
+use crate::{digest32::Commitment, hash::sha256, public::recover, signature::Signature};
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+use subtle::ConstantTimeEq;
+
+/// An WOTS signature together with the commitment to the public key it
+/// verifies under.
+///
+/// The commitment is [`PublicKey::commitment`](crate::PublicKey::commitment),
+/// so the full public key doesn't need to be stored.
+#[derive(Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct CommittedSignature {
+    /// The commitment to the signer's public key.
+    pub commitment: Commitment,
+    /// The signature itself.
+    pub signature: Signature,
+}
+
+impl CommittedSignature {
+    /// Verify this signature on a `message`.
+    ///
+    /// The public key is recovered from the `message` and the signature,
+    /// hashed, and compared to the stored commitment.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use rand::rngs::OsRng;
+    /// use wots_rs::{CommittedSignature, Keypair};
+    ///
+    /// let mut csprng = OsRng{};
+    /// let keypair: Keypair = Keypair::generate(&mut csprng);
+    ///
+    /// let message = b"hello";
+    /// let committed = CommittedSignature {
+    ///     commitment: keypair.public.commitment(),
+    ///     signature: keypair.sign_unchecked(message),
+    /// };
+    ///
+    /// assert!(committed.verify(message));
+    ///
+    /// ```
+    pub fn verify(&self, message: &[u8]) -> bool {
+        let public_key = recover(&sha256(message), &self.signature);
+
+        Commitment::from(sha256(public_key.as_flattened()))
+            .ct_eq(&self.commitment)
+            .into()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Keypair;
+
+    #[test]
+    fn verifies_against_commitment() {
+        let message = b"hello";
+
+        let keypair = Keypair::from_seed([42u8; 32]);
+        let mut committed = CommittedSignature {
+            commitment: keypair.public.commitment(),
+            signature: keypair.sign_unchecked(message),
+        };
+
+        assert!(committed.verify(message));
+        assert!(!committed.verify(b"world"));
+
+        committed.commitment = Keypair::from_seed([43u8; 32]).public.commitment();
+        assert!(!committed.verify(message));
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn serde_round_trip() {
+        let message = b"hello";
+
+        let keypair = Keypair::from_seed([42u8; 32]);
+        let committed = CommittedSignature {
+            commitment: keypair.public.commitment(),
+            signature: keypair.sign_unchecked(message),
+        };
+
+        let bytes = bincode::serialize(&committed).unwrap();
+        let decoded: CommittedSignature = bincode::deserialize(&bytes).unwrap();
+
+        assert!(decoded == committed);
+        assert!(decoded.verify(message));
+    }
+}
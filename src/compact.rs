@@ -0,0 +1,245 @@
+//! WOTS signatures with chains already at the public key omitted.
+use crate::{
+    constants::CHAINS, error::WotsError, hash::sha256, public::PublicKey, signature::Signature,
+};
+use alloc::vec::Vec;
+
+/// An WOTS signature with the chains equal to the public key omitted.
+///
+/// A chain signing the digit `0` is not hashed further on verification, so
+/// its value is the matching public key chain. Such chains are dropped and a
+/// bitmap records their positions; they are restored from the public key
+/// when verifying.
+///
+/// The scheme has [`CHAINS`](crate::CHAINS) chains, so the bitmap is 64 bits wide.
+/// [`to_bytes`](Self::to_bytes) encodes it as a big-endian `u64` followed by
+/// the stored chains.
+#[derive(Eq, PartialEq)]
+pub struct CompactSignature {
+    omitted: u64,
+    chains: Vec<[u8; 32]>,
+}
+
+impl CompactSignature {
+    /// Compress a `signature` by omitting the chains equal to the chains of
+    /// `public_key`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use wots_rs::{CompactSignature, Keypair, MESSAGE_CHAINS};
+    ///
+    /// let keypair = Keypair::from_seed(&[7u8; 32]);
+    ///
+    /// let mut digits = [7u8; MESSAGE_CHAINS];
+    /// digits[..4].fill(0);
+    /// let signature = keypair.sign_digits(&digits);
+    ///
+    /// let compact = CompactSignature::compress(&signature, &keypair.public);
+    /// assert_eq!(compact.omitted(), 0b1111);
+    ///
+    /// assert!(compact.expand(&keypair.public).unwrap() == signature);
+    ///
+    /// ```
+    pub fn compress(signature: &Signature, public_key: &PublicKey) -> Self {
+        let mut omitted = 0;
+        let mut chains = Vec::new();
+
+        for (i, (chain, key)) in signature
+            .as_ref()
+            .iter()
+            .zip(public_key.as_ref().iter())
+            .enumerate()
+        {
+            if chain == key {
+                omitted |= 1 << i;
+            } else {
+                chains.push(*chain);
+            }
+        }
+
+        CompactSignature { omitted, chains }
+    }
+
+    /// The bitmap of omitted chains, bit `i` set if chain `i` was omitted.
+    pub fn omitted(&self) -> u64 {
+        self.omitted
+    }
+
+    /// The stored chain values, in chain order.
+    pub fn chains(&self) -> &[[u8; 32]] {
+        &self.chains
+    }
+
+    /// The size in bytes of the bitmap and the stored chains.
+    pub fn size(&self) -> usize {
+        8 + self.chains.len() * 32
+    }
+
+    /// Encode this signature as the big-endian bitmap followed by the
+    /// stored chains.
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let mut bytes = Vec::with_capacity(self.size());
+        bytes.extend_from_slice(&self.omitted.to_be_bytes());
+        bytes.extend_from_slice(self.chains.as_flattened());
+
+        bytes
+    }
+
+    /// Decode a signature encoded by [`to_bytes`](Self::to_bytes).
+    ///
+    /// # Returns
+    ///
+    /// Returns `WotsError::InvalidEncoding` if the bitmap marks chains
+    /// beyond the [`CHAINS`](crate::CHAINS) of the scheme, or
+    /// `WotsError::LengthMismatch` unless `bytes` holds exactly one chain
+    /// for every bit the bitmap leaves clear.
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self, WotsError> {
+        let bitmap = bytes.get(..8).ok_or(WotsError::LengthMismatch {
+            expected: 8,
+            actual: bytes.len(),
+        })?;
+        let omitted = u64::from_be_bytes(bitmap.try_into().unwrap());
+        if omitted >> CHAINS != 0 {
+            return Err(WotsError::InvalidEncoding);
+        }
+
+        let expected = 8 + (CHAINS - omitted.count_ones() as usize) * 32;
+        if bytes.len() != expected {
+            return Err(WotsError::LengthMismatch {
+                expected,
+                actual: bytes.len(),
+            });
+        }
+
+        Ok(CompactSignature {
+            omitted,
+            chains: bytes[8..]
+                .chunks_exact(32)
+                .map(|chain| chain.try_into().unwrap())
+                .collect(),
+        })
+    }
+
+    /// Restore the full signature, taking the omitted chains from
+    /// `public_key`.
+    ///
+    /// # Returns
+    ///
+    /// Returns `WotsError::LengthMismatch` if the number of stored chains
+    /// doesn't match the chains the bitmap leaves clear.
+    pub fn expand(&self, public_key: &PublicKey) -> Result<Signature, WotsError> {
+        let expected = CHAINS - (self.omitted & ((1 << CHAINS) - 1)).count_ones() as usize;
+        if self.omitted >> CHAINS != 0 || self.chains.len() != expected {
+            return Err(WotsError::LengthMismatch {
+                expected,
+                actual: self.chains.len(),
+            });
+        }
+
+        let mut signature = public_key.to_bytes();
+        let mut chains = self.chains.iter();
+        for (i, chain) in signature.iter_mut().enumerate() {
+            if self.omitted & (1 << i) == 0 {
+                *chain = *chains.next().unwrap();
+            }
+        }
+
+        Ok(Signature::from(signature))
+    }
+
+    /// Verify this signature on a `message` with `public_key`.
+    pub fn verify(&self, message: &[u8], public_key: &PublicKey) -> bool {
+        self.expand(public_key)
+            .and_then(|signature| public_key.verify_digest(&sha256(message), &signature))
+            .unwrap_or(false)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{Keypair, CHAINS, MESSAGE_CHAINS};
+
+    #[test]
+    fn omits_zero_digit_chains() {
+        let keypair = Keypair::from_seed([42u8; 32]);
+
+        let mut digits = [7u8; MESSAGE_CHAINS];
+        for i in [0, 5, 9, 31] {
+            digits[i] = 0;
+        }
+        let signature = keypair.sign_digits(&digits);
+
+        let compact = CompactSignature::compress(&signature, &keypair.public);
+        assert_eq!(compact.omitted(), 1 << 0 | 1 << 5 | 1 << 9 | 1 << 31);
+        assert_eq!(compact.chains().len(), CHAINS - 4);
+        assert_eq!(compact.size(), 8 + (CHAINS - 4) * 32);
+
+        let expanded = compact.expand(&keypair.public).unwrap();
+        assert!(keypair.public.verify_digits(&digits, &expanded));
+
+        digits[5] = 1;
+        assert!(!keypair.public.verify_digits(&digits, &expanded));
+    }
+
+    #[test]
+    fn bytes_round_trip() {
+        let keypair = Keypair::from_seed([42u8; 32]);
+
+        let mut digits = [7u8; MESSAGE_CHAINS];
+        digits[3] = 0;
+        let compact = CompactSignature::compress(&keypair.sign_digits(&digits), &keypair.public);
+
+        let bytes = compact.to_bytes();
+        assert_eq!(bytes.len(), compact.size());
+        assert_eq!(&bytes[..8], &(1u64 << 3).to_be_bytes());
+        assert!(CompactSignature::from_bytes(&bytes).unwrap() == compact);
+
+        assert_eq!(
+            CompactSignature::from_bytes(&bytes[..bytes.len() - 32]).err(),
+            Some(WotsError::LengthMismatch {
+                expected: bytes.len(),
+                actual: bytes.len() - 32,
+            })
+        );
+        assert_eq!(
+            CompactSignature::from_bytes(&bytes[..4]).err(),
+            Some(WotsError::LengthMismatch {
+                expected: 8,
+                actual: 4
+            })
+        );
+
+        let mut beyond = bytes.clone();
+        beyond[..8].copy_from_slice(&(1u64 << CHAINS | 1 << 3).to_be_bytes());
+        assert_eq!(
+            CompactSignature::from_bytes(&beyond).err(),
+            Some(WotsError::InvalidEncoding)
+        );
+
+        let inconsistent = CompactSignature {
+            omitted: 0,
+            chains: compact.chains().to_vec(),
+        };
+        assert_eq!(
+            inconsistent.expand(&keypair.public).err(),
+            Some(WotsError::LengthMismatch {
+                expected: CHAINS,
+                actual: CHAINS - 1,
+            })
+        );
+        assert!(!inconsistent.verify(b"hello", &keypair.public));
+    }
+
+    #[test]
+    fn verifies_messages() {
+        let message = b"hello";
+
+        let keypair = Keypair::from_seed([42u8; 32]);
+        let compact = CompactSignature::compress(&keypair.sign_unchecked(message), &keypair.public);
+
+        assert!(compact.verify(message, &keypair.public));
+        assert!(!compact.verify(b"world", &keypair.public));
+    }
+}
@@ -0,0 +1,37 @@
+//! Constants describing the layout of WOTS keys and signatures.
+
+/// The number of chains signing the message digest, one per digest byte.
+pub const MESSAGE_CHAINS: usize = 32;
+
+/// The number of chains signing the checksum of the message digits.
+///
+/// The checksum is at most `MESSAGE_CHAINS * 255 = 8160`, which takes two
+/// base-256 digits.
+pub const CHECKSUM_CHAINS: usize = 2;
+
+/// The total number of chains in a key or signature.
+pub const CHAINS: usize = MESSAGE_CHAINS + CHECKSUM_CHAINS;
+
+/// The length of a WOTS signature, in bytes.
+pub const SIGNATURE_LENGTH: usize = CHAINS * 32;
+
+/// The magic header prefixing signatures in their magic byte representation.
+pub const SIGNATURE_MAGIC: [u8; 4] = *b"WOTS";
+
+/// The number of hash steps from a secret chain value to its public value.
+#[cfg(not(wots_test_fast_chains))]
+pub(crate) const CHAIN_LENGTH: usize = 256;
+
+/// The number of hash steps from a secret chain value to its public value.
+///
+/// Reduced by `--cfg wots_test_fast_chains` so tests run quickly. Digits are
+/// signed modulo this length, which is trivially forgeable. It is a compiler
+/// flag rather than a cargo feature so that neither `--all-features` nor a
+/// dependent crate can turn it on.
+#[cfg(wots_test_fast_chains)]
+pub(crate) const CHAIN_LENGTH: usize = 16;
+
+/// The number of hash steps that sign `digit`.
+pub(crate) fn steps(digit: u8) -> usize {
+    digit as usize % CHAIN_LENGTH
+}
@@ -0,0 +1,44 @@
+//! Domain separation of the digests signed by the message-binding modes.
+use sha2::{Digest, Sha512_256};
+
+/// The tag in front of every domain-separated digest input.
+const TAG: &[u8] = b"wots-rs domain";
+
+/// The signing modes that bind a message to a value, each separated from
+/// the others and from plain signing.
+#[derive(Clone, Copy)]
+pub(crate) enum Mode {
+    /// An application context.
+    Context = 0,
+    /// A monotonic counter.
+    Counter = 1,
+    /// A signing-time randomizer.
+    Randomized = 2,
+}
+
+/// The digest signed for a `message` bound to `value` in `mode`,
+/// `SHA-512/256(tag || mode || len || value || message)` with the length of
+/// `value` as a big-endian `u64`.
+///
+/// Plain signing hashes messages with SHA-256, and SHA-512/256 starts from
+/// a different initial state, so producing one of these digests with plain
+/// signing takes a SHA-256 preimage. The mode byte and the length keep the
+/// modes and their values apart from each other.
+pub(crate) fn domain_digest(mode: Mode, value: &[u8], message: &[u8]) -> [u8; 32] {
+    let mut hasher = Sha512_256::new();
+    hasher.update(TAG);
+    hasher.update([mode as u8]);
+    hasher.update((value.len() as u64).to_be_bytes());
+    hasher.update(value);
+    hasher.update(message);
+
+    hasher.finalize().into()
+}
+
+/// The digest signed for a `message` under `context`.
+///
+/// Every context, the empty one included, is separated from plain signing
+/// and from the other contexts.
+pub(crate) fn context_digest(message: &[u8], context: &[u8]) -> [u8; 32] {
+    domain_digest(Mode::Context, context, message)
+}
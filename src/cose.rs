@@ -0,0 +1,467 @@
+//! COSE encodings of WOTS public keys and signatures.
+//!
+//! [`PublicKey::to_cose_key`] encodes a public key as a COSE_Key and
+//! [`CoseSign1`] wraps a signature in a COSE_Sign1 message, both as
+//! defined by RFC 9052. WOTS has no registered COSE identifiers, so the key
+//! type [`COSE_KEY_TYPE`] and the algorithm [`COSE_ALGORITHM`] are taken
+//! from the private-use range below -65536; they are only meaningful
+//! between users of this crate.
+//!
+//! Only the subset of CBOR these structures need is supported. Encodings
+//! use definite lengths and the deterministic map key order of RFC 8949.
+use crate::{
+    constants::SIGNATURE_LENGTH, error::WotsError, keypair::Keypair, public::PublicKey,
+    signature::Signature,
+};
+use alloc::vec::Vec;
+
+/// The provisional COSE key type of WOTS public keys.
+pub const COSE_KEY_TYPE: i64 = -65537;
+
+/// The provisional COSE algorithm identifier of WOTS signatures.
+pub const COSE_ALGORITHM: i64 = -65538;
+
+/// The COSE header and key parameter labels used here.
+const KTY: i64 = 1;
+const KID: i64 = 2;
+const ALG: i64 = 3;
+const PUB: i64 = -1;
+
+/// The tag of a COSE_Sign1 message.
+const SIGN1_TAG: u64 = 18;
+
+/// How deeply nested an unknown value may be before it is rejected.
+const MAX_DEPTH: usize = 16;
+
+/// Append the head of a CBOR data item with `major` type and `value`.
+fn head(out: &mut Vec<u8>, major: u8, value: u64) {
+    let major = major << 5;
+    match value {
+        0..=23 => out.push(major | value as u8),
+        24..=0xff => out.extend_from_slice(&[major | 24, value as u8]),
+        0x100..=0xffff => {
+            out.push(major | 25);
+            out.extend_from_slice(&(value as u16).to_be_bytes());
+        }
+        0x1_0000..=0xffff_ffff => {
+            out.push(major | 26);
+            out.extend_from_slice(&(value as u32).to_be_bytes());
+        }
+        _ => {
+            out.push(major | 27);
+            out.extend_from_slice(&value.to_be_bytes());
+        }
+    }
+}
+
+/// Append a CBOR integer.
+fn int(out: &mut Vec<u8>, value: i64) {
+    if value < 0 {
+        head(out, 1, !value as u64);
+    } else {
+        head(out, 0, value as u64);
+    }
+}
+
+/// Append a CBOR byte string.
+fn bytes(out: &mut Vec<u8>, value: &[u8]) {
+    head(out, 2, value.len() as u64);
+    out.extend_from_slice(value);
+}
+
+/// Append a CBOR text string.
+fn text(out: &mut Vec<u8>, value: &str) {
+    head(out, 3, value.len() as u64);
+    out.extend_from_slice(value.as_bytes());
+}
+
+/// A cursor over CBOR input, failing with `WotsError::InvalidEncoding`.
+struct Reader<'a> {
+    input: &'a [u8],
+}
+
+impl<'a> Reader<'a> {
+    /// Take the next `len` bytes.
+    fn take(&mut self, len: usize) -> Result<&'a [u8], WotsError> {
+        if len > self.input.len() {
+            return Err(WotsError::InvalidEncoding);
+        }
+        let (taken, rest) = self.input.split_at(len);
+        self.input = rest;
+
+        Ok(taken)
+    }
+
+    /// Read the head of the next data item as its major type and value.
+    ///
+    /// Indefinite lengths are rejected.
+    fn head(&mut self) -> Result<(u8, u64), WotsError> {
+        let initial = self.take(1)?[0];
+        let value = match initial & 0x1f {
+            info @ 0..=23 => info as u64,
+            24 => self.take(1)?[0] as u64,
+            25 => u16::from_be_bytes(self.take(2)?.try_into().unwrap()) as u64,
+            26 => u32::from_be_bytes(self.take(4)?.try_into().unwrap()) as u64,
+            27 => u64::from_be_bytes(self.take(8)?.try_into().unwrap()),
+            _ => return Err(WotsError::InvalidEncoding),
+        };
+
+        Ok((initial >> 5, value))
+    }
+
+    /// Read a head of the `expected` major type and return its value.
+    fn expect(&mut self, expected: u8) -> Result<u64, WotsError> {
+        match self.head()? {
+            (major, value) if major == expected => Ok(value),
+            _ => Err(WotsError::InvalidEncoding),
+        }
+    }
+
+    /// Read an integer.
+    fn int(&mut self) -> Result<i64, WotsError> {
+        let (major, value) = self.head()?;
+        let value = i64::try_from(value).map_err(|_| WotsError::InvalidEncoding)?;
+
+        match major {
+            0 => Ok(value),
+            1 => Ok(!value),
+            _ => Err(WotsError::InvalidEncoding),
+        }
+    }
+
+    /// Read a byte string.
+    fn bytes(&mut self) -> Result<&'a [u8], WotsError> {
+        let len = self.expect(2)?;
+        self.take(usize::try_from(len).map_err(|_| WotsError::InvalidEncoding)?)
+    }
+
+    /// Read the header of a map and return its number of entries.
+    fn map(&mut self) -> Result<u64, WotsError> {
+        self.expect(5)
+    }
+
+    /// Skip over one data item of any type.
+    fn skip(&mut self, depth: usize) -> Result<(), WotsError> {
+        if depth > MAX_DEPTH {
+            return Err(WotsError::InvalidEncoding);
+        }
+
+        match self.head()? {
+            (0 | 1 | 7, _) => Ok(()),
+            (2 | 3, len) => {
+                self.take(usize::try_from(len).map_err(|_| WotsError::InvalidEncoding)?)?;
+                Ok(())
+            }
+            (4, len) => (0..len).try_for_each(|_| self.skip(depth + 1)),
+            (5, len) => (0..len).try_for_each(|_| {
+                self.skip(depth + 1)?;
+                self.skip(depth + 1)
+            }),
+            (6, _) => self.skip(depth + 1),
+            _ => Err(WotsError::InvalidEncoding),
+        }
+    }
+
+    /// Check that all input has been read.
+    fn finish(&self) -> Result<(), WotsError> {
+        if self.input.is_empty() {
+            Ok(())
+        } else {
+            Err(WotsError::InvalidEncoding)
+        }
+    }
+}
+
+impl PublicKey {
+    /// Encode this public key as a COSE_Key.
+    ///
+    /// The key is the map `{1: kty, 2: kid, 3: alg, -1: pub}`, with
+    /// [`COSE_KEY_TYPE`], the [`fingerprint`](Self::fingerprint) as key
+    /// ID, [`COSE_ALGORITHM`] and the flat bytes of the key.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use wots_rs::{Keypair, PublicKey};
+    ///
+    /// let keypair = Keypair::from_seed(&[7u8; 32]);
+    /// let cose_key = keypair.public.to_cose_key();
+    ///
+    /// assert!(PublicKey::from_cose_key(&cose_key).unwrap() == keypair.public);
+    ///
+    /// ```
+    pub fn to_cose_key(&self) -> Vec<u8> {
+        let mut out = Vec::with_capacity(SIGNATURE_LENGTH + 64);
+        head(&mut out, 5, 4);
+        int(&mut out, KTY);
+        int(&mut out, COSE_KEY_TYPE);
+        int(&mut out, KID);
+        bytes(&mut out, &self.fingerprint());
+        int(&mut out, ALG);
+        int(&mut out, COSE_ALGORITHM);
+        int(&mut out, PUB);
+        bytes(&mut out, self.as_bytes());
+
+        out
+    }
+
+    /// Decode a `PublicKey` from a COSE_Key.
+    ///
+    /// Other parameters are ignored, but a key ID or algorithm, if present,
+    /// must match.
+    ///
+    /// # Returns
+    ///
+    /// Returns `WotsError::InvalidEncoding` if `cose_key` is not a COSE_Key
+    /// of type [`COSE_KEY_TYPE`] holding a
+    /// [`SIGNATURE_LENGTH`](crate::SIGNATURE_LENGTH)-byte public key.
+    pub fn from_cose_key(cose_key: &[u8]) -> Result<Self, WotsError> {
+        let mut reader = Reader { input: cose_key };
+        let (mut kty, mut kid, mut alg, mut public) = (None, None, None, None);
+
+        for _ in 0..reader.map()? {
+            match reader.int()? {
+                KTY => kty = Some(reader.int()?),
+                KID => kid = Some(reader.bytes()?),
+                ALG => alg = Some(reader.int()?),
+                PUB => public = Some(reader.bytes()?),
+                _ => reader.skip(0)?,
+            }
+        }
+        reader.finish()?;
+
+        if kty != Some(COSE_KEY_TYPE) || alg.is_some_and(|alg| alg != COSE_ALGORITHM) {
+            return Err(WotsError::InvalidEncoding);
+        }
+        let public_key = public.ok_or(WotsError::InvalidEncoding).and_then(|bytes| {
+            PublicKey::from_bytes(bytes).map_err(|_| WotsError::InvalidEncoding)
+        })?;
+        if kid.is_some_and(|kid| kid != public_key.fingerprint()) {
+            return Err(WotsError::InvalidEncoding);
+        }
+
+        Ok(public_key)
+    }
+}
+
+/// A COSE_Sign1 message: a payload and one WOTS signature on it.
+///
+/// The protected header names [`COSE_ALGORITHM`] and the unprotected header
+/// carries the signer's [`fingerprint`](PublicKey::fingerprint) as key ID.
+/// The signature covers the `Sig_structure` of RFC 9052, so it binds the
+/// protected header as well as the payload.
+pub struct CoseSign1 {
+    protected: Vec<u8>,
+    /// The key ID from the unprotected header, if any.
+    pub kid: Option<Vec<u8>>,
+    /// The signed payload.
+    pub payload: Vec<u8>,
+    /// The WOTS signature.
+    pub signature: Signature,
+}
+
+impl CoseSign1 {
+    /// Sign a `payload` with `keypair`.
+    ///
+    /// The keypair must sign only one payload.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use wots_rs::{CoseSign1, Keypair};
+    ///
+    /// let keypair = Keypair::from_seed(&[7u8; 32]);
+    /// let message = CoseSign1::sign(&keypair, b"hello").to_bytes();
+    ///
+    /// let message = CoseSign1::from_bytes(&message).unwrap();
+    /// assert_eq!(message.payload, b"hello");
+    /// assert!(message.verify(&keypair.public));
+    ///
+    /// ```
+    pub fn sign(keypair: &Keypair, payload: &[u8]) -> Self {
+        let mut protected = Vec::new();
+        head(&mut protected, 5, 1);
+        int(&mut protected, ALG);
+        int(&mut protected, COSE_ALGORITHM);
+
+        let signature = keypair.sign_unchecked(&to_be_signed(&protected, payload));
+
+        CoseSign1 {
+            protected,
+            kid: Some(keypair.fingerprint().to_vec()),
+            payload: payload.to_vec(),
+            signature,
+        }
+    }
+
+    /// Verify this message against `public_key`.
+    ///
+    /// Returns `false` if the protected header does not name
+    /// [`COSE_ALGORITHM`] or the signature does not verify.
+    pub fn verify(&self, public_key: &PublicKey) -> bool {
+        self.algorithm() == Ok(COSE_ALGORITHM)
+            && public_key.verify(
+                &to_be_signed(&self.protected, &self.payload),
+                self.signature,
+            )
+    }
+
+    /// The algorithm named by the protected header.
+    fn algorithm(&self) -> Result<i64, WotsError> {
+        let mut reader = Reader {
+            input: &self.protected,
+        };
+        let mut alg = None;
+
+        for _ in 0..reader.map()? {
+            match reader.int()? {
+                ALG => alg = Some(reader.int()?),
+                _ => reader.skip(0)?,
+            }
+        }
+        reader.finish()?;
+
+        alg.ok_or(WotsError::InvalidEncoding)
+    }
+
+    /// Encode this message as a tagged COSE_Sign1.
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let mut out = Vec::with_capacity(SIGNATURE_LENGTH + self.payload.len() + 64);
+        head(&mut out, 6, SIGN1_TAG);
+        head(&mut out, 4, 4);
+        bytes(&mut out, &self.protected);
+        match &self.kid {
+            Some(kid) => {
+                head(&mut out, 5, 1);
+                int(&mut out, KID);
+                bytes(&mut out, kid);
+            }
+            None => head(&mut out, 5, 0),
+        }
+        bytes(&mut out, &self.payload);
+        bytes(&mut out, self.signature.as_bytes());
+
+        out
+    }
+
+    /// Decode a COSE_Sign1 message, tagged or untagged.
+    ///
+    /// # Returns
+    ///
+    /// Returns `WotsError::InvalidEncoding` if `message` is not a
+    /// COSE_Sign1 with an attached payload, or `WotsError::LengthMismatch`
+    /// if its signature is not
+    /// [`SIGNATURE_LENGTH`](crate::SIGNATURE_LENGTH) bytes long.
+    pub fn from_bytes(message: &[u8]) -> Result<Self, WotsError> {
+        let mut reader = Reader { input: message };
+        if reader.input.first() == Some(&(0xc0 | SIGN1_TAG as u8)) {
+            reader.take(1)?;
+        }
+        if reader.expect(4)? != 4 {
+            return Err(WotsError::InvalidEncoding);
+        }
+
+        let protected = reader.bytes()?.to_vec();
+        let mut kid = None;
+        for _ in 0..reader.map()? {
+            match reader.int()? {
+                KID => kid = Some(reader.bytes()?.to_vec()),
+                _ => reader.skip(0)?,
+            }
+        }
+        let payload = reader.bytes()?.to_vec();
+        let signature = Signature::from_bytes(reader.bytes()?)?;
+        reader.finish()?;
+
+        Ok(CoseSign1 {
+            protected,
+            kid,
+            payload,
+            signature,
+        })
+    }
+}
+
+/// Encode the `Sig_structure` of a COSE_Sign1 with no external data.
+fn to_be_signed(protected: &[u8], payload: &[u8]) -> Vec<u8> {
+    let mut out = Vec::with_capacity(protected.len() + payload.len() + 24);
+    head(&mut out, 4, 4);
+    text(&mut out, "Signature1");
+    bytes(&mut out, protected);
+    bytes(&mut out, &[]);
+    bytes(&mut out, payload);
+
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloc::vec;
+
+    #[test]
+    fn encodes_integers_and_heads() {
+        let mut out = Vec::new();
+        for value in [0, 23, 24, -1, -24, -25, 1000, COSE_ALGORITHM] {
+            int(&mut out, value);
+        }
+        assert_eq!(
+            out,
+            [
+                0x00, 0x17, 0x18, 0x18, 0x20, 0x37, 0x38, 0x18, 0x19, 0x03, 0xe8, 0x3a, 0x00, 0x01,
+                0x00, 0x01
+            ]
+        );
+
+        let mut reader = Reader { input: &out };
+        for value in [0, 23, 24, -1, -24, -25, 1000, COSE_ALGORITHM] {
+            assert_eq!(reader.int().unwrap(), value);
+        }
+        assert!(reader.finish().is_ok());
+    }
+
+    #[test]
+    fn cose_key_round_trips_and_checks_fields() {
+        let keypair = Keypair::from_seed([7u8; 32]);
+        let cose_key = keypair.public.to_cose_key();
+
+        assert_eq!(&cose_key[..3], [0xa4, 0x01, 0x3a]);
+        assert!(PublicKey::from_cose_key(&cose_key).unwrap() == keypair.public);
+
+        let other = Keypair::from_seed([8u8; 32]).public.to_cose_key();
+        let mut swapped = cose_key[..cose_key.len() - SIGNATURE_LENGTH].to_vec();
+        swapped.extend_from_slice(&other[other.len() - SIGNATURE_LENGTH..]);
+        assert_eq!(
+            PublicKey::from_cose_key(&swapped).err(),
+            Some(WotsError::InvalidEncoding)
+        );
+
+        assert!(PublicKey::from_cose_key(&cose_key[..cose_key.len() - 1]).is_err());
+        assert!(PublicKey::from_cose_key(&[cose_key.as_slice(), &[0]].concat()).is_err());
+    }
+
+    #[test]
+    fn sign1_round_trips_and_rejects_tampering() {
+        let keypair = Keypair::from_seed([7u8; 32]);
+        let encoded = CoseSign1::sign(&keypair, b"hello").to_bytes();
+        assert_eq!(&encoded[..2], [0xd2, 0x84]);
+
+        let message = CoseSign1::from_bytes(&encoded).unwrap();
+        assert_eq!(message.kid, Some(keypair.fingerprint().to_vec()));
+        assert!(message.verify(&keypair.public));
+        assert!(CoseSign1::from_bytes(&encoded[1..])
+            .unwrap()
+            .verify(&keypair.public));
+        assert!(!message.verify(&Keypair::from_seed([8u8; 32]).public));
+
+        let mut tampered = CoseSign1::from_bytes(&encoded).unwrap();
+        tampered.payload = b"world".to_vec();
+        assert!(!tampered.verify(&keypair.public));
+
+        let mut tampered = CoseSign1::from_bytes(&encoded).unwrap();
+        tampered.protected = vec![0xa1, 0x01, 0x26];
+        assert!(!tampered.verify(&keypair.public));
+
+        assert!(CoseSign1::from_bytes(&encoded[..encoded.len() - 1]).is_err());
+    }
+}
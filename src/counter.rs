@@ -0,0 +1,27 @@
+//! WOTS signatures committing to a monotonic counter.
+use crate::{
+    context::{domain_digest, Mode},
+    signature::Signature,
+};
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+/// An WOTS signature on a message together with the counter it commits to.
+///
+/// The counter is folded into the signed digest, so it can't be changed
+/// without invalidating the signature. See
+/// [`Keypair::sign_counter`](crate::Keypair::sign_counter).
+#[derive(Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct CounterSignature {
+    /// The counter committed to by the signature.
+    pub counter: u64,
+    /// The signature itself.
+    pub signature: Signature,
+}
+
+/// The digest signed for a `message` at `counter`, domain separated from
+/// plain signing with the counter in big-endian order.
+pub(crate) fn counter_digest(message: &[u8], counter: u64) -> [u8; 32] {
+    domain_digest(Mode::Counter, &counter.to_be_bytes(), message)
+}
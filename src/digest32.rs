@@ -0,0 +1,209 @@
+//! 32-byte values with distinct meanings.
+//!
+//! Each newtype wraps a `[u8; 32]` and converts to and from it, but they
+//! don't convert into each other, so a commitment can't be passed where a
+//! digest is expected by accident:
+//!
+//! ```compile_fail
+//! use wots_rs::{Commitment, Digest32};
+//!
+//! fn takes_digest(_: Digest32) {}
+//!
+//! takes_digest(Commitment::from([0u8; 32]));
+//! ```
+//!
+//! Nor can a seed stand in for a digest, or a digest for a seed:
+//!
+//! ```compile_fail
+//! use wots_rs::{Digest32, Seed};
+//!
+//! fn takes_root(_: &Digest32) {}
+//!
+//! takes_root(&Seed::from([0u8; 32]));
+//! ```
+//!
+//! ```compile_fail
+//! use wots_rs::{Digest32, SecretKey};
+//!
+//! SecretKey::from_seed(Digest32::from([0u8; 32]));
+//! ```
+use core::fmt;
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+use subtle::{Choice, ConstantTimeEq};
+#[cfg(feature = "zeroize")]
+use zeroize::{Zeroize, ZeroizeOnDrop};
+
+macro_rules! bytes32 {
+    ($(#[$meta:meta])* $name:ident) => {
+        $(#[$meta])*
+        #[derive(Clone, Copy, Debug, Eq, Hash, Ord, PartialEq, PartialOrd)]
+        #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+        pub struct $name([u8; 32]);
+
+        impl $name {
+            /// Convert this value to a byte array.
+            pub fn to_bytes(&self) -> [u8; 32] {
+                self.0
+            }
+        }
+
+        impl From<[u8; 32]> for $name {
+            fn from(value: [u8; 32]) -> Self {
+                Self(value)
+            }
+        }
+
+        impl From<$name> for [u8; 32] {
+            fn from(value: $name) -> Self {
+                value.0
+            }
+        }
+
+        impl AsRef<[u8]> for $name {
+            fn as_ref(&self) -> &[u8] {
+                &self.0
+            }
+        }
+
+        impl ConstantTimeEq for $name {
+            fn ct_eq(&self, other: &Self) -> Choice {
+                self.0.ct_eq(&other.0)
+            }
+        }
+    };
+}
+
+bytes32!(
+    /// A SHA-256 digest, such as a Merkle root.
+    Digest32
+);
+
+bytes32!(
+    /// A commitment to one or more public keys, see
+    /// [`PublicKey::commitment`](crate::PublicKey::commitment).
+    Commitment
+);
+
+bytes32!(
+    /// A public key compressed to the hash of its chains, see
+    /// [`PublicKey::compress`](crate::PublicKey::compress).
+    CompactPublicKey
+);
+
+/// A secret 32-byte seed that keys are expanded from, see
+/// [`SecretKey::from_seed`](crate::SecretKey::from_seed).
+///
+/// The seed-taking constructors accept a `Seed` or a plain `[u8; 32]`, but
+/// no other newtype. Unlike the public values above, a seed compares in
+/// constant time, is hidden from `Debug` and is wiped on drop with the
+/// `zeroize` feature enabled.
+#[derive(Clone)]
+pub struct Seed([u8; 32]);
+
+impl Seed {
+    /// View this seed as its bytes.
+    pub fn as_bytes(&self) -> &[u8; 32] {
+        &self.0
+    }
+}
+
+impl From<[u8; 32]> for Seed {
+    fn from(value: [u8; 32]) -> Self {
+        Seed(value)
+    }
+}
+
+impl From<&[u8; 32]> for Seed {
+    fn from(value: &[u8; 32]) -> Self {
+        Seed(*value)
+    }
+}
+
+impl From<&Seed> for Seed {
+    fn from(value: &Seed) -> Self {
+        value.clone()
+    }
+}
+
+impl From<Seed> for [u8; 32] {
+    fn from(value: Seed) -> Self {
+        value.0
+    }
+}
+
+/// Format a `Seed` without revealing its contents.
+impl fmt::Debug for Seed {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("Seed(***)")
+    }
+}
+
+/// Compare two seeds in constant time.
+impl ConstantTimeEq for Seed {
+    fn ct_eq(&self, other: &Self) -> Choice {
+        self.0.ct_eq(&other.0)
+    }
+}
+
+/// Compare two seeds in constant time.
+impl PartialEq for Seed {
+    fn eq(&self, other: &Self) -> bool {
+        self.ct_eq(other).into()
+    }
+}
+
+impl Eq for Seed {}
+
+/// Overwrite a `Seed` with zeros.
+#[cfg(feature = "zeroize")]
+impl Zeroize for Seed {
+    fn zeroize(&mut self) {
+        self.0.zeroize();
+    }
+}
+
+/// Wipe a `Seed` when it goes out of scope.
+#[cfg(feature = "zeroize")]
+impl Drop for Seed {
+    fn drop(&mut self) {
+        self.zeroize();
+    }
+}
+
+#[cfg(feature = "zeroize")]
+impl ZeroizeOnDrop for Seed {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloc::format;
+
+    #[test]
+    fn converts_to_and_from_bytes() {
+        let bytes = [7u8; 32];
+
+        let digest = Digest32::from(bytes);
+        assert_eq!(<[u8; 32]>::from(digest), bytes);
+        assert_eq!(digest.as_ref(), &bytes[..]);
+
+        let commitment: Commitment = bytes.into();
+        assert_eq!(commitment.to_bytes(), bytes);
+
+        let seed = Seed::from(&bytes);
+        assert_eq!(seed.as_bytes(), &bytes);
+        assert_eq!(<[u8; 32]>::from(seed.clone()), bytes);
+        assert!(seed == Seed::from(bytes));
+        assert_eq!(format!("{:?}", seed), "Seed(***)");
+    }
+
+    #[test]
+    fn constructors_take_seeds_and_arrays() {
+        use crate::{Keypair, SecretKey};
+
+        let seed = Seed::from([7u8; 32]);
+
+        assert!(SecretKey::from_seed(&seed) == SecretKey::from_seed([7u8; 32]));
+        assert!(Keypair::derive(&seed, 1).public == Keypair::derive([7u8; 32], 1).public);
+    }
+}
@@ -0,0 +1,174 @@
+//! Signing 64-byte inputs with a pair of WOTS keypairs.
+use crate::{hash::sha256, keypair::Keypair, public::PublicKey, signature::Signature};
+use rand::{CryptoRng, RngCore};
+
+/// A pair of WOTS keypairs signing the two 32-byte halves of a 64-byte input.
+pub struct DoubleKeypair {
+    /// The keypair signing the first half of the input.
+    pub first: Keypair,
+    /// The keypair signing the second half of the input.
+    pub second: Keypair,
+}
+
+/// The public half of a [`DoubleKeypair`].
+#[derive(Eq, PartialEq)]
+pub struct DoublePublicKey {
+    /// The public key verifying the first half of the input.
+    pub first: PublicKey,
+    /// The public key verifying the second half of the input.
+    pub second: PublicKey,
+}
+
+/// A signature on a 64-byte input made by a [`DoubleKeypair`].
+#[derive(Eq, PartialEq)]
+pub struct DoubleSignature {
+    /// The signature on the first half of the input.
+    pub first: Signature,
+    /// The signature on the second half of the input.
+    pub second: Signature,
+}
+
+impl DoubleKeypair {
+    /// Generate a `DoubleKeypair` from a `csprng`.
+    pub fn generate<R>(csprng: &mut R) -> Self
+    where
+        R: CryptoRng + RngCore,
+    {
+        DoubleKeypair {
+            first: Keypair::generate(csprng),
+            second: Keypair::generate(csprng),
+        }
+    }
+
+    /// The public half of this keypair.
+    pub fn public(&self) -> DoublePublicKey {
+        DoublePublicKey {
+            first: PublicKey::from(self.first.public.to_bytes()),
+            second: PublicKey::from(self.second.public.to_bytes()),
+        }
+    }
+
+    /// Sign a 64-byte `input`, such as a SHA-512 digest.
+    ///
+    /// The halves of the input are tagged by [`split_domain`]. The first is
+    /// signed as digits by the first keypair and the second by the second.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use rand::rngs::OsRng;
+    /// use wots_rs::DoubleKeypair;
+    ///
+    /// let mut csprng = OsRng{};
+    /// let keypair: DoubleKeypair = DoubleKeypair::generate(&mut csprng);
+    ///
+    /// let input = [7u8; 64];
+    /// let signature = keypair.sign(&input);
+    ///
+    /// assert!(keypair.public().verify(&input, &signature));
+    ///
+    /// ```
+    pub fn sign(&self, input: &[u8; 64]) -> DoubleSignature {
+        let (first, second) = split_domain(input);
+
+        DoubleSignature {
+            first: self.first.sign_digits(&first),
+            second: self.second.sign_digits(&second),
+        }
+    }
+
+    /// Verify a `signature` on a 64-byte `input` with this keypair.
+    pub fn verify(&self, input: &[u8; 64], signature: &DoubleSignature) -> bool {
+        let (first, second) = split_domain(input);
+
+        self.first.verify_digits(&first, &signature.first)
+            & self.second.verify_digits(&second, &signature.second)
+    }
+}
+
+impl DoublePublicKey {
+    /// Verify a `signature` on a 64-byte `input`, checking both halves.
+    pub fn verify(&self, input: &[u8; 64], signature: &DoubleSignature) -> bool {
+        let (first, second) = split_domain(input);
+
+        self.first.verify_digits(&first, &signature.first)
+            & self.second.verify_digits(&second, &signature.second)
+    }
+}
+
+/// Split a 64-byte `input` into two domain-separated 32-byte halves.
+///
+/// The first half is `sha256(0x00 || input[..32])` and the second
+/// `sha256(0x01 || input[32..])`, so a half signed in one position can't be
+/// reused in the other.
+///
+/// # Example
+///
+/// ```
+/// use wots_rs::split_domain;
+///
+/// let (first, second) = split_domain(&[7u8; 64]);
+///
+/// assert_ne!(first, second);
+///
+/// ```
+pub fn split_domain(input: &[u8; 64]) -> ([u8; 32], [u8; 32]) {
+    let mut tagged = [0u8; 33];
+
+    tagged[1..].copy_from_slice(&input[..32]);
+    let first = sha256(&tagged);
+
+    tagged[0] = 1;
+    tagged[1..].copy_from_slice(&input[32..]);
+    let second = sha256(&tagged);
+
+    (first, second)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn sign_and_verify_64_bytes() {
+        let keypair = DoubleKeypair {
+            first: Keypair::from_seed([1u8; 32]),
+            second: Keypair::from_seed([2u8; 32]),
+        };
+
+        let mut input = [0u8; 64];
+        for (i, byte) in input.iter_mut().enumerate() {
+            *byte = (i * 3) as u8;
+        }
+
+        let signature = keypair.sign(&input);
+        assert!(keypair.verify(&input, &signature));
+        assert!(keypair.public().verify(&input, &signature));
+
+        let mut tampered = input;
+        tampered[40] ^= 1;
+        assert!(!keypair.public().verify(&tampered, &signature));
+    }
+
+    #[test]
+    fn swapped_halves_fail() {
+        let keypair = DoubleKeypair {
+            first: Keypair::from_seed([1u8; 32]),
+            second: Keypair::from_seed([1u8; 32]),
+        };
+
+        let mut input = [0u8; 64];
+        input[32..].fill(9);
+        let signature = keypair.sign(&input);
+
+        let mut swapped = [0u8; 64];
+        swapped[..32].copy_from_slice(&input[32..]);
+        swapped[32..].copy_from_slice(&input[..32]);
+        let signature = DoubleSignature {
+            first: signature.second,
+            second: signature.first,
+        };
+
+        assert!(!keypair.verify(&swapped, &signature));
+    }
+}
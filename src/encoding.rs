@@ -0,0 +1,121 @@
+//! Text encodings for signatures and keys.
+use crate::{error::WotsError, hex};
+use alloc::{string::String, vec::Vec};
+
+/// The standard base64 alphabet.
+const BASE64_ALPHABET: &[u8; 64] =
+    b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789+/";
+
+/// A text encoding for binary data.
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub enum Encoding {
+    /// Lowercase hexadecimal.
+    Hex,
+    /// Standard base64 with padding.
+    #[default]
+    Base64,
+}
+
+impl Encoding {
+    /// Encode `bytes` as text.
+    pub fn encode(&self, bytes: &[u8]) -> String {
+        match self {
+            Encoding::Hex => hex::encode(bytes),
+            Encoding::Base64 => base64_encode(bytes),
+        }
+    }
+
+    /// Decode `text` into bytes.
+    ///
+    /// # Returns
+    ///
+    /// Returns `WotsError::InvalidEncoding` if `text` is not valid in this
+    /// encoding.
+    pub fn decode(&self, text: &str) -> Result<Vec<u8>, WotsError> {
+        match self {
+            Encoding::Hex => hex::decode(text),
+            Encoding::Base64 => base64_decode(text),
+        }
+        .ok_or(WotsError::InvalidEncoding)
+    }
+}
+
+/// Encode `bytes` as padded base64.
+fn base64_encode(bytes: &[u8]) -> String {
+    let mut text = String::with_capacity(bytes.len().div_ceil(3) * 4);
+
+    for chunk in bytes.chunks(3) {
+        let mut group = [0u8; 3];
+        group[..chunk.len()].copy_from_slice(chunk);
+        let n = u32::from_be_bytes([0, group[0], group[1], group[2]]);
+
+        for i in 0..4 {
+            if i <= chunk.len() {
+                let sextet = (n >> (18 - 6 * i)) & 0x3f;
+                text.push(BASE64_ALPHABET[sextet as usize] as char);
+            } else {
+                text.push('=');
+            }
+        }
+    }
+
+    text
+}
+
+/// Decode padded base64 into bytes.
+///
+/// Returns `None` if `text` is not a multiple of four characters, contains a
+/// character outside the alphabet or misplaced padding.
+fn base64_decode(text: &str) -> Option<Vec<u8>> {
+    let text = text.as_bytes();
+    if !text.len().is_multiple_of(4) {
+        return None;
+    }
+
+    let mut bytes = Vec::with_capacity(text.len() / 4 * 3);
+
+    for (i, chunk) in text.chunks_exact(4).enumerate() {
+        let last = i == text.len() / 4 - 1;
+        let padding = chunk.iter().rev().take_while(|&&c| c == b'=').count();
+        if padding > 2 || (padding > 0 && !last) {
+            return None;
+        }
+
+        let mut n = 0u32;
+        for &c in &chunk[..4 - padding] {
+            let sextet = BASE64_ALPHABET.iter().position(|&a| a == c)?;
+            n = n << 6 | sextet as u32;
+        }
+        n <<= 6 * padding;
+
+        bytes.extend_from_slice(&n.to_be_bytes()[1..4 - padding]);
+    }
+
+    Some(bytes)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn base64_matches_known_values() {
+        let cases: [(&[u8], &str); 4] = [
+            (b"", ""),
+            (b"f", "Zg=="),
+            (b"fo", "Zm8="),
+            (b"foobar", "Zm9vYmFy"),
+        ];
+
+        for (bytes, text) in cases {
+            assert_eq!(Encoding::Base64.encode(bytes), text);
+            assert_eq!(Encoding::Base64.decode(text), Ok(bytes.to_vec()));
+        }
+
+        assert_eq!(
+            Encoding::Base64.decode("Zg=a"),
+            Err(WotsError::InvalidEncoding)
+        );
+        assert_eq!(Encoding::Hex.decode("abc"), Err(WotsError::InvalidEncoding));
+    }
+}
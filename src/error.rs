@@ -0,0 +1,127 @@
+//! Errors which may occur when working with WOTS keys and signatures.
+use core::fmt;
+
+/// Errors which may occur while processing WOTS keys and signatures.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum WotsError {
+    /// The length of an input did not match the length the scheme expects.
+    LengthMismatch {
+        /// The expected length.
+        expected: usize,
+        /// The length that was actually supplied.
+        actual: usize,
+    },
+    /// A signature chain did not hash to the matching public key chain.
+    ChainMismatch {
+        /// The index of the first mismatching chain.
+        index: usize,
+    },
+    /// An index was outside the range of the collection it addresses.
+    IndexOutOfRange {
+        /// The index that was supplied.
+        index: usize,
+        /// The length of the collection.
+        len: usize,
+    },
+    /// A base-`w` digit was not below `w`.
+    InvalidDigit {
+        /// The index of the offending digit.
+        index: usize,
+        /// The offending digit.
+        digit: u8,
+    },
+    /// The Winternitz parameter is not supported.
+    UnsupportedW {
+        /// The requested Winternitz parameter.
+        w: usize,
+    },
+    /// A string was not valid in the expected text encoding.
+    InvalidEncoding,
+    /// Serialized bytes did not start with the expected magic header.
+    BadMagic,
+    /// The random number generator failed or produced output that is
+    /// obviously not random.
+    RngFailure,
+    /// A one-time key had already signed a message.
+    KeyAlreadyUsed,
+    /// A key-usage state store had no unused index left to reserve.
+    StateExhausted {
+        /// The index the store would have reserved next.
+        next: u64,
+    },
+}
+
+impl fmt::Display for WotsError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            WotsError::LengthMismatch { expected, actual } => {
+                write!(f, "length mismatch: expected {}, got {}", expected, actual)
+            }
+            WotsError::ChainMismatch { index } => {
+                write!(f, "signature chain {} does not match the public key", index)
+            }
+            WotsError::IndexOutOfRange { index, len } => {
+                write!(f, "index {} out of range for length {}", index, len)
+            }
+            WotsError::InvalidDigit { index, digit } => {
+                write!(f, "digit {} at index {} is out of range", digit, index)
+            }
+            WotsError::UnsupportedW { w } => write!(f, "unsupported Winternitz parameter {}", w),
+            WotsError::InvalidEncoding => write!(f, "invalid text encoding"),
+            WotsError::BadMagic => write!(f, "missing magic header"),
+            WotsError::RngFailure => write!(f, "random number generator failure"),
+            WotsError::KeyAlreadyUsed => write!(f, "one-time key already used"),
+            WotsError::StateExhausted { next } => {
+                write!(f, "no index left to reserve after {}", next)
+            }
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for WotsError {}
+
+/// The crate-wide error type, returned by parsing and by
+/// [`PublicKey::verify_strict`](crate::PublicKey::verify_strict).
+pub type Error = WotsError;
+
+/// The reasons a signature may fail to verify.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum VerifyError {
+    /// The signature bytes could not be parsed.
+    MalformedSignature(WotsError),
+    /// A chain signing a message digit did not hash to the public key.
+    ChainMismatch {
+        /// The index of the first mismatching chain.
+        index: usize,
+    },
+    /// A chain signing a checksum digit did not hash to the public key.
+    ChecksumMismatch {
+        /// The index of the first mismatching chain.
+        index: usize,
+    },
+}
+
+impl fmt::Display for VerifyError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            VerifyError::MalformedSignature(error) => write!(f, "malformed signature: {}", error),
+            VerifyError::ChainMismatch { index } => {
+                write!(f, "message chain {} does not match the public key", index)
+            }
+            VerifyError::ChecksumMismatch { index } => {
+                write!(f, "checksum chain {} does not match the public key", index)
+            }
+        }
+    }
+}
+
+/// Report a signature which could not be parsed.
+impl From<WotsError> for VerifyError {
+    fn from(value: WotsError) -> Self {
+        VerifyError::MalformedSignature(value)
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for VerifyError {}
@@ -0,0 +1,344 @@
+//! A C interface to WOTS keys and signatures.
+//!
+//! Keys and signatures are passed as their flat
+//! [`SIGNATURE_LENGTH`]-byte encoding through a
+//! pointer and a length. Every function returns one of the `WOTS_*` status
+//! codes, with [`WOTS_OK`] on success. The C declarations are in
+//! `include/wots.h`, generated from this file with `cbindgen`.
+use crate::{
+    constants::SIGNATURE_LENGTH, keypair::Keypair, public::PublicKey, secret::SecretKey,
+    signature::Signature,
+};
+use core::slice;
+
+/// The length of keys and signatures in bytes.
+///
+/// Spelled out so that `cbindgen` can copy it into the header.
+pub const WOTS_KEY_LENGTH: usize = 1088;
+
+const _: () = assert!(WOTS_KEY_LENGTH == SIGNATURE_LENGTH);
+
+/// The call succeeded; for `wots_verify`, the signature is valid.
+pub const WOTS_OK: i32 = 0;
+/// A required pointer was null.
+pub const WOTS_ERR_NULL_POINTER: i32 = -1;
+/// A buffer did not have the expected length.
+pub const WOTS_ERR_LENGTH: i32 = -2;
+/// The operating system's random number generator failed.
+pub const WOTS_ERR_RNG: i32 = -3;
+/// The signature does not verify.
+pub const WOTS_ERR_INVALID_SIGNATURE: i32 = -4;
+
+/// View `len` bytes at `ptr`, allowing a null pointer for an empty buffer.
+///
+/// # Safety
+///
+/// Unless `len` is zero, `ptr` must be valid for reads of `len` bytes.
+unsafe fn input<'a>(ptr: *const u8, len: usize) -> Result<&'a [u8], i32> {
+    if len == 0 {
+        return Ok(&[]);
+    }
+    if ptr.is_null() {
+        return Err(WOTS_ERR_NULL_POINTER);
+    }
+
+    // SAFETY: the caller guarantees `ptr` is valid for `len` bytes.
+    Ok(unsafe { slice::from_raw_parts(ptr, len) })
+}
+
+/// View the `len`-byte output buffer at `ptr`, checking its length.
+///
+/// # Safety
+///
+/// `ptr` must be null or valid for writes of `len` bytes.
+unsafe fn output<'a>(ptr: *mut u8, len: usize) -> Result<&'a mut [u8], i32> {
+    if ptr.is_null() {
+        return Err(WOTS_ERR_NULL_POINTER);
+    }
+    if len != WOTS_KEY_LENGTH {
+        return Err(WOTS_ERR_LENGTH);
+    }
+
+    // SAFETY: the caller guarantees `ptr` is valid for `len` bytes.
+    Ok(unsafe { slice::from_raw_parts_mut(ptr, len) })
+}
+
+/// Write the flat bytes of `keypair` to the output buffers.
+///
+/// # Safety
+///
+/// As for [`wots_keypair_from_seed`].
+unsafe fn write_keypair(
+    keypair: &Keypair,
+    secret_key: *mut u8,
+    secret_key_len: usize,
+    public_key: *mut u8,
+    public_key_len: usize,
+) -> Result<(), i32> {
+    let (secret_out, public_out) = unsafe {
+        (
+            output(secret_key, secret_key_len)?,
+            output(public_key, public_key_len)?,
+        )
+    };
+
+    secret_out.copy_from_slice(keypair.secret.as_bytes());
+    public_out.copy_from_slice(keypair.public.as_bytes());
+
+    Ok(())
+}
+
+/// Collapse a result into a status code.
+fn status(result: Result<(), i32>) -> i32 {
+    result.err().unwrap_or(WOTS_OK)
+}
+
+/// Generate a keypair from the operating system's random number generator.
+///
+/// Writes the secret and public keys, `WOTS_KEY_LENGTH` bytes each.
+///
+/// # Safety
+///
+/// `secret_key` and `public_key` must be valid for writes of
+/// `secret_key_len` and `public_key_len` bytes.
+#[cfg(feature = "std")]
+#[no_mangle]
+pub unsafe extern "C" fn wots_keypair_generate(
+    secret_key: *mut u8,
+    secret_key_len: usize,
+    public_key: *mut u8,
+    public_key_len: usize,
+) -> i32 {
+    let Ok(keypair) = Keypair::try_generate(&mut rand::rngs::OsRng) else {
+        return WOTS_ERR_RNG;
+    };
+
+    status(unsafe {
+        write_keypair(
+            &keypair,
+            secret_key,
+            secret_key_len,
+            public_key,
+            public_key_len,
+        )
+    })
+}
+
+/// Derive a keypair from the 32-byte `seed`, see [`Keypair::from_seed`].
+///
+/// Writes the secret and public keys, `WOTS_KEY_LENGTH` bytes each.
+///
+/// # Safety
+///
+/// `seed` must be valid for reads of `seed_len` bytes, and `secret_key`
+/// and `public_key` must be valid for writes of `secret_key_len` and
+/// `public_key_len` bytes.
+#[no_mangle]
+pub unsafe extern "C" fn wots_keypair_from_seed(
+    seed: *const u8,
+    seed_len: usize,
+    secret_key: *mut u8,
+    secret_key_len: usize,
+    public_key: *mut u8,
+    public_key_len: usize,
+) -> i32 {
+    status((|| {
+        let seed: &[u8; 32] = unsafe { input(seed, seed_len)? }
+            .try_into()
+            .map_err(|_| WOTS_ERR_LENGTH)?;
+
+        unsafe {
+            write_keypair(
+                &Keypair::from_seed(seed),
+                secret_key,
+                secret_key_len,
+                public_key,
+                public_key_len,
+            )
+        }
+    })())
+}
+
+/// Sign `message` with `secret_key`, writing `WOTS_KEY_LENGTH` bytes to
+/// `signature`.
+///
+/// A secret key must sign only one message.
+///
+/// # Safety
+///
+/// `secret_key` and `message` must be valid for reads of `secret_key_len`
+/// and `message_len` bytes, and `signature` for writes of `signature_len`
+/// bytes. `message` may be null if `message_len` is zero.
+#[no_mangle]
+pub unsafe extern "C" fn wots_sign(
+    secret_key: *const u8,
+    secret_key_len: usize,
+    message: *const u8,
+    message_len: usize,
+    signature: *mut u8,
+    signature_len: usize,
+) -> i32 {
+    status((|| {
+        let secret_key = unsafe { input(secret_key, secret_key_len)? };
+        if secret_key.is_empty() {
+            return Err(WOTS_ERR_NULL_POINTER);
+        }
+        let secret_key = SecretKey::from_bytes(secret_key).map_err(|_| WOTS_ERR_LENGTH)?;
+        let message = unsafe { input(message, message_len)? };
+        let out = unsafe { output(signature, signature_len)? };
+
+        secret_key.sign_into(message, out.try_into().unwrap());
+
+        Ok(())
+    })())
+}
+
+/// Verify a `signature` on `message` against `public_key`.
+///
+/// Returns `WOTS_OK` if the signature is valid and
+/// `WOTS_ERR_INVALID_SIGNATURE` if it is not.
+///
+/// # Safety
+///
+/// `public_key`, `message` and `signature` must be valid for reads of
+/// `public_key_len`, `message_len` and `signature_len` bytes. `message` may
+/// be null if `message_len` is zero.
+#[no_mangle]
+pub unsafe extern "C" fn wots_verify(
+    public_key: *const u8,
+    public_key_len: usize,
+    message: *const u8,
+    message_len: usize,
+    signature: *const u8,
+    signature_len: usize,
+) -> i32 {
+    status((|| {
+        let (public_key, message, signature) = unsafe {
+            (
+                input(public_key, public_key_len)?,
+                input(message, message_len)?,
+                input(signature, signature_len)?,
+            )
+        };
+        let public_key = PublicKey::from_bytes(public_key).map_err(|_| WOTS_ERR_LENGTH)?;
+        let signature = Signature::from_bytes(signature).map_err(|_| WOTS_ERR_LENGTH)?;
+
+        if public_key.verify(message, signature) {
+            Ok(())
+        } else {
+            Err(WOTS_ERR_INVALID_SIGNATURE)
+        }
+    })())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use core::ptr;
+
+    #[test]
+    fn sign_and_verify_through_pointers() {
+        let mut secret_key = [0u8; WOTS_KEY_LENGTH];
+        let mut public_key = [0u8; WOTS_KEY_LENGTH];
+        let mut signature = [0u8; WOTS_KEY_LENGTH];
+        let seed = [7u8; 32];
+        let message = b"hello";
+
+        unsafe {
+            assert_eq!(
+                wots_keypair_from_seed(
+                    seed.as_ptr(),
+                    seed.len(),
+                    secret_key.as_mut_ptr(),
+                    secret_key.len(),
+                    public_key.as_mut_ptr(),
+                    public_key.len(),
+                ),
+                WOTS_OK
+            );
+            assert_eq!(public_key, Keypair::from_seed(seed).public.to_flat_bytes());
+
+            assert_eq!(
+                wots_sign(
+                    secret_key.as_ptr(),
+                    secret_key.len(),
+                    message.as_ptr(),
+                    message.len(),
+                    signature.as_mut_ptr(),
+                    signature.len(),
+                ),
+                WOTS_OK
+            );
+
+            let verify = |message: &[u8], signature: &[u8]| {
+                wots_verify(
+                    public_key.as_ptr(),
+                    public_key.len(),
+                    message.as_ptr(),
+                    message.len(),
+                    signature.as_ptr(),
+                    signature.len(),
+                )
+            };
+            assert_eq!(verify(message, &signature), WOTS_OK);
+            assert_eq!(verify(b"world", &signature), WOTS_ERR_INVALID_SIGNATURE);
+            assert_eq!(verify(message, &signature[1..]), WOTS_ERR_LENGTH);
+        }
+    }
+
+    #[test]
+    fn rejects_bad_buffers() {
+        let mut out = [0u8; WOTS_KEY_LENGTH];
+        let seed = [7u8; 32];
+
+        unsafe {
+            assert_eq!(
+                wots_keypair_from_seed(
+                    seed.as_ptr(),
+                    31,
+                    out.as_mut_ptr(),
+                    out.len(),
+                    out.as_mut_ptr(),
+                    out.len(),
+                ),
+                WOTS_ERR_LENGTH
+            );
+            assert_eq!(
+                wots_sign(
+                    ptr::null(),
+                    WOTS_KEY_LENGTH,
+                    ptr::null(),
+                    0,
+                    out.as_mut_ptr(),
+                    out.len(),
+                ),
+                WOTS_ERR_NULL_POINTER
+            );
+            assert_eq!(
+                wots_verify(out.as_ptr(), out.len(), ptr::null(), 0, ptr::null(), 0),
+                WOTS_ERR_LENGTH
+            );
+        }
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn generates_keypairs() {
+        let mut secret_key = [0u8; WOTS_KEY_LENGTH];
+        let mut public_key = [0u8; WOTS_KEY_LENGTH];
+
+        let status = unsafe {
+            wots_keypair_generate(
+                secret_key.as_mut_ptr(),
+                secret_key.len(),
+                public_key.as_mut_ptr(),
+                public_key.len(),
+            )
+        };
+        assert_eq!(status, WOTS_OK);
+        assert!(
+            PublicKey::from(&SecretKey::from_bytes(&secret_key).unwrap())
+                == PublicKey::from_bytes(&public_key).unwrap()
+        );
+    }
+}
@@ -0,0 +1,377 @@
+//! FORS few-time signatures.
+//!
+//! A FORS key is a forest of `k` Merkle trees with `2^a` secret leaves each.
+//! The SHA-256 hash of a message is split into `k` indices of `a` bits, and a
+//! signature reveals the secret leaf at each index together with its
+//! authentication path. The public key is the hash of the `k` roots,
+//! together with the parameters it was made for.
+//!
+//! Unlike a WOTS key, a FORS key survives a few signatures: each one reveals
+//! only `k` of its leaves, and a forger must find a message whose indices
+//! all fall on revealed leaves. See [`ForsParams::security_bits`] for how
+//! quickly that gets easier.
+use crate::{digest32::Seed, error::WotsError, hash::sha256, merkle::parent};
+use alloc::{vec, vec::Vec};
+use rand::{CryptoRng, RngCore};
+use subtle::ConstantTimeEq;
+
+/// FORS parameters: `k` trees of height `a`.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct ForsParams {
+    k: usize,
+    a: usize,
+}
+
+impl ForsParams {
+    /// FORS parameters with `k` trees of height `a`.
+    ///
+    /// # Errors
+    ///
+    /// Returns `WotsError::UnsupportedW` with `w = 2^a` unless `a` is between
+    /// 1 and 16 and the `k * a` index bits fit in a SHA-256 digest.
+    pub fn new(k: usize, a: usize) -> Result<Self, WotsError> {
+        if !(1..=16).contains(&a) || k == 0 || k * a > 256 {
+            return Err(WotsError::UnsupportedW {
+                w: 1usize.checked_shl(a as u32).unwrap_or(0),
+            });
+        }
+
+        Ok(ForsParams { k, a })
+    }
+
+    /// The number of trees.
+    pub fn trees(&self) -> usize {
+        self.k
+    }
+
+    /// The height of each tree.
+    pub fn height(&self) -> usize {
+        self.a
+    }
+
+    /// The size of a signature in bytes.
+    pub fn signature_size(&self) -> usize {
+        self.k * (self.a + 1) * 32
+    }
+
+    /// Estimate the bits of security left after `signatures` signatures.
+    ///
+    /// After `r` signatures each leaf has been revealed with probability
+    /// `1 - (1 - 2^-a)^r`, and a forger needs a message whose `k` indices
+    /// all hit revealed leaves, so the estimate is
+    /// `-k * log2(1 - (1 - 2^-a)^r)`. With the default parameters that is
+    /// 242 bits after one signature, about 220 after two, 169 after ten and
+    /// 97 after a hundred.
+    ///
+    /// Requires the `std` feature for floating-point logarithms.
+    #[cfg(feature = "std")]
+    pub fn security_bits(&self, signatures: u32) -> f64 {
+        let unrevealed = (1.0 - (-(self.a as f64)).exp2()).powi(signatures as i32);
+
+        -(self.k as f64) * (1.0 - unrevealed).log2()
+    }
+
+    /// The `k` leaf indices selected by the message hash `digest`.
+    fn indices(&self, digest: &[u8; 32]) -> Vec<usize> {
+        (0..self.k)
+            .map(|tree| {
+                (tree * self.a..(tree + 1) * self.a).fold(0, |index, bit| {
+                    index << 1 | (digest[bit / 8] >> (7 - bit % 8)) as usize & 1
+                })
+            })
+            .collect()
+    }
+}
+
+/// The default FORS parameters: 22 trees of height 11, using 242 bits of
+/// the message hash.
+impl Default for ForsParams {
+    fn default() -> Self {
+        ForsParams { k: 22, a: 11 }
+    }
+}
+
+/// A FORS keypair.
+///
+/// The trees are kept in memory, so signing only looks up leaves and paths.
+pub struct ForsKeypair {
+    params: ForsParams,
+    seed: Seed,
+    trees: Vec<Vec<Vec<[u8; 32]>>>,
+}
+
+impl ForsKeypair {
+    /// Generate a FORS keypair for `params`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use rand::rngs::OsRng;
+    /// use wots_rs::{ForsKeypair, ForsParams};
+    ///
+    /// let mut csprng = OsRng{};
+    /// let keypair = ForsKeypair::generate(&mut csprng, ForsParams::new(8, 4).unwrap());
+    ///
+    /// let public_key = keypair.public_key();
+    /// for message in [b"hello", b"world"] {
+    ///     assert!(public_key.verify(message, &keypair.sign(message)));
+    /// }
+    ///
+    /// ```
+    pub fn generate<R>(csprng: &mut R, params: ForsParams) -> Self
+    where
+        R: CryptoRng + RngCore,
+    {
+        let mut seed = [0u8; 32];
+        csprng.fill_bytes(&mut seed);
+
+        Self::from_seed(seed, params)
+    }
+
+    /// Derive a FORS keypair for `params` from a 32-byte `seed`.
+    ///
+    /// Leaf `j` of tree `i` has the secret `sha256(seed || i || j)`, with
+    /// `i` and `j` encoded as big-endian `u32`s.
+    pub fn from_seed(seed: impl Into<Seed>, params: ForsParams) -> Self {
+        let mut keypair = ForsKeypair {
+            params,
+            seed: seed.into(),
+            trees: Vec::with_capacity(params.k),
+        };
+
+        for tree in 0..params.k {
+            let leaves: Vec<[u8; 32]> = (0..1 << params.a)
+                .map(|leaf| sha256(&keypair.secret(tree, leaf)))
+                .collect();
+
+            let mut levels = vec![leaves];
+            while levels[levels.len() - 1].len() > 1 {
+                let level = levels[levels.len() - 1]
+                    .chunks_exact(2)
+                    .map(|pair| parent(&pair[0], &pair[1]))
+                    .collect();
+                levels.push(level);
+            }
+            keypair.trees.push(levels);
+        }
+
+        keypair
+    }
+
+    /// The parameters of this keypair.
+    pub fn params(&self) -> ForsParams {
+        self.params
+    }
+
+    /// The public key of this keypair, the hash of its roots.
+    pub fn public_key(&self) -> ForsPublicKey {
+        let roots: Vec<[u8; 32]> = self
+            .trees
+            .iter()
+            .map(|levels| levels[self.params.a][0])
+            .collect();
+
+        ForsPublicKey {
+            params: self.params,
+            root: sha256(roots.as_flattened()),
+        }
+    }
+
+    /// Sign a `message`.
+    ///
+    /// Every signature weakens the key, see [`ForsParams::security_bits`].
+    pub fn sign(&self, message: &[u8]) -> ForsSignature {
+        let leaves = self
+            .params
+            .indices(&sha256(message))
+            .into_iter()
+            .zip(self.trees.iter())
+            .enumerate()
+            .map(|(tree, (index, levels))| ForsLeaf {
+                secret: self.secret(tree, index),
+                auth_path: levels[..self.params.a]
+                    .iter()
+                    .enumerate()
+                    .map(|(height, level)| level[(index >> height) ^ 1])
+                    .collect(),
+            })
+            .collect();
+
+        ForsSignature {
+            params: self.params,
+            leaves,
+        }
+    }
+
+    /// The secret of leaf `leaf` in tree `tree`.
+    fn secret(&self, tree: usize, leaf: usize) -> [u8; 32] {
+        let mut input = [0u8; 40];
+        input[..32].copy_from_slice(self.seed.as_bytes());
+        input[32..36].copy_from_slice(&(tree as u32).to_be_bytes());
+        input[36..].copy_from_slice(&(leaf as u32).to_be_bytes());
+
+        sha256(&input)
+    }
+}
+
+/// A FORS public key, the 32-byte hash of the roots of its trees and the
+/// parameters of those trees.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct ForsPublicKey {
+    params: ForsParams,
+    root: [u8; 32],
+}
+
+impl ForsPublicKey {
+    /// A public key for `params` with the hash of the roots `root`.
+    pub fn new(params: ForsParams, root: [u8; 32]) -> Self {
+        ForsPublicKey { params, root }
+    }
+
+    /// The parameters of this public key.
+    pub fn params(&self) -> ForsParams {
+        self.params
+    }
+
+    /// The hash of the roots of the trees.
+    pub fn root(&self) -> [u8; 32] {
+        self.root
+    }
+
+    /// Verify a FORS `signature` on a `message` with this public key.
+    ///
+    /// Signatures carrying other parameters than the key are rejected.
+    pub fn verify(&self, message: &[u8], signature: &ForsSignature) -> bool {
+        let params = self.params;
+        if signature.params != params
+            || signature.leaves.len() != params.k
+            || signature
+                .leaves
+                .iter()
+                .any(|leaf| leaf.auth_path.len() != params.a)
+        {
+            return false;
+        }
+
+        let roots: Vec<[u8; 32]> = params
+            .indices(&sha256(message))
+            .into_iter()
+            .zip(signature.leaves.iter())
+            .map(|(index, leaf)| {
+                leaf.auth_path.iter().enumerate().fold(
+                    sha256(&leaf.secret),
+                    |node, (height, sibling)| {
+                        if (index >> height) & 1 == 0 {
+                            parent(&node, sibling)
+                        } else {
+                            parent(sibling, &node)
+                        }
+                    },
+                )
+            })
+            .collect();
+
+        sha256(roots.as_flattened()).ct_eq(&self.root).into()
+    }
+}
+
+/// One revealed leaf of a FORS signature.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ForsLeaf {
+    /// The secret of the leaf.
+    pub secret: [u8; 32],
+    /// The authentication path of the leaf, from the bottom up.
+    pub auth_path: Vec<[u8; 32]>,
+}
+
+/// A FORS signature, one revealed leaf per tree.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ForsSignature {
+    /// The parameters of the signing key.
+    pub params: ForsParams,
+    /// The revealed leaves, one per tree.
+    pub leaves: Vec<ForsLeaf>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn keypair() -> ForsKeypair {
+        ForsKeypair::from_seed([42u8; 32], ForsParams::new(8, 4).unwrap())
+    }
+
+    #[test]
+    fn signs_several_messages() {
+        let keypair = keypair();
+        let public_key = keypair.public_key();
+
+        for message in [&b"hello"[..], b"world", b"again"] {
+            let signature = keypair.sign(message);
+            assert_eq!(signature.leaves.len(), 8);
+            assert!(public_key.verify(message, &signature));
+        }
+
+        assert!(!public_key.verify(b"world", &keypair.sign(b"hello")));
+    }
+
+    #[test]
+    fn rejects_tampered_signature() {
+        let keypair = keypair();
+        let public_key = keypair.public_key();
+
+        let mut signature = keypair.sign(b"hello");
+        signature.leaves[3].secret[0] ^= 1;
+        assert!(!public_key.verify(b"hello", &signature));
+
+        let mut signature = keypair.sign(b"hello");
+        signature.leaves[0].auth_path.pop();
+        assert!(!public_key.verify(b"hello", &signature));
+    }
+
+    #[test]
+    fn rejects_signature_for_other_params() {
+        let keypair = keypair();
+        let public_key = keypair.public_key();
+        assert_eq!(public_key.params(), keypair.params());
+
+        // A key that commits to a lone tree whose root the forger chose.
+        let forged = ForsKeypair::from_seed([7u8; 32], ForsParams::new(1, 1).unwrap());
+        let signature = forged.sign(b"hello");
+        let claimed = ForsPublicKey::new(keypair.params(), forged.public_key().root());
+        assert!(!claimed.verify(b"hello", &signature));
+        assert!(forged.public_key().verify(b"hello", &signature));
+    }
+
+    #[test]
+    fn indices_read_digest_bits_in_order() {
+        let params = ForsParams::new(4, 4).unwrap();
+
+        let mut digest = [0u8; 32];
+        digest[..2].copy_from_slice(&[0x1f, 0xa5]);
+        assert_eq!(params.indices(&digest), [1, 15, 10, 5]);
+    }
+
+    #[test]
+    fn rejects_unsupported_params() {
+        assert_eq!(
+            ForsParams::new(16, 17),
+            Err(WotsError::UnsupportedW { w: 1 << 17 })
+        );
+        assert!(ForsParams::new(24, 11).is_err());
+        assert!(ForsParams::new(0, 11).is_err());
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn security_degrades_with_signatures() {
+        let params = ForsParams::default();
+
+        let bits: Vec<f64> = [1, 2, 10, 100]
+            .iter()
+            .map(|&r| params.security_bits(r))
+            .collect();
+        assert!((241.9..242.1).contains(&bits[0]), "{}", bits[0]);
+        assert!(bits.windows(2).all(|pair| pair[0] > pair[1]), "{:?}", bits);
+    }
+}
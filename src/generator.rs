@@ -0,0 +1,108 @@
+//! Deterministic, resumable generation of many WOTS keypairs.
+use crate::{digest32::Seed, keypair::Keypair};
+use alloc::vec::Vec;
+
+/// A generator deriving a sequence of keypairs from a single seed.
+///
+/// Keypair `i` is [`Keypair::derive`] of `seed` at `i`, so a generator and
+/// direct derivation from the same seed give the same keys. Any keypair can
+/// be derived on demand, so an
+/// interrupted process can resume from the last persisted index without
+/// regenerating the keypairs before it.
+pub struct KeyGenerator {
+    seed: Seed,
+    index: u64,
+}
+
+impl KeyGenerator {
+    /// Create a generator for the keypairs derived from `seed`, starting at
+    /// index 0.
+    pub fn new(seed: impl Into<Seed>) -> Self {
+        Self::resume(seed, 0)
+    }
+
+    /// Create a generator for the keypairs derived from `seed`, starting at
+    /// `index`.
+    pub fn resume(seed: impl Into<Seed>, index: u64) -> Self {
+        KeyGenerator {
+            seed: seed.into(),
+            index,
+        }
+    }
+
+    /// The index of the next keypair this generator produces.
+    pub fn index(&self) -> u64 {
+        self.index
+    }
+
+    /// Derive the keypair at `index`, independently of the generator's
+    /// position.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use wots_rs::KeyGenerator;
+    ///
+    /// let mut generator = KeyGenerator::new(&[7u8; 32]);
+    /// let keypairs = generator.generate_many(3);
+    ///
+    /// assert!(generator.key(2).public == keypairs[2].public);
+    ///
+    /// ```
+    pub fn key(&self, index: u64) -> Keypair {
+        Keypair::derive(&self.seed, index)
+    }
+
+    /// Generate the next `count` keypairs.
+    pub fn generate_many(&mut self, count: usize) -> Vec<Keypair> {
+        self.by_ref().take(count).collect()
+    }
+}
+
+impl Iterator for KeyGenerator {
+    type Item = Keypair;
+
+    fn next(&mut self) -> Option<Keypair> {
+        let keypair = self.key(self.index);
+        self.index = self.index.checked_add(1)?;
+
+        Some(keypair)
+    }
+
+    /// Skip `n` keypairs without deriving them.
+    fn nth(&mut self, n: usize) -> Option<Keypair> {
+        self.index = self.index.checked_add(n as u64)?;
+        self.next()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn nth_matches_generate_many() {
+        let seed = [42u8; 32];
+
+        let keypairs = KeyGenerator::new(seed).generate_many(6);
+        assert_eq!(keypairs.len(), 6);
+
+        let mut generator = KeyGenerator::new(seed);
+        let keypair = generator.nth(5).unwrap();
+        assert!(keypair.public == keypairs[5].public);
+        assert_eq!(generator.index(), 6);
+
+        let mut resumed = KeyGenerator::resume(seed, 3);
+        assert!(resumed.next().unwrap().public == keypairs[3].public);
+        assert!(keypairs[0].public != keypairs[1].public);
+    }
+
+    #[test]
+    fn matches_keypair_derive() {
+        let seed = [42u8; 32];
+
+        for (index, keypair) in KeyGenerator::new(seed).take(3).enumerate() {
+            assert!(keypair.public == Keypair::derive(seed, index as u64).public);
+        }
+    }
+}
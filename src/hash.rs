@@ -0,0 +1,489 @@
+//! The hash function underlying WOTS chains.
+//!
+//! SHA-256 is computed by `sha256_rs` by default. With the `sha256-core`
+//! feature, or without the `sha256-rs` feature, the `no_std`-compatible `sha2`
+//! implementation is used instead. Both produce identical output.
+//!
+//! `sha2` detects SHA-NI and the ARMv8 SHA extensions at runtime, which
+//! makes every chain step several times faster than `sha256_rs`. The `asm`
+//! feature selects `sha2` with its assembly implementation on top of that.
+use core::slice;
+use digest::{generic_array::GenericArray, Digest};
+
+/// Hash `data` with the SHA-256 primitive used for chains and message digests.
+pub(crate) fn sha256(data: &[u8]) -> [u8; 32] {
+    #[cfg(test)]
+    CALLS.with(|calls| calls.set(calls.get() + 1));
+
+    #[cfg(test)]
+    if let Some(fault) = FAULT.with(|fault| fault.get()) {
+        return fault(data);
+    }
+
+    backend(data)
+}
+
+#[cfg(all(feature = "sha256-rs", not(feature = "sha256-core")))]
+fn backend(data: &[u8]) -> [u8; 32] {
+    sha256_rs::sha256(data)
+}
+
+#[cfg(any(feature = "sha256-core", not(feature = "sha256-rs")))]
+fn backend(data: &[u8]) -> [u8; 32] {
+    use sha2::{Digest, Sha256};
+
+    Sha256::digest(data).into()
+}
+
+/// Compute HMAC-SHA256 of `message` under `key`, as in RFC 2104.
+pub(crate) fn hmac_sha256(key: &[u8], message: &[u8]) -> [u8; 32] {
+    let mut block = [0u8; 64];
+    if key.len() > 64 {
+        block[..32].copy_from_slice(&sha256(key));
+    } else {
+        block[..key.len()].copy_from_slice(key);
+    }
+
+    let mut input = alloc::vec::Vec::with_capacity(64 + message.len().max(32));
+    input.extend(block.iter().map(|byte| byte ^ 0x36));
+    input.extend_from_slice(message);
+    let inner = sha256(&input);
+
+    input.clear();
+    input.extend(block.iter().map(|byte| byte ^ 0x5c));
+    input.extend_from_slice(&inner);
+    let outer = sha256(&input);
+
+    input.fill(0);
+    block.fill(0);
+
+    outer
+}
+
+/// Apply `steps` chain hash steps to `x`.
+///
+/// The output is identical to calling [`sha256`] `steps` times.
+pub(crate) fn iterate(mut x: [u8; 32], steps: usize) -> [u8; 32] {
+    iterate_in_place(&mut x, steps);
+
+    x
+}
+
+/// Apply `steps` chain hash steps to `x` in place, like [`iterate`].
+///
+/// With the `sha2` backend each step is a single compression of a padded
+/// block, on a block and state owned here. With the `zeroize` feature both
+/// are wiped afterwards, as they hold the intermediate chain values.
+/// `sha256_rs` keeps its own buffers, which can't be wiped.
+pub(crate) fn iterate_in_place(x: &mut [u8; 32], steps: usize) {
+    #[cfg(test)]
+    if FAULT.with(|fault| fault.get()).is_some() {
+        for _ in 0..steps {
+            *x = sha256(x);
+        }
+
+        return;
+    }
+
+    #[cfg(any(feature = "sha256-core", not(feature = "sha256-rs")))]
+    {
+        #[cfg(test)]
+        CALLS.with(|calls| calls.set(calls.get() + steps));
+
+        const IV: [u32; 8] = [
+            0x6a09e667, 0xbb67ae85, 0x3c6ef372, 0xa54ff53a, 0x510e527f, 0x9b05688c, 0x1f83d9ab,
+            0x5be0cd19,
+        ];
+
+        // A 32-byte value pads to one block: the value, a one bit and its
+        // length of 256 bits.
+        let mut block = [0u8; 64];
+        block[32] = 0x80;
+        block[62] = 0x01;
+        let mut state = [0u32; 8];
+
+        for _ in 0..steps {
+            block[..32].copy_from_slice(x);
+            state.copy_from_slice(&IV);
+            sha2::compress256(
+                &mut state,
+                slice::from_ref(GenericArray::from_slice(&block)),
+            );
+
+            for (bytes, word) in x.chunks_exact_mut(4).zip(&state) {
+                bytes.copy_from_slice(&word.to_be_bytes());
+            }
+        }
+
+        #[cfg(feature = "zeroize")]
+        {
+            use zeroize::Zeroize;
+
+            block.zeroize();
+            state.zeroize();
+        }
+    }
+
+    #[cfg(all(feature = "sha256-rs", not(feature = "sha256-core")))]
+    for _ in 0..steps {
+        *x = sha256(x);
+    }
+}
+
+/// Hash the concatenation of `parts` with `D`, whose output is `N` bytes.
+///
+/// For chains of other lengths than the 32 bytes of [`sha256`], and counted
+/// and faulted like it in tests. A faulty hash is repeated to `N` bytes.
+pub(crate) fn digest<D: Digest, const N: usize>(parts: &[&[u8]]) -> [u8; N] {
+    #[cfg(test)]
+    CALLS.with(|calls| calls.set(calls.get() + 1));
+
+    #[cfg(test)]
+    if let Some(fault) = FAULT.with(|fault| fault.get()) {
+        let output = fault(&parts.concat());
+        return core::array::from_fn(|i| output[i % output.len()]);
+    }
+
+    let mut hasher = D::new();
+    for part in parts {
+        hasher.update(part);
+    }
+
+    let mut output = [0u8; N];
+    output.copy_from_slice(&hasher.finalize());
+
+    output
+}
+
+/// A hash whose chain steps on `N`-byte values are computed in place.
+pub(crate) trait ChainDigest<const N: usize>: Digest {
+    /// Apply `steps` chain steps to `x` in place, without the test hooks.
+    fn iterate_in_place(x: &mut [u8; N], steps: usize);
+}
+
+/// Each step is a single compression of a padded block, on a block and
+/// state that are wiped afterwards with the `zeroize` feature, like the
+/// SHA-256 steps of [`iterate_in_place`].
+impl ChainDigest<64> for sha2::Sha512 {
+    fn iterate_in_place(x: &mut [u8; 64], steps: usize) {
+        const IV: [u64; 8] = [
+            0x6a09e667f3bcc908,
+            0xbb67ae8584caa73b,
+            0x3c6ef372fe94f82b,
+            0xa54ff53a5f1d36f1,
+            0x510e527fade682d1,
+            0x9b05688c2b3e6c1f,
+            0x1f83d9abfb41bd6b,
+            0x5be0cd19137e2179,
+        ];
+
+        // A 64-byte value pads to one block: the value, a one bit and its
+        // length of 512 bits.
+        let mut block = [0u8; 128];
+        block[64] = 0x80;
+        block[126] = 0x02;
+        let mut state = [0u64; 8];
+
+        for _ in 0..steps {
+            block[..64].copy_from_slice(x);
+            state.copy_from_slice(&IV);
+            sha2::compress512(
+                &mut state,
+                slice::from_ref(GenericArray::from_slice(&block)),
+            );
+
+            for (bytes, word) in x.chunks_exact_mut(8).zip(&state) {
+                bytes.copy_from_slice(&word.to_be_bytes());
+            }
+        }
+
+        #[cfg(feature = "zeroize")]
+        {
+            use zeroize::Zeroize;
+
+            block.zeroize();
+            state.zeroize();
+        }
+    }
+}
+
+/// Apply `steps` chain steps of `D` to the `N`-byte `x` in place, like
+/// [`iterate_in_place`].
+pub(crate) fn iterate_digest<D: ChainDigest<N>, const N: usize>(x: &mut [u8; N], steps: usize) {
+    #[cfg(test)]
+    if FAULT.with(|fault| fault.get()).is_some() {
+        for _ in 0..steps {
+            *x = digest::<D, N>(&[x]);
+        }
+
+        return;
+    }
+
+    #[cfg(test)]
+    CALLS.with(|calls| calls.set(calls.get() + steps));
+
+    D::iterate_in_place(x, steps);
+}
+
+/// Set each of the `N`-byte `chains` to `start(i)` advanced by `steps(i)`
+/// chain steps of `D`, through [`for_each_chain`].
+pub(crate) fn iterate_digest_chains<D, const N: usize, S, F>(
+    chains: &mut [[u8; N]],
+    start: S,
+    steps: F,
+) where
+    D: ChainDigest<N>,
+    S: Fn(usize) -> [u8; N] + Send + Sync,
+    F: Fn(usize) -> usize + Send + Sync,
+{
+    for_each_chain(chains, |i, x| {
+        *x = start(i);
+        iterate_digest::<D, N>(x, steps(i));
+    });
+}
+
+/// A hash of arbitrary data to 32 bytes.
+#[cfg(test)]
+type HashFn = fn(&[u8]) -> [u8; 32];
+
+/// Hash everything read from `reader` with SHA-256, in fixed-size blocks.
+#[cfg(feature = "std")]
+pub(crate) fn sha256_reader(mut reader: impl std::io::Read) -> std::io::Result<[u8; 32]> {
+    use sha2::{Digest, Sha256};
+
+    let mut hasher = Sha256::new();
+    let mut buffer = [0u8; 4096];
+
+    loop {
+        match reader.read(&mut buffer) {
+            Ok(0) => return Ok(hasher.finalize().into()),
+            Ok(n) => hasher.update(&buffer[..n]),
+            Err(error) if error.kind() == std::io::ErrorKind::Interrupted => {}
+            Err(error) => return Err(error),
+        }
+    }
+}
+
+/// Set each of `chains` to `start(i)` advanced by `steps(i)` hash steps.
+///
+/// With the `multi-buffer` feature enabled the chains are hashed in
+/// lockstep lanes, otherwise one by one through [`for_each_chain`]. With
+/// `rayon` as well, each run of [`LANES`](crate::multibuffer::LANES)
+/// chains gets its own lanes on the rayon thread pool. The output is
+/// identical either way.
+pub(crate) fn iterate_chains<S, N>(chains: &mut [[u8; 32]], start: S, steps: N)
+where
+    S: Fn(usize) -> [u8; 32] + Send + Sync,
+    N: Fn(usize) -> usize + Send + Sync,
+{
+    #[cfg(feature = "multi-buffer")]
+    {
+        #[cfg(test)]
+        let fault = FAULT.with(|fault| fault.get()).is_some();
+        #[cfg(not(test))]
+        let fault = false;
+
+        if !fault {
+            #[cfg(test)]
+            CALLS.with(|calls| {
+                calls.set(calls.get() + (0..chains.len()).map(&steps).sum::<usize>())
+            });
+
+            #[cfg(feature = "rayon")]
+            {
+                use crate::multibuffer::{iterate_lanes, LANES};
+                use rayon::prelude::*;
+
+                chains
+                    .par_chunks_mut(LANES)
+                    .enumerate()
+                    .for_each(|(chunk, lanes)| {
+                        let offset = chunk * LANES;
+                        for (i, x) in lanes.iter_mut().enumerate() {
+                            *x = start(offset + i);
+                        }
+
+                        iterate_lanes(lanes, |i| steps(offset + i));
+                    });
+            }
+
+            #[cfg(not(feature = "rayon"))]
+            {
+                for (i, x) in chains.iter_mut().enumerate() {
+                    *x = start(i);
+                }
+
+                crate::multibuffer::iterate_lanes(chains, steps);
+            }
+
+            return;
+        }
+    }
+
+    for_each_chain(chains, |i, x| {
+        *x = start(i);
+        iterate_in_place(x, steps(i));
+    });
+}
+
+/// Apply `f` to each chain value in `chains` along with its index.
+///
+/// With the `rayon` feature enabled the chains are processed in parallel;
+/// the output is identical either way.
+pub(crate) fn for_each_chain<T, F>(chains: &mut [T], f: F)
+where
+    T: Send,
+    F: Fn(usize, &mut T) + Send + Sync,
+{
+    #[cfg(feature = "rayon")]
+    {
+        use rayon::prelude::*;
+
+        // Hash counting and fault injection are per thread, so carry them
+        // over to the worker threads.
+        #[cfg(test)]
+        let (fault, calls) = (
+            FAULT.with(|fault| fault.get()),
+            core::sync::atomic::AtomicUsize::new(0),
+        );
+
+        chains.par_iter_mut().enumerate().for_each(|(i, x)| {
+            #[cfg(test)]
+            {
+                let previous = FAULT.with(|cell| cell.replace(fault));
+                let (_, hashes) = count_hashes(|| f(i, x));
+                FAULT.with(|cell| cell.set(previous));
+
+                CALLS.with(|cell| cell.set(cell.get() - hashes));
+                calls.fetch_add(hashes, core::sync::atomic::Ordering::Relaxed);
+            }
+
+            #[cfg(not(test))]
+            f(i, x);
+        });
+
+        #[cfg(test)]
+        CALLS.with(|cell| cell.set(cell.get() + calls.into_inner()));
+    }
+
+    #[cfg(not(feature = "rayon"))]
+    for (i, x) in chains.iter_mut().enumerate() {
+        f(i, x);
+    }
+}
+
+#[cfg(test)]
+std::thread_local! {
+    /// The number of hashes computed on this thread, for bounding work in tests.
+    static CALLS: core::cell::Cell<usize> = const { core::cell::Cell::new(0) };
+
+    /// A faulty hash replacing SHA-256 on this thread, for testing error paths.
+    static FAULT: core::cell::Cell<Option<HashFn>> = const { core::cell::Cell::new(None) };
+}
+
+/// Count the hashes computed on this thread while running `f`.
+#[cfg(test)]
+pub(crate) fn count_hashes<T>(f: impl FnOnce() -> T) -> (T, usize) {
+    let before = CALLS.with(|calls| calls.get());
+    let result = f();
+
+    (result, CALLS.with(|calls| calls.get()) - before)
+}
+
+/// Run `f` with every hash on this thread computed by `fault` instead of
+/// SHA-256.
+#[cfg(test)]
+pub(crate) fn with_fault_hash<T>(fault: HashFn, f: impl FnOnce() -> T) -> T {
+    let previous = FAULT.with(|cell| cell.replace(Some(fault)));
+    let result = f();
+    FAULT.with(|cell| cell.set(previous));
+
+    result
+}
+
+/// The hash function used for every chain step and message digest.
+///
+/// Protocols that commit to public keys can use this to hash with exactly
+/// the primitive the crate uses internally.
+///
+/// # Example
+///
+/// ```
+/// use wots_rs::chain_hash_fn;
+///
+/// let hash = chain_hash_fn();
+/// let commitment = hash(b"hello");
+///
+/// ```
+pub fn chain_hash_fn() -> fn(&[u8]) -> [u8; 32] {
+    sha256
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use sha2::{Digest, Sha256};
+
+    #[test]
+    fn implementations_agree() {
+        let inputs: [&[u8]; 5] = [b"", b"x", b"hello", &[0xab; 55], &[0xcd; 1000]];
+
+        for input in inputs {
+            let expected: [u8; 32] = Sha256::digest(input).into();
+
+            assert_eq!(sha256_rs::sha256(input), expected);
+            assert_eq!(sha256(input), expected);
+        }
+    }
+
+    #[test]
+    fn iterate_matches_repeated_hashing() {
+        let mut x = [7u8; 32];
+
+        for steps in 0..20 {
+            assert_eq!(iterate([7u8; 32], steps), x);
+            x = sha256(&x);
+        }
+
+        let (_, hashes) = count_hashes(|| iterate(x, 5));
+        assert_eq!(hashes, 5);
+    }
+
+    #[test]
+    fn sha512_steps_match_repeated_hashing() {
+        use sha2::Sha512;
+
+        let mut x = [7u8; 64];
+        let mut expected = x;
+        for steps in 0..5 {
+            let mut y = [7u8; 64];
+            iterate_digest::<Sha512, 64>(&mut y, steps);
+            assert_eq!(y, expected);
+
+            let next = Sha512::digest(expected);
+            expected.copy_from_slice(&next);
+        }
+
+        let (_, hashes) = count_hashes(|| iterate_digest::<Sha512, 64>(&mut x, 3));
+        assert_eq!(hashes, 3);
+    }
+
+    #[test]
+    fn hmac_matches_rfc4231() {
+        assert_eq!(
+            hmac_sha256(b"Jefe", b"what do ya want for nothing?"),
+            crate::hex::decode("5bdcc146bf60754e6a042426089575c75a003f089d2739839dec58b964ec3843")
+                .unwrap()
+                .as_slice()
+        );
+        assert_eq!(
+            hmac_sha256(
+                &[0xaa; 131],
+                b"Test Using Larger Than Block-Size Key - Hash Key First"
+            ),
+            crate::hex::decode("60e431591ee0b67f0d8a26aacbf5b77f8e0bc6213728c5140546040f0ee37f54")
+                .unwrap()
+                .as_slice()
+        );
+    }
+}
@@ -0,0 +1,43 @@
+//! Hexadecimal encoding of key and signature bytes.
+use alloc::{format, string::String, vec::Vec};
+use core::fmt::Write;
+
+/// Encode `bytes` as lowercase hexadecimal.
+pub(crate) fn encode(bytes: &[u8]) -> String {
+    let mut hex = String::with_capacity(bytes.len() * 2);
+
+    for byte in bytes {
+        write!(hex, "{:02x}", byte).unwrap();
+    }
+
+    hex
+}
+
+/// Decode lowercase or uppercase hexadecimal into bytes.
+///
+/// Returns `None` if `hex` has an odd length or contains a non-hex digit.
+pub(crate) fn decode(hex: &str) -> Option<Vec<u8>> {
+    if !hex.len().is_multiple_of(2) {
+        return None;
+    }
+
+    hex.as_bytes()
+        .chunks_exact(2)
+        .map(|pair| {
+            let high = (pair[0] as char).to_digit(16)?;
+            let low = (pair[1] as char).to_digit(16)?;
+
+            Some((high << 4 | low) as u8)
+        })
+        .collect()
+}
+
+/// Render `chains` one per line as `chain[i]: <hex>`, for debugging.
+pub(crate) fn chains_debug_string(chains: &[[u8; 32]]) -> String {
+    chains
+        .iter()
+        .enumerate()
+        .map(|(i, chain)| format!("chain[{}]: {}", i, encode(chain)))
+        .collect::<Vec<_>>()
+        .join("\n")
+}
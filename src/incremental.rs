@@ -0,0 +1,178 @@
+//! WOTS signing and verification of messages supplied in pieces.
+use crate::{keypair::Keypair, public::PublicKey, secret::SecretKey, signature::Signature};
+use sha2::{Digest, Sha256};
+
+/// Signs a message fed to it in pieces, hashing them as they arrive.
+///
+/// The signature is identical to signing the concatenation of the pieces
+/// with [`SecretKey::sign`]. With the `std` feature the context is also an
+/// [`std::io::Write`], so a file can be hashed with [`std::io::copy`].
+///
+/// # Example
+///
+/// ```
+/// use wots_rs::Keypair;
+///
+/// let keypair = Keypair::from_seed(&[7u8; 32]);
+///
+/// let mut signer = keypair.signing_context();
+/// signer.update(b"hello");
+/// signer.update(b" world");
+/// let signature = signer.finalize();
+///
+/// let mut verifier = keypair.public.verifying_context();
+/// verifier.update(b"hello world");
+/// assert!(verifier.finalize(&signature));
+///
+/// ```
+pub struct SigningContext<'a> {
+    secret_key: &'a SecretKey,
+    hasher: Sha256,
+}
+
+impl<'a> SigningContext<'a> {
+    /// Start signing a message with `secret_key`.
+    pub fn new(secret_key: &'a SecretKey) -> Self {
+        SigningContext {
+            secret_key,
+            hasher: Sha256::new(),
+        }
+    }
+
+    /// Append `data` to the message.
+    pub fn update(&mut self, data: &[u8]) {
+        self.hasher.update(data);
+    }
+
+    /// Sign the message fed so far.
+    pub fn finalize(self) -> Signature {
+        self.secret_key
+            .sign_prehashed(&self.hasher.finalize().into())
+    }
+}
+
+/// Verifies a signature on a message fed to it in pieces.
+///
+/// The result is identical to verifying the concatenation of the pieces
+/// with [`PublicKey::verify`]. With the `std` feature the context is also
+/// an [`std::io::Write`].
+pub struct VerifyingContext<'a> {
+    public_key: &'a PublicKey,
+    hasher: Sha256,
+}
+
+impl<'a> VerifyingContext<'a> {
+    /// Start verifying a message against `public_key`.
+    pub fn new(public_key: &'a PublicKey) -> Self {
+        VerifyingContext {
+            public_key,
+            hasher: Sha256::new(),
+        }
+    }
+
+    /// Append `data` to the message.
+    pub fn update(&mut self, data: &[u8]) {
+        self.hasher.update(data);
+    }
+
+    /// Verify a `signature` on the message fed so far.
+    pub fn finalize(self, signature: &Signature) -> bool {
+        self.public_key
+            .verify_prehashed(&self.hasher.finalize().into(), signature)
+    }
+}
+
+impl SecretKey {
+    /// Start signing a message supplied in pieces.
+    ///
+    /// See [`SigningContext`].
+    pub fn signing_context(&self) -> SigningContext<'_> {
+        SigningContext::new(self)
+    }
+}
+
+impl PublicKey {
+    /// Start verifying a message supplied in pieces.
+    ///
+    /// See [`VerifyingContext`].
+    pub fn verifying_context(&self) -> VerifyingContext<'_> {
+        VerifyingContext::new(self)
+    }
+}
+
+impl Keypair {
+    /// Start signing a message supplied in pieces.
+    ///
+    /// See [`SigningContext`].
+    pub fn signing_context(&self) -> SigningContext<'_> {
+        self.secret.signing_context()
+    }
+}
+
+/// Feed written bytes into the message being signed.
+#[cfg(feature = "std")]
+impl std::io::Write for SigningContext<'_> {
+    fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+        self.update(buf);
+        Ok(buf.len())
+    }
+
+    fn flush(&mut self) -> std::io::Result<()> {
+        Ok(())
+    }
+}
+
+/// Feed written bytes into the message being verified.
+#[cfg(feature = "std")]
+impl std::io::Write for VerifyingContext<'_> {
+    fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+        self.update(buf);
+        Ok(buf.len())
+    }
+
+    fn flush(&mut self) -> std::io::Result<()> {
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn matches_one_shot_signing() {
+        let keypair = Keypair::from_seed([7u8; 32]);
+        let message = [0xabu8; 1000];
+
+        let mut signer = keypair.signing_context();
+        for chunk in message.chunks(77) {
+            signer.update(chunk);
+        }
+        let signature = signer.finalize();
+        assert!(signature == keypair.sign_unchecked(&message));
+
+        let mut verifier = keypair.public.verifying_context();
+        verifier.update(&message[..500]);
+        verifier.update(&message[500..]);
+        assert!(verifier.finalize(&signature));
+
+        let mut verifier = keypair.public.verifying_context();
+        verifier.update(&message[..500]);
+        assert!(!verifier.finalize(&signature));
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn accepts_io_copy() {
+        let keypair = Keypair::from_seed([7u8; 32]);
+        let message = [0xcdu8; 10_000];
+
+        let mut signer = keypair.signing_context();
+        std::io::copy(&mut &message[..], &mut signer).unwrap();
+        let signature = signer.finalize();
+
+        let mut verifier = keypair.public.verifying_context();
+        std::io::copy(&mut &message[..], &mut verifier).unwrap();
+        assert!(verifier.finalize(&signature));
+    }
+}
@@ -1,13 +1,38 @@
 //! WOTS keypairs.
-use crate::{public::PublicKey, secret::SecretKey, signature::Signature};
+use crate::{
+    constants::{CHAINS, MESSAGE_CHAINS, SIGNATURE_LENGTH},
+    counter::{counter_digest, CounterSignature},
+    digest32::Seed,
+    error::WotsError,
+    public::PublicKey,
+    randomized::{randomized_digest, RandomizedSignature},
+    secret::{wipe, SecretKey},
+    signature::Signature,
+};
+use alloc::{string::String, vec::Vec};
 use rand::{CryptoRng, RngCore};
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+#[cfg(feature = "zeroize")]
+use zeroize::{Zeroize, ZeroizeOnDrop};
 
 /// An WOTS keypair.
-pub struct Keypair {
+///
+/// Generic over the length `N` of its chain values and its number of chains
+/// `L`; see [`Sha512Keypair`](crate::Sha512Keypair) for 64-byte chains.
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct Keypair<const N: usize = 32, const L: usize = CHAINS> {
     /// The secret half of this keypair.
-    pub secret: SecretKey,
+    ///
+    /// Public for compatibility, so `keypair.secret.sign(message)` still
+    /// signs without consuming the keypair, as
+    /// [`sign_unchecked`](Keypair::sign_unchecked) does. Code enforcing
+    /// one-time use should not expose the `Keypair` itself, but sign with
+    /// [`sign_once`](Keypair::sign_once) or hold a
+    /// [`OneTimeKeypair`](crate::OneTimeKeypair).
+    pub secret: SecretKey<N, L>,
     /// The public half of this keypair.
-    pub public: PublicKey,
+    pub public: PublicKey<N, L>,
 }
 
 impl Keypair {
@@ -40,6 +65,215 @@ impl Keypair {
         }
     }
 
+    /// Generate `count` WOTS keypairs, reporting progress.
+    ///
+    /// `on_progress` is invoked after each keypair with the number of
+    /// keypairs generated so far, from 1 up to `count`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use rand::rngs::OsRng;
+    /// use wots_rs::Keypair;
+    ///
+    /// let mut csprng = OsRng{};
+    /// let keypairs = Keypair::generate_many_with_progress(&mut csprng, 3, |done| {
+    ///     println!("{}/3 keypairs generated", done);
+    /// });
+    ///
+    /// assert_eq!(keypairs.len(), 3);
+    ///
+    /// ```
+    pub fn generate_many_with_progress<R>(
+        csprng: &mut R,
+        count: usize,
+        mut on_progress: impl FnMut(usize),
+    ) -> Vec<Self>
+    where
+        R: CryptoRng + RngCore,
+    {
+        (1..=count)
+            .map(|done| {
+                let keypair = Keypair::generate(csprng);
+                on_progress(done);
+                keypair
+            })
+            .collect()
+    }
+
+    /// Generate `count` WOTS keypairs at once.
+    ///
+    /// Each secret key is drawn from the `csprng` in a single call rather
+    /// than one call per chain. With the `rayon` feature enabled the public
+    /// keys are then computed in parallel.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use rand::rngs::OsRng;
+    /// use wots_rs::Keypair;
+    ///
+    /// let mut csprng = OsRng{};
+    /// let keypairs = Keypair::generate_batch(&mut csprng, 3);
+    ///
+    /// assert_eq!(keypairs.len(), 3);
+    ///
+    /// ```
+    pub fn generate_batch<R>(csprng: &mut R, count: usize) -> Vec<Self>
+    where
+        R: CryptoRng + RngCore,
+    {
+        let mut bytes = [0u8; SIGNATURE_LENGTH];
+        let secrets = (0..count)
+            .map(|_| {
+                csprng.fill_bytes(&mut bytes);
+                SecretKey::from_bytes(&bytes).unwrap()
+            })
+            .collect::<Vec<_>>();
+        wipe(&mut bytes);
+
+        #[cfg(feature = "rayon")]
+        {
+            use rayon::prelude::*;
+
+            secrets.into_par_iter().map(Keypair::from).collect()
+        }
+
+        #[cfg(not(feature = "rayon"))]
+        secrets.into_iter().map(Keypair::from).collect()
+    }
+
+    /// Generate an WOTS keypair, checking the drawn entropy.
+    ///
+    /// See [`SecretKey::try_generate`].
+    ///
+    /// # Errors
+    ///
+    /// Returns `WotsError::RngFailure` if the `csprng` fails or its output
+    /// looks weak.
+    pub fn try_generate<R>(csprng: &mut R) -> Result<Self, WotsError>
+    where
+        R: CryptoRng + RngCore,
+    {
+        Ok(Keypair::from(SecretKey::try_generate(csprng)?))
+    }
+
+    /// Generate an WOTS keypair domain-separated by a `device_id`.
+    ///
+    /// See [`SecretKey::generate_for_device`] for the derivation.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use rand::rngs::OsRng;
+    /// use wots_rs::Keypair;
+    ///
+    /// let mut csprng = OsRng{};
+    /// let keypair: Keypair = Keypair::generate_for_device(&mut csprng, b"device-1");
+    ///
+    /// ```
+    pub fn generate_for_device<R>(csprng: &mut R, device_id: &[u8]) -> Self
+    where
+        R: CryptoRng + RngCore,
+    {
+        let sk = SecretKey::generate_for_device(csprng, device_id);
+        let pk = PublicKey::from(&sk);
+
+        Keypair {
+            secret: sk,
+            public: pk,
+        }
+    }
+
+    /// Derive an WOTS keypair deterministically from a 32-byte `seed`.
+    ///
+    /// See [`SecretKey::from_seed`] for the derivation.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use wots_rs::Keypair;
+    ///
+    /// let seed = [7u8; 32];
+    /// let keypair: Keypair = Keypair::from_seed(&seed);
+    ///
+    /// assert!(keypair.public == Keypair::from_seed(&seed).public);
+    ///
+    /// ```
+    pub fn from_seed(seed: impl Into<Seed>) -> Self {
+        let sk = SecretKey::from_seed(seed);
+        let pk = PublicKey::from(&sk);
+
+        Keypair {
+            secret: sk,
+            public: pk,
+        }
+    }
+
+    /// Derive an WOTS keypair from a 32-byte `seed`, without an RNG.
+    ///
+    /// The same as [`from_seed`](Self::from_seed), taking the seed by
+    /// value.
+    pub fn generate_from_seed(seed: [u8; 32]) -> Self {
+        Keypair::from_seed(seed)
+    }
+
+    /// Derive an WOTS keypair from caller-supplied `entropy`, without an
+    /// RNG.
+    ///
+    /// See [`SecretKey::from_entropy`].
+    ///
+    /// # Errors
+    ///
+    /// Returns `WotsError::LengthMismatch` if `entropy` is shorter than 32
+    /// bytes.
+    pub fn from_entropy(entropy: &[u8]) -> Result<Self, WotsError> {
+        Ok(Keypair::from(SecretKey::from_entropy(entropy)?))
+    }
+
+    /// Derive the WOTS keypair at `index` from a 32-byte `master_seed`.
+    ///
+    /// See [`SecretKey::derive`] for the derivation.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use wots_rs::{Keypair, MerkleKeypair};
+    ///
+    /// let master_seed = [7u8; 32];
+    /// let tree: MerkleKeypair = (0..4).map(|i| Keypair::derive(&master_seed, i)).collect();
+    ///
+    /// let signature = tree.sign(2, b"hello").unwrap();
+    /// assert!(signature.signature == Keypair::derive(&master_seed, 2).sign(b"hello"));
+    ///
+    /// ```
+    pub fn derive(master_seed: impl Into<Seed>, index: u64) -> Self {
+        Keypair::from(SecretKey::derive(master_seed, index))
+    }
+
+    /// Rebuild the `Keypair` of a stored `secret` key, recomputing its
+    /// public half.
+    ///
+    /// The public key costs one full pass over every chain, so a caller
+    /// rebuilding the same keypair often should keep the `Keypair` rather
+    /// than its `SecretKey` alone. This is the same as
+    /// `Keypair::from(secret)`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use wots_rs::{Keypair, SecretKey};
+    ///
+    /// let secret = SecretKey::from_seed(&[7u8; 32]);
+    /// let keypair = Keypair::from_secret(secret);
+    ///
+    /// assert!(keypair.public == Keypair::from_seed(&[7u8; 32]).public);
+    ///
+    /// ```
+    pub fn from_secret(secret: SecretKey) -> Self {
+        Keypair::from(secret)
+    }
+
     /// Sign a `message` with this `Keypair` using the
     /// WOTS algorithm.
     ///
@@ -51,6 +285,12 @@ impl Keypair {
     ///
     /// An WOTS [`Signature`] on the `message`.
     ///
+    /// Nothing stops a second call signing another message, which breaks
+    /// the one-time key, so this is deprecated in favour of
+    /// [`sign_once`](Self::sign_once), or of
+    /// [`sign_unchecked`](Self::sign_unchecked) where the caller tracks
+    /// key use itself.
+    ///
     /// # Example
     ///
     /// ```
@@ -64,10 +304,174 @@ impl Keypair {
     /// let signature = keypair.sign(message);
     ///
     /// ```
+    #[deprecated(note = "use sign_once or sign_unchecked")]
     pub fn sign(&self, message: &[u8]) -> Signature {
         self.secret.sign(message)
     }
 
+    /// Sign a `message`, consuming this `Keypair`.
+    ///
+    /// The secret half is dropped once the signature is made, so the type
+    /// system rules out a second signature with it. The public half is
+    /// handed back for verifying the signature.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use wots_rs::Keypair;
+    ///
+    /// let keypair = Keypair::from_seed(&[7u8; 32]);
+    ///
+    /// let (signature, public_key) = keypair.sign_once(b"hello");
+    ///
+    /// assert!(public_key.verify(b"hello", signature));
+    ///
+    /// ```
+    pub fn sign_once(self, message: &[u8]) -> (Signature, PublicKey) {
+        (self.secret.sign(message), self.public)
+    }
+
+    /// Sign a `message` without consuming this `Keypair`.
+    ///
+    /// Nothing stops this key signing a second message, which the name
+    /// makes plain at the call site.
+    /// Prefer [`sign_once`](Self::sign_once) where the key is not needed
+    /// afterwards.
+    pub fn sign_unchecked(&self, message: &[u8]) -> Signature {
+        self.secret.sign(message)
+    }
+
+    /// Sign a `message` with the secret half of this `Keypair`.
+    ///
+    /// This is equivalent to `keypair.secret.sign(message)`, but keeps call
+    /// sites free of field access. Deprecated like [`sign`](Self::sign).
+    #[deprecated(note = "use sign_once or sign_unchecked")]
+    pub fn secret_sign(&self, message: &[u8]) -> Signature {
+        self.secret.sign(message)
+    }
+
+    /// Sign a message read from `reader` with this `Keypair`.
+    ///
+    /// See [`SecretKey::sign_stream`].
+    #[cfg(feature = "std")]
+    pub fn sign_stream(&self, reader: impl std::io::Read) -> std::io::Result<Signature> {
+        self.secret.sign_stream(reader)
+    }
+
+    /// Sign a `message` bound to an application `context`, e.g. `b"appA"`.
+    ///
+    /// A signature made under one context doesn't verify under another,
+    /// and no context, the empty one included, can be confused with
+    /// [`sign`](Self::sign).
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use wots_rs::Keypair;
+    ///
+    /// let keypair = Keypair::from_seed(&[7u8; 32]);
+    ///
+    /// let message = b"hello";
+    /// let signature = keypair.sign_with_context(message, b"appA");
+    ///
+    /// assert!(keypair.verify_with_context(message, b"appA", &signature));
+    /// assert!(!keypair.verify_with_context(message, b"appB", &signature));
+    ///
+    /// ```
+    pub fn sign_with_context(&self, message: &[u8], context: &[u8]) -> Signature {
+        self.secret.sign_with_context(message, context)
+    }
+
+    /// Sign a `message` committing to a monotonic `counter`, so a verifier
+    /// can order the signatures of one signer.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use wots_rs::Keypair;
+    ///
+    /// let keypair = Keypair::from_seed(&[7u8; 32]);
+    ///
+    /// let message = b"hello";
+    /// let signature = keypair.sign_counter(message, 42);
+    ///
+    /// assert_eq!(keypair.public.verify_counter(message, &signature), Some(42));
+    ///
+    /// ```
+    pub fn sign_counter(&self, message: &[u8], counter: u64) -> CounterSignature {
+        CounterSignature {
+            counter,
+            signature: self.secret.sign_digits(&counter_digest(message, counter)),
+        }
+    }
+
+    /// Sign a message given by its SHA-256 `digest` with this `Keypair`.
+    ///
+    /// See [`SecretKey::sign_prehashed`].
+    pub fn sign_prehashed(&self, digest: &[u8; 32]) -> Signature {
+        self.secret.sign_prehashed(digest)
+    }
+
+    /// Sign a randomized hash of a `message`, drawing the randomizer from
+    /// `csprng`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use rand::rngs::OsRng;
+    /// use wots_rs::Keypair;
+    ///
+    /// let keypair = Keypair::from_seed(&[7u8; 32]);
+    ///
+    /// let message = b"hello";
+    /// let signature = keypair.sign_randomized(&mut OsRng, message);
+    ///
+    /// assert!(keypair.public.verify_randomized(message, &signature));
+    ///
+    /// ```
+    pub fn sign_randomized<R>(&self, csprng: &mut R, message: &[u8]) -> RandomizedSignature
+    where
+        R: CryptoRng + RngCore,
+    {
+        let mut r = [0u8; 32];
+        csprng.fill_bytes(&mut r);
+
+        RandomizedSignature {
+            r,
+            signature: self.secret.sign_digits(&randomized_digest(message, &r)),
+        }
+    }
+
+    /// Sign an explicit array of base-`w` `digits` with this `Keypair`.
+    ///
+    /// See [`SecretKey::sign_digits`].
+    pub fn sign_digits(&self, digits: &[u8; MESSAGE_CHAINS]) -> Signature {
+        self.secret.sign_digits(digits)
+    }
+
+    /// Sign a `message` with this `Keypair`, writing the signature directly
+    /// into `out` in its flat byte representation.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use rand::rngs::OsRng;
+    /// use wots_rs::{Keypair, SIGNATURE_LENGTH};
+    ///
+    /// let mut csprng = OsRng{};
+    /// let keypair: Keypair = Keypair::generate(&mut csprng);
+    ///
+    /// let message = b"hello";
+    /// let mut signature = [0u8; SIGNATURE_LENGTH];
+    /// keypair.sign_into(message, &mut signature);
+    ///
+    /// assert_eq!(signature, keypair.sign_unchecked(message).to_flat_bytes());
+    ///
+    /// ```
+    pub fn sign_into(&self, message: &[u8], out: &mut [u8; SIGNATURE_LENGTH]) {
+        self.secret.sign_into(message, out)
+    }
+
     /// Verify a `signature` on a `message` using the WOTS algorithm.
     ///
     /// # Inputs
@@ -90,7 +494,7 @@ impl Keypair {
     /// let keypair: Keypair = Keypair::generate(&mut csprng);
     ///
     /// let message = b"hello";
-    /// let signature = keypair.sign(message);
+    /// let signature = keypair.sign_unchecked(message);
     ///
     /// assert!(keypair.verify(message, signature));
     ///
@@ -98,4 +502,156 @@ impl Keypair {
     pub fn verify(&self, message: &[u8], signature: Signature) -> bool {
         self.public.verify(message, signature)
     }
+
+    /// Verify a `signature` on a message given by its SHA-256 `digest`.
+    ///
+    /// See [`PublicKey::verify_prehashed`].
+    pub fn verify_prehashed(&self, digest: &[u8; 32], signature: &Signature) -> bool {
+        self.public.verify_prehashed(digest, signature)
+    }
+
+    /// Verify a `signature` on an explicit array of base-`w` `digits`.
+    ///
+    /// See [`PublicKey::verify_digits`].
+    pub fn verify_digits(&self, digits: &[u8; MESSAGE_CHAINS], signature: &Signature) -> bool {
+        self.public.verify_digits(digits, signature)
+    }
+
+    /// Verify a `signature` on a `message` supplied as a sequence of `chunks`.
+    ///
+    /// See [`PublicKey::verify_chunks`].
+    pub fn verify_chunks(&self, chunks: &[&[u8]], signature: &Signature) -> bool {
+        self.public.verify_chunks(chunks, signature)
+    }
+
+    /// Verify a `signature` on a message read from `reader`.
+    ///
+    /// See [`PublicKey::verify_stream`].
+    #[cfg(feature = "std")]
+    pub fn verify_stream(
+        &self,
+        reader: impl std::io::Read,
+        signature: &Signature,
+    ) -> std::io::Result<bool> {
+        self.public.verify_stream(reader, signature)
+    }
+
+    /// Verify a `signature` on a `message` bound to an application `context`.
+    ///
+    /// See [`PublicKey::verify_with_context`].
+    pub fn verify_with_context(
+        &self,
+        message: &[u8],
+        context: &[u8],
+        signature: &Signature,
+    ) -> bool {
+        self.public.verify_with_context(message, context, signature)
+    }
+
+    /// Verify a batch of `(message, signature)` pairs against this `Keypair`,
+    /// reporting the result of each.
+    ///
+    /// See [`PublicKey::verify_batch`].
+    pub fn verify_batch(&self, items: &[(&[u8], &Signature)]) -> Vec<bool> {
+        self.public.verify_batch(items)
+    }
+
+    /// Verify a batch of `(message, signature)` pairs against this `Keypair`.
+    ///
+    /// See [`PublicKey::verify_batch_all`].
+    pub fn verify_batch_all(&self, items: &[(&[u8], &Signature)], early_exit: bool) -> bool {
+        self.public.verify_batch_all(items, early_exit)
+    }
+
+    /// Verify a `signature` on a `message`, reporting why verification failed.
+    ///
+    /// See [`PublicKey::verify_strict`].
+    ///
+    /// # Inputs
+    ///
+    /// * `message` in bytes representation.
+    /// * `signature` is a purported WOTS [`Signature`] on the `message`.
+    ///
+    /// # Returns
+    ///
+    /// Returns `Ok(())` if the `signature` was a valid signature created by this
+    /// `Keypair` on the `message`, or `WotsError::ChainMismatch` naming the first
+    /// chain that failed to verify.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use rand::rngs::OsRng;
+    /// use wots_rs::Keypair;
+    ///
+    /// let mut csprng = OsRng{};
+    /// let keypair: Keypair = Keypair::generate(&mut csprng);
+    ///
+    /// let message = b"hello";
+    /// let signature = keypair.sign_unchecked(message);
+    ///
+    /// assert!(keypair.verify_strict(message, &signature).is_ok());
+    ///
+    /// ```
+    pub fn verify_strict(&self, message: &[u8], signature: &Signature) -> Result<(), WotsError> {
+        self.public.verify_strict(message, signature)
+    }
+
+    /// Fingerprint the public half of this keypair.
+    ///
+    /// See [`PublicKey::fingerprint`].
+    pub fn fingerprint(&self) -> [u8; 32] {
+        self.public.fingerprint()
+    }
+
+    /// The short hex form of the [`fingerprint`](Self::fingerprint).
+    ///
+    /// See [`PublicKey::short_fingerprint`].
+    pub fn short_fingerprint(&self) -> String {
+        self.public.short_fingerprint()
+    }
+}
+
+/// Construct a `Keypair` from a `SecretKey`, deriving its public half.
+impl From<SecretKey> for Keypair {
+    fn from(value: SecretKey) -> Self {
+        let pk = PublicKey::from(&value);
+
+        Keypair {
+            secret: value,
+            public: pk,
+        }
+    }
+}
+
+/// Overwrite the secret half of a `Keypair` with zeros.
+#[cfg(feature = "zeroize")]
+impl<const N: usize, const L: usize> Zeroize for Keypair<N, L> {
+    fn zeroize(&mut self) {
+        self.secret.zeroize();
+    }
+}
+
+/// The secret half of a `Keypair` is wiped when it is dropped.
+#[cfg(feature = "zeroize")]
+impl<const N: usize, const L: usize> ZeroizeOnDrop for Keypair<N, L> {}
+
+/// Generate an WOTS keypair using the operating system's random number generator.
+///
+/// # Example
+///
+/// ```
+/// use wots_rs::generate_keypair;
+///
+/// let keypair = generate_keypair();
+///
+/// let message = b"hello";
+/// let signature = keypair.sign_unchecked(message);
+///
+/// assert!(keypair.verify(message, signature));
+///
+/// ```
+#[cfg(feature = "std")]
+pub fn generate_keypair() -> Keypair {
+    Keypair::generate(&mut rand::rngs::OsRng {})
 }
@@ -12,25 +12,173 @@
 //! let keypair: Keypair = Keypair::generate(&mut csprng);
 //!
 //! let message = b"hello";
-//! let signature = keypair.sign(message);
+//! let signature = keypair.sign_unchecked(message);
 //!
 //! assert!(keypair.verify(message, signature));
 //!
 //! ```
+//!
+//! # Choosing `w`
+//!
+//! The default types fix the Winternitz parameter at `w = 256`. The
+//! [`params`] module exposes the same scheme with `w` chosen at runtime
+//! through [`Params::with_w`], trading signature size against the number of
+//! hashes per chain:
+//!
+//! | `w` | chains | signature bytes | hashes per chain |
+//! |-----|--------|-----------------|------------------|
+//! | 4   | 133    | 4256            | 4                |
+//! | 16  | 67     | 2144            | 16               |
+//! | 256 | 34     | 1088            | 256              |
+//!
+//! # `no_std`
+//!
+//! Without the default `std` feature the crate is `no_std`. Keys are
+//! generated from any [`CryptoRng`](rand::CryptoRng) +
+//! [`RngCore`](rand::RngCore) passed in, and signing and verifying a
+//! [`Keypair`] work on fixed-size arrays without allocating. Coarser
+//! helpers such as Merkle trees and the parameterized scheme use `alloc`.
+//! The default `sha256-rs` hash backend needs `std`; without it hashing
+//! falls back to the `no_std` [`sha2`] crate.
+//!
+//! To check the `no_std` build, compile for a target without `std`:
+//!
+//! ```text
+//! cargo build --no-default-features --target thumbv7em-none-eabi
+//! ```
+//!
+//! `cargo test --no-default-features` runs the tests without `std`,
+//! including `tests/no_std.rs`, a `no_std` crate that generates, signs and
+//! verifies with its own RNG.
+#![cfg_attr(not(feature = "std"), no_std)]
+
+extern crate alloc;
+#[cfg(test)]
+extern crate std;
+
+#[cfg(target_has_atomic = "8")]
+mod atomic;
+mod cached;
+mod checksum;
+mod committed;
+mod compact;
+mod constants;
+mod context;
+#[cfg(feature = "cose")]
+mod cose;
+mod counter;
+mod digest32;
+mod double;
+mod encoding;
+mod error;
+#[cfg(feature = "ffi")]
+pub mod ffi;
+mod fors;
+mod generator;
+mod hash;
+mod hex;
+mod incremental;
 mod keypair;
+pub mod lms;
+mod merkle;
+#[cfg(feature = "multi-buffer")]
+mod multibuffer;
+mod one_time;
+pub mod params;
+#[cfg(feature = "pkcs8")]
+mod pkcs;
 mod public;
+#[cfg(feature = "rand_core_0_9")]
+mod rand_compat;
+mod randomized;
+pub mod rfc8391;
+mod rotating;
 mod secret;
+mod seed;
+#[cfg(feature = "serde")]
+mod serialization;
 mod signature;
+mod signer;
+mod state;
+mod streaming;
+#[cfg(feature = "signature")]
+mod traits;
+#[cfg(feature = "wasm")]
+pub mod wasm;
+mod width;
+pub mod wotsp;
+pub mod xmss;
 
+#[cfg(target_has_atomic = "8")]
+pub use atomic::*;
+pub use cached::*;
+pub use committed::*;
+pub use compact::*;
+pub use constants::*;
+#[cfg(feature = "cose")]
+pub use cose::*;
+pub use counter::*;
+pub use digest32::*;
+pub use double::*;
+pub use encoding::*;
+pub use error::*;
+pub use fors::*;
+pub use generator::*;
+pub use hash::chain_hash_fn;
+pub use incremental::*;
 pub use keypair::*;
+pub use merkle::*;
+pub use one_time::*;
+pub use params::Params;
+#[cfg(feature = "pkcs8")]
+pub use pkcs::*;
 pub use public::*;
+#[cfg(feature = "rand_core_0_9")]
+pub use rand_compat::*;
+pub use randomized::*;
+pub use rotating::*;
 pub use secret::*;
+pub use seed::*;
 pub use signature::*;
+pub use signer::*;
+pub use state::*;
+pub use streaming::*;
+pub use width::*;
 
 #[cfg(test)]
 mod tests {
     use super::*;
-    use rand::rngs::OsRng;
+    use alloc::{
+        format,
+        string::{String, ToString},
+        vec,
+        vec::Vec,
+    };
+    use rand::{rngs::OsRng, CryptoRng, RngCore};
+
+    /// An RNG that always produces the same byte.
+    struct FixedRng(u8);
+
+    impl RngCore for FixedRng {
+        fn next_u32(&mut self) -> u32 {
+            u32::from_le_bytes([self.0; 4])
+        }
+
+        fn next_u64(&mut self) -> u64 {
+            u64::from_le_bytes([self.0; 8])
+        }
+
+        fn fill_bytes(&mut self, dest: &mut [u8]) {
+            dest.fill(self.0);
+        }
+
+        fn try_fill_bytes(&mut self, dest: &mut [u8]) -> Result<(), rand::Error> {
+            self.fill_bytes(dest);
+            Ok(())
+        }
+    }
+
+    impl CryptoRng for FixedRng {}
 
     #[test]
     fn it_works() {
@@ -39,7 +187,1534 @@ mod tests {
         let mut csprng = OsRng {};
         let keypair = Keypair::generate(&mut csprng);
 
-        let signature = keypair.sign(message);
+        let signature = keypair.sign_unchecked(message);
+        assert!(keypair.verify(message, signature));
+    }
+
+    #[test]
+    fn as_ref_matches_to_bytes() {
+        let message = b"hello";
+
+        let mut csprng = OsRng {};
+        let keypair = Keypair::generate(&mut csprng);
+
+        let signature = keypair.sign_unchecked(message);
+        assert_eq!(signature.as_ref()[0], signature.to_bytes()[0]);
+        assert_eq!(keypair.public.as_ref()[0], keypair.public.to_bytes()[0]);
+        assert_eq!(keypair.secret.as_ref()[0], keypair.secret.to_bytes()[0]);
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn generate_keypair_works() {
+        let message = b"hello";
+
+        let keypair = generate_keypair();
+
+        let signature = keypair.sign_unchecked(message);
+        assert!(keypair.verify(message, signature));
+    }
+
+    #[test]
+    fn verify_strict_reports_chain() {
+        let message = b"hello";
+
+        let mut csprng = OsRng {};
+        let keypair = Keypair::generate(&mut csprng);
+
+        let mut bytes = keypair.sign_unchecked(message).to_bytes();
+        bytes[5][0] ^= 1;
+        let signature = Signature::from(bytes);
+
+        assert_eq!(
+            keypair.verify_strict(message, &signature),
+            Err(WotsError::ChainMismatch { index: 5 })
+        );
+    }
+
+    #[test]
+    fn public_key_verify_strict() {
+        let message = b"hello";
+
+        let secret = SecretKey::from_seed([42u8; 32]);
+        let public = PublicKey::from(&secret);
+        let other = SecretKey::from_seed([43u8; 32]);
+
+        assert_eq!(public.verify_strict(message, &secret.sign(message)), Ok(()));
+
+        let mut bytes = secret.sign(message).to_bytes();
+        bytes[31][0] ^= 1;
+        assert_eq!(
+            public.verify_strict(message, &Signature::from(bytes)),
+            Err(WotsError::ChainMismatch { index: 31 })
+        );
+
+        assert_eq!(
+            public.verify_strict(message, &other.sign(message)),
+            Err(WotsError::ChainMismatch { index: 0 })
+        );
+    }
+
+    #[cfg(feature = "tracing")]
+    #[test]
+    fn verify_strict_emits_event() {
+        use std::sync::{Arc, Mutex};
+        use tracing::{
+            field::{Field, Visit},
+            span, Event, Metadata, Subscriber,
+        };
+
+        #[derive(Clone, Default)]
+        struct Recorder(Arc<Mutex<Vec<(String, u64)>>>);
+
+        impl Visit for Recorder {
+            fn record_u64(&mut self, field: &Field, value: u64) {
+                self.0
+                    .lock()
+                    .unwrap()
+                    .push((field.name().to_string(), value));
+            }
+
+            fn record_debug(&mut self, _field: &Field, _value: &dyn core::fmt::Debug) {}
+        }
+
+        impl Subscriber for Recorder {
+            fn enabled(&self, _metadata: &Metadata<'_>) -> bool {
+                true
+            }
+
+            fn new_span(&self, _span: &span::Attributes<'_>) -> span::Id {
+                span::Id::from_u64(1)
+            }
+
+            fn record(&self, _span: &span::Id, _values: &span::Record<'_>) {}
+
+            fn record_follows_from(&self, _span: &span::Id, _follows: &span::Id) {}
+
+            fn event(&self, event: &Event<'_>) {
+                event.record(&mut self.clone());
+            }
+
+            fn enter(&self, _span: &span::Id) {}
+
+            fn exit(&self, _span: &span::Id) {}
+        }
+
+        let message = b"hello";
+
+        let mut csprng = OsRng {};
+        let keypair = Keypair::generate(&mut csprng);
+
+        let mut bytes = keypair.sign_unchecked(message).to_bytes();
+        bytes[7][31] ^= 1;
+        let signature = Signature::from(bytes);
+
+        let recorder = Recorder::default();
+        tracing::subscriber::with_default(recorder.clone(), || {
+            assert!(keypair.verify_strict(message, &signature).is_err());
+        });
+
+        let fields = recorder.0.lock().unwrap();
+        assert!(fields.contains(&("chain".to_string(), 7)));
+        assert!(fields.contains(&("message_len".to_string(), 5)));
+    }
+
+    #[test]
+    fn matches_agrees_with_verify() {
+        let message = b"hello";
+
+        let mut csprng = OsRng {};
+        let keypair = Keypair::generate(&mut csprng);
+        let other = Keypair::generate(&mut csprng);
+
+        let signature = keypair.sign_unchecked(message);
+        assert!(signature.matches(message, &keypair.public));
+        assert!(!signature.matches(message, &other.public));
+        assert!(!signature.matches(b"world", &keypair.public));
+        assert!(keypair.verify(message, signature));
+    }
+
+    #[cfg(not(wots_test_fast_chains))]
+    #[test]
+    fn from_seed_public_key_is_pinned() {
+        let public_key = Keypair::from_seed([42u8; 32]).public;
+
+        assert_eq!(
+            hex::encode(&public_key.to_bytes()[0]),
+            "e3c5d9f14a2ece6cf0d3f0cbd0bd496f40bc7413dbb6ee338c187d664166cb03"
+        );
+        assert_eq!(
+            hex::encode(&public_key.to_bytes()[CHAINS - 1]),
+            "3843ce251e7dbbbf7791745b51cac348e27574226e37713503c3cd80c90383e4"
+        );
+        assert_eq!(
+            hex::encode(public_key.commitment().as_ref()),
+            "462ba2b53f9c1f42af2f22197f1e3f7ce06cc375e6318d469a8d74c64a330228"
+        );
+    }
+
+    #[cfg(not(wots_test_fast_chains))]
+    #[test]
+    fn from_seed_signature_is_pinned() {
+        let message = b"hello";
+
+        let keypair = Keypair::from_seed([42u8; 32]);
+        let signature = keypair.sign_unchecked(message);
         assert!(keypair.verify(message, signature));
+
+        let signature = keypair.sign_unchecked(message).to_bytes();
+        let hex: String = signature
+            .iter()
+            .flatten()
+            .map(|byte| format!("{:02x}", byte))
+            .collect();
+
+        assert_eq!(
+            &hex[..64],
+            "fe64e1b222634d53587ac32b37a627ee9a293bc3a6d850fc8f2d64ebfe45ee97"
+        );
+        assert_eq!(
+            &hex[hex.len() - 64..],
+            "716791302d21ad81736b7be5382462f7cbe84524dc7061d7659f3f41805d1d07"
+        );
+
+        let digest: String = sha256_rs::sha256(&signature.concat())
+            .iter()
+            .map(|byte| format!("{:02x}", byte))
+            .collect();
+        assert_eq!(
+            digest,
+            "be3f5e2de7a7ecc5f7cb944656091be46b86a6b1a4adcfd5ccdf2dcb138c7ae7"
+        );
+    }
+
+    #[test]
+    fn sign_into_matches_sign() {
+        let message = b"hello";
+
+        let mut csprng = OsRng {};
+        let keypair = Keypair::generate(&mut csprng);
+
+        let mut out = [0xffu8; SIGNATURE_LENGTH];
+        keypair.sign_into(message, &mut out);
+
+        assert_eq!(out, keypair.sign_unchecked(message).to_flat_bytes());
+    }
+
+    #[test]
+    fn verify_chunks_matches_verify() {
+        let a: &[u8] = b"hello, ";
+        let b: &[u8] = b"world";
+
+        let mut csprng = OsRng {};
+        let keypair = Keypair::generate(&mut csprng);
+
+        let signature = keypair.sign_unchecked(&[a, b].concat());
+        assert!(keypair.verify_chunks(&[a, b], &signature));
+        assert!(keypair.verify_chunks(&[&[a, b].concat()], &signature));
+        assert!(!keypair.verify_chunks(&[b, a], &signature));
+        assert!(keypair.verify(&[a, b].concat(), signature));
+    }
+
+    #[test]
+    fn ct_eq_agrees_with_eq() {
+        use subtle::ConstantTimeEq;
+
+        let keypair = Keypair::from_seed([42u8; 32]);
+
+        let signature = keypair.sign_unchecked(b"hello");
+        let same = keypair.sign_unchecked(b"hello");
+        let other = keypair.sign_unchecked(b"world");
+
+        assert!(signature == same);
+        assert!(bool::from(signature.ct_eq(&same)));
+        assert!(signature != other);
+        assert!(!bool::from(signature.ct_eq(&other)));
+    }
+
+    #[test]
+    fn chain_hash_fn_is_sha256() {
+        assert_eq!(chain_hash_fn()(b"x"), sha256_rs::sha256(b"x"));
+    }
+
+    #[test]
+    fn verify_batch_all_with_invalid_item() {
+        let keypair = Keypair::from_seed([42u8; 32]);
+
+        let hello = keypair.sign_unchecked(b"hello");
+        let world = keypair.sign_unchecked(b"world");
+        let items: [(&[u8], &Signature); 3] = [
+            (b"hello", &hello),
+            (b"tampered", &world),
+            (b"world", &world),
+        ];
+
+        assert!(!keypair.verify_batch_all(&items, true));
+        assert!(!keypair.verify_batch_all(&items, false));
+
+        let items: [(&[u8], &Signature); 2] = [(b"hello", &hello), (b"world", &world)];
+
+        assert!(keypair.verify_batch_all(&items, true));
+        assert!(keypair.verify_batch_all(&items, false));
+    }
+
+    #[test]
+    fn generate_for_device_separates_devices() {
+        let a = Keypair::generate_for_device(&mut FixedRng(7), b"device-a");
+        let b = Keypair::generate_for_device(&mut FixedRng(7), b"device-b");
+        let again = Keypair::generate_for_device(&mut FixedRng(7), b"device-a");
+
+        assert!(a.secret != b.secret);
+        assert!(a.public != b.public);
+        assert!(a.public == again.public);
+
+        let signature = a.sign_unchecked(b"hello");
+        assert!(a.verify(b"hello", signature));
+    }
+
+    #[test]
+    fn keypair_from_secret_key() {
+        let message = b"hello";
+
+        let keypair = Keypair::from(SecretKey::from_seed([42u8; 32]));
+        assert!(keypair.public == Keypair::from_seed([42u8; 32]).public);
+
+        let signature = keypair.sign_unchecked(message);
+        assert!(keypair.verify(message, signature));
+    }
+
+    #[test]
+    fn hash_is_consistent_with_eq() {
+        use std::collections::hash_map::DefaultHasher;
+        use std::collections::HashSet;
+        use std::hash::{Hash, Hasher};
+
+        fn hash_of(signature: &Signature) -> u64 {
+            let mut hasher = DefaultHasher::new();
+            signature.hash(&mut hasher);
+            hasher.finish()
+        }
+
+        let keypair = Keypair::from_seed([42u8; 32]);
+
+        let signature = keypair.sign_unchecked(b"hello");
+        let same = keypair.sign_unchecked(b"hello");
+        assert!(signature == same);
+        assert_eq!(hash_of(&signature), hash_of(&same));
+
+        let mut set = HashSet::new();
+        set.insert(signature);
+        set.insert(same);
+        set.insert(keypair.sign_unchecked(b"world"));
+        assert_eq!(set.len(), 2);
+    }
+
+    #[test]
+    fn sign_batch_pairs_keys_and_messages() {
+        let keys = [
+            SecretKey::from_seed([1u8; 32]),
+            SecretKey::from_seed([2u8; 32]),
+            SecretKey::from_seed([3u8; 32]),
+        ];
+        let messages: [&[u8]; 3] = [b"one", b"two", b"three"];
+
+        let signatures = sign_batch(&keys, &messages).unwrap();
+        assert_eq!(signatures.len(), 3);
+
+        for ((key, message), signature) in keys.iter().zip(messages).zip(signatures) {
+            assert!(PublicKey::from(key).verify(message, signature));
+        }
+
+        assert_eq!(
+            sign_batch(&keys, &messages[..2]).err(),
+            Some(WotsError::LengthMismatch {
+                expected: 3,
+                actual: 2
+            })
+        );
+    }
+
+    #[test]
+    fn digits_round_trip() {
+        let keypair = Keypair::from_seed([42u8; 32]);
+
+        let mut ascending = [0u8; 32];
+        for (i, digit) in ascending.iter_mut().enumerate() {
+            *digit = (i * 8) as u8;
+        }
+
+        for digits in [[0u8; 32], [255u8; 32], ascending] {
+            let signature = keypair.sign_digits(&digits);
+            assert!(keypair.verify_digits(&digits, &signature));
+
+            let mut other = digits;
+            other[0] ^= 1;
+            assert!(!keypair.verify_digits(&other, &signature));
+        }
+
+        let digest = sha256_rs::sha256(b"hello");
+        assert!(keypair.sign_digits(&digest) == keypair.sign_unchecked(b"hello"));
+    }
+
+    #[test]
+    fn try_generate_rejects_weak_rng() {
+        assert!(Keypair::generate(&mut FixedRng(0)).secret.looks_weak());
+        assert_eq!(
+            Keypair::try_generate(&mut FixedRng(0)).err(),
+            Some(WotsError::RngFailure)
+        );
+        assert_eq!(
+            Keypair::try_generate(&mut FixedRng(7)).err(),
+            Some(WotsError::RngFailure)
+        );
+
+        let keypair = Keypair::try_generate(&mut OsRng {}).unwrap();
+        assert!(!keypair.secret.looks_weak());
+        assert!(!SecretKey::from_seed([0u8; 32]).looks_weak());
+    }
+
+    #[test]
+    fn try_generate_reports_failing_rng() {
+        /// An RNG that fails once it has produced `self.0` chains.
+        struct FailingRng(usize);
+
+        impl RngCore for FailingRng {
+            fn next_u32(&mut self) -> u32 {
+                unimplemented!()
+            }
+
+            fn next_u64(&mut self) -> u64 {
+                unimplemented!()
+            }
+
+            fn fill_bytes(&mut self, _: &mut [u8]) {
+                unimplemented!()
+            }
+
+            fn try_fill_bytes(&mut self, dest: &mut [u8]) -> Result<(), rand::Error> {
+                if self.0 == 0 {
+                    let code = core::num::NonZeroU32::new(rand::Error::CUSTOM_START).unwrap();
+                    return Err(code.into());
+                }
+
+                self.0 -= 1;
+                OsRng.try_fill_bytes(dest)
+            }
+        }
+
+        impl CryptoRng for FailingRng {}
+
+        for chains in [0, 1, CHAINS - 1] {
+            assert_eq!(
+                SecretKey::try_generate(&mut FailingRng(chains)).err(),
+                Some(WotsError::RngFailure)
+            );
+        }
+        assert!(SecretKey::try_generate(&mut FailingRng(CHAINS)).is_ok());
+    }
+
+    #[test]
+    fn finish_chain_reproduces_public_key() {
+        let message = b"hello";
+
+        let keypair = Keypair::from_seed([42u8; 32]);
+        let signature = keypair.sign_unchecked(message).to_bytes();
+        let digest = sha256_rs::sha256(message);
+
+        let public = keypair.public.to_bytes();
+        for i in 0..32 {
+            assert_eq!(finish_chain(&signature[i], digest[i]), public[i]);
+        }
+    }
+
+    #[test]
+    fn maximal_checksum_round_trip() {
+        let keypair = Keypair::from_seed([42u8; 32]);
+
+        let digits = [0u8; MESSAGE_CHAINS];
+        let signature = keypair.sign_digits(&digits);
+        assert!(keypair.verify_digits(&digits, &signature));
+
+        let chains = signature.to_bytes();
+        let public = keypair.public.to_bytes();
+        assert_eq!(
+            finish_chain(&chains[MESSAGE_CHAINS], 0x1f),
+            public[MESSAGE_CHAINS]
+        );
+        assert_eq!(
+            finish_chain(&chains[MESSAGE_CHAINS + 1], 0xe0),
+            public[MESSAGE_CHAINS + 1]
+        );
+    }
+
+    #[test]
+    fn verify_digest_rejects_wrong_length() {
+        let message = b"hello";
+
+        let mut csprng = OsRng {};
+        let keypair = Keypair::generate(&mut csprng);
+
+        let signature = keypair.sign_unchecked(message);
+        let digest = [sha256_rs::sha256(message), sha256_rs::sha256(message)].concat();
+
+        assert_eq!(
+            keypair.public.verify_digest(&digest, &signature),
+            Err(WotsError::LengthMismatch {
+                expected: 32,
+                actual: 64
+            })
+        );
+        assert_eq!(
+            keypair.public.verify_digest(&digest[..31], &signature),
+            Err(WotsError::LengthMismatch {
+                expected: 32,
+                actual: 31
+            })
+        );
+    }
+
+    #[cfg(feature = "bitcoin-interop")]
+    #[test]
+    fn script_pushes_round_trip() {
+        let message = b"hello";
+
+        let keypair = Keypair::from_seed([42u8; 32]);
+        let signature = keypair.sign_unchecked(message);
+
+        let pushes = signature.to_script_pushes();
+        assert!(pushes.iter().all(|push| push.len() == 32));
+
+        let signature = Signature::from_script_pushes(&pushes).unwrap();
+        assert!(keypair.verify(message, signature));
+    }
+
+    #[cfg(feature = "bitcoin-interop")]
+    #[test]
+    fn script_pushes_reject_wrong_count() {
+        let keypair = Keypair::from_seed([42u8; 32]);
+        let mut pushes = keypair.sign_unchecked(b"hello").to_script_pushes();
+
+        pushes.pop();
+        assert_eq!(
+            Signature::from_script_pushes(&pushes).err(),
+            Some(WotsError::LengthMismatch {
+                expected: CHAINS,
+                actual: CHAINS - 1
+            })
+        );
+
+        pushes.push(vec![0u8; 31]);
+        assert_eq!(
+            Signature::from_script_pushes(&pushes).err(),
+            Some(WotsError::LengthMismatch {
+                expected: 32,
+                actual: 31
+            })
+        );
+    }
+
+    #[test]
+    fn verify_control_short_circuits() {
+        let keypair = Keypair::from_seed([42u8; 32]);
+
+        let hello = keypair.sign_unchecked(b"hello");
+        let world = keypair.sign_unchecked(b"world");
+        let items: [(&[u8], &Signature); 3] =
+            [(b"hello", &hello), (b"hello", &world), (b"world", &world)];
+
+        let mut verified = 0;
+        let result = items.iter().try_for_each(|(message, signature)| {
+            verified += 1;
+            signature.verify_control(message, &keypair.public)
+        });
+
+        assert!(result.is_break());
+        assert_eq!(verified, 2);
+
+        assert!(items[2]
+            .1
+            .verify_control(items[2].0, &keypair.public)
+            .is_continue());
+    }
+
+    /// The flat bytes of `Keypair::from_seed(&[42; 32]).sign(b"hello")`,
+    /// computed independently with Python's `hashlib`. Each line is one
+    /// chain; the last two lines are the checksum chains.
+    #[cfg(not(wots_test_fast_chains))]
+    const REFERENCE_SIGNATURE: &str = concat!(
+        "fe64e1b222634d53587ac32b37a627ee9a293bc3a6d850fc8f2d64ebfe45ee97",
+        "0db2f71eb9a0fc2417b3d2c910ea1a1d1d21fa3f7fa632d9bfa8096a26006633",
+        "d6c5cb25bec1c143141b4e61a9a744be95cb62cc5560ed3c08ec037e5d9a9785",
+        "7f12690e7e4607c5c91f43aca7d7420e434f4167af5c520c6e8736d68171d81f",
+        "b1e127ad914926ff1e489edc81818fffa23ac26a4bca44ec4425c21a154f64c8",
+        "9e8d26f1509921273a77e1f6648edfb9c42d81f94e645e2eb31c9deadfccdb07",
+        "2ba24dcad98d181f2ee033200244e56a46374b17743bd67e3c1ae396baa85a8a",
+        "4894e8427213057c78b754a2fac362883a4b3e4bea2f4f008c506d7fbe8ff25d",
+        "f881ada475a4cd2d8dbb903d41f36a99e49e2fd662beaa6369b97d6539a9922e",
+        "732216d0e50f3912e1fceb8c262a31c48cc3e35174fe0582ca02fbf3f7e3f76f",
+        "9d89b39b2bf62a728ed01d925828d90995d240264ec7826734d464c7428589eb",
+        "c2c155fddbae4507fa850fca215979b747053559a8c71e4571ec3771157e546a",
+        "a66d476a71c7747858ae5163cf44a6e654ec81a6dec0306b32c6a9b848b2cdae",
+        "cd379d1839a72b16b7661222bd0f6fab66041fb8c1336f1514a39eea15b80590",
+        "638ab8b16d079786bb3f69645a371889d4ba643f65835739f67edf1f1b1f3bb6",
+        "ace275ba9a6d75753b7df2afa734ae8b14555793f5d8be238a42ca9783c0e2ef",
+        "e21469804fbe1370bade76befc3bee90244744e611df33fef7cb4e899bc4c39d",
+        "1d21b250aa820f34ec20a8041d73db3c1f73e6dbd407a55080c3e588df4cdf30",
+        "b941a54774121e606b787cae8f1406cded67b834ac279630158a5f631eeea35f",
+        "402cee4d22638e412d8541e8f87e2a13eb973a84c3355648a9b6a0e8e5e44f69",
+        "b0bcb535375e31af435f8090c9ab21ae4c12d609c5b32b128e5527e174f88b41",
+        "7418103235168df7fa431910ecd7b3f37bc582843961169a67a9cecb60273b35",
+        "8fee6cb0c260d2fbce87fa30f29e6ce9cbcf6bf03159e56f0162b8cd04bef16b",
+        "a560bfd5ff011569556b6379aaff3135c62f722577f4e5dbee06a48b8d3f948c",
+        "eef5e71370f811478b1be42526ced795026bbaee79c8877506ac53523eaeb4c3",
+        "0ce993521f40d4eb6b7522f30902e2ca85b7234823444265577365d48d43fcdd",
+        "118b54c85b7335b0a0c2c3d35fb04a237efcbd9fa984ce3ecfbdc7d2841b2726",
+        "db02b0047c2d2a857d704b64d183af1d10207cdf5cb222853cb72426647feb66",
+        "286c01f613ddf70a24a2321a4fabac6bba58e8628ea69ee1751733e4a957bf3e",
+        "d602dc140764fea613f27e304d09730b6ea198af065d8142239a3856735e9fd1",
+        "286ec09431807843dc08cfe4fbafbb96ae79ca4c46712647ef2c8d0396c43666",
+        "0fd66bd40b0e22cca49220734212d33c32f579d6340d5b71dd803ee0b482a8bd",
+        "4bc26141db608813b0bdce368fb1124e1791db5da9eb26bcb2e262fa46890d3a",
+        "716791302d21ad81736b7be5382462f7cbe84524dc7061d7659f3f41805d1d07",
+    );
+
+    #[cfg(not(wots_test_fast_chains))]
+    #[test]
+    fn from_flat_bytes_decodes_reference_vector() {
+        let bytes: [u8; SIGNATURE_LENGTH] = hex::decode(REFERENCE_SIGNATURE)
+            .unwrap()
+            .try_into()
+            .unwrap();
+        let signature = Signature::from_flat_bytes(&bytes);
+        let chains = signature.to_bytes();
+
+        assert_eq!(
+            hex::encode(&chains[1]),
+            "0db2f71eb9a0fc2417b3d2c910ea1a1d1d21fa3f7fa632d9bfa8096a26006633"
+        );
+        assert_eq!(
+            hex::encode(&chains[MESSAGE_CHAINS]),
+            "4bc26141db608813b0bdce368fb1124e1791db5da9eb26bcb2e262fa46890d3a"
+        );
+
+        let keypair = Keypair::from_seed([42u8; 32]);
+        assert!(signature == keypair.sign_unchecked(b"hello"));
+        assert!(keypair.verify(b"hello", signature));
+    }
+
+    #[test]
+    fn from_flat_bytes_follows_chain_order() {
+        let mut bytes = [0u8; SIGNATURE_LENGTH];
+        for (i, byte) in bytes.iter_mut().enumerate() {
+            *byte = (i / 32) as u8 ^ (i % 32) as u8;
+        }
+
+        let signature = Signature::from_flat_bytes(&bytes);
+        let chains = signature.to_bytes();
+
+        assert_eq!(chains[0][..4], [0x00, 0x01, 0x02, 0x03]);
+        assert_eq!(chains[1][..4], [0x01, 0x00, 0x03, 0x02]);
+        assert_eq!(chains[CHAINS - 1][31], 0x21 ^ 0x1f);
+        assert_eq!(signature.to_flat_bytes(), bytes);
+    }
+
+    #[test]
+    #[allow(deprecated)]
+    fn secret_sign_matches_sign() {
+        let message = b"hello";
+
+        let keypair = Keypair::from_seed([42u8; 32]);
+        assert!(keypair.secret_sign(message) == keypair.sign(message));
+        assert!(keypair.sign(message) == keypair.sign_unchecked(message));
+    }
+
+    #[test]
+    fn rejects_flipped_message_bit() {
+        let message = *b"hello";
+
+        let keypair = Keypair::from_seed([42u8; 32]);
+        let signature = keypair.sign_unchecked(&message);
+
+        for bit in 0..message.len() * 8 {
+            let mut tampered = message;
+            tampered[bit / 8] ^= 1 << (bit % 8);
+            assert!(!signature.matches(&tampered, &keypair.public));
+        }
+    }
+
+    #[test]
+    fn rejects_flipped_signature_bit() {
+        let message = b"hello";
+
+        let keypair = Keypair::from_seed([42u8; 32]);
+        let signature = keypair.sign_unchecked(message).to_bytes();
+
+        for i in [0, 17, MESSAGE_CHAINS, CHAINS - 1] {
+            for bit in [0, 100, 255] {
+                let mut tampered = signature;
+                tampered[i][bit / 8] ^= 1 << (bit % 8);
+                assert!(!keypair.verify(message, Signature::from(tampered)));
+            }
+        }
+    }
+
+    #[test]
+    fn rejects_signature_from_other_keypair() {
+        let message = b"hello";
+
+        let keypair = Keypair::from_seed([42u8; 32]);
+        let other = Keypair::from_seed([43u8; 32]);
+
+        assert!(!keypair.verify(message, other.sign_unchecked(message)));
+    }
+
+    #[test]
+    fn rejects_signature_for_other_message() {
+        let keypair = Keypair::from_seed([42u8; 32]);
+
+        assert!(!keypair.verify(b"hello", keypair.sign_unchecked(b"world")));
+        assert!(!keypair.verify(b"", keypair.sign_unchecked(b"hello")));
+    }
+
+    #[test]
+    fn rejects_truncated_or_extended_signature() {
+        let keypair = Keypair::from_seed([42u8; 32]);
+        let bytes = keypair.sign_unchecked(b"hello").to_flat_bytes();
+
+        assert!(Signature::try_from(&bytes[..]).is_ok());
+        assert_eq!(
+            Signature::try_from(&bytes[..SIGNATURE_LENGTH - 1]).err(),
+            Some(WotsError::LengthMismatch {
+                expected: SIGNATURE_LENGTH,
+                actual: SIGNATURE_LENGTH - 1
+            })
+        );
+
+        let mut extended = bytes.to_vec();
+        extended.push(0);
+        assert_eq!(
+            Signature::try_from(extended.as_slice()).err(),
+            Some(WotsError::LengthMismatch {
+                expected: SIGNATURE_LENGTH,
+                actual: SIGNATURE_LENGTH + 1
+            })
+        );
+    }
+
+    #[cfg(wots_test_fast_chains)]
+    #[test]
+    fn fast_chains_sign_and_verify() {
+        let message = b"hello";
+
+        let keypair = Keypair::from_seed([42u8; 32]);
+        let signature = keypair.sign_unchecked(message);
+
+        let mut chain = keypair.secret.to_bytes()[0];
+        for _ in 0..16 {
+            chain = sha256_rs::sha256(&chain);
+        }
+        assert_eq!(chain, keypair.public.to_bytes()[0]);
+
+        assert!(keypair.verify(message, signature));
+        assert!(!keypair.verify(b"world", keypair.sign_unchecked(message)));
+    }
+
+    #[test]
+    fn magic_bytes_round_trip() {
+        let message = b"hello";
+
+        let keypair = Keypair::from_seed([42u8; 32]);
+        let bytes = keypair.sign_unchecked(message).to_magic_bytes();
+
+        assert_eq!(bytes[..4], SIGNATURE_MAGIC);
+        assert_eq!(bytes[4..], keypair.sign_unchecked(message).to_flat_bytes());
+
+        let signature = Signature::from_magic_bytes(&bytes).unwrap();
+        assert!(keypair.verify(message, signature));
+    }
+
+    #[test]
+    fn magic_bytes_reject_missing_magic() {
+        let keypair = Keypair::from_seed([42u8; 32]);
+        let mut bytes = keypair.sign_unchecked(b"hello").to_magic_bytes();
+
+        let flat = keypair.sign_unchecked(b"hello").to_flat_bytes();
+        assert_eq!(
+            Signature::from_magic_bytes(&flat).err(),
+            Some(WotsError::BadMagic)
+        );
+
+        bytes[0] = b'X';
+        assert_eq!(
+            Signature::from_magic_bytes(&bytes).err(),
+            Some(WotsError::BadMagic)
+        );
+
+        assert_eq!(
+            Signature::from_magic_bytes(&SIGNATURE_MAGIC).err(),
+            Some(WotsError::LengthMismatch {
+                expected: SIGNATURE_LENGTH,
+                actual: 0
+            })
+        );
+    }
+
+    #[test]
+    fn verification_work_is_bounded() {
+        use crate::{constants::CHAIN_LENGTH, hash::count_hashes};
+
+        let keypair = Keypair::from_seed([42u8; 32]);
+        let bound = (CHAIN_LENGTH - 1) * CHAINS;
+
+        for digits in [
+            [0xffu8; MESSAGE_CHAINS],
+            [0u8; MESSAGE_CHAINS],
+            [0x80; MESSAGE_CHAINS],
+        ] {
+            let signature = keypair.sign_digits(&digits);
+
+            let (valid, hashes) = count_hashes(|| keypair.verify_digits(&digits, &signature));
+            assert!(valid);
+            assert!(hashes <= bound, "{} hashes exceed {}", hashes, bound);
+        }
+    }
+
+    #[test]
+    fn debug_string_groups_chains() {
+        let keypair = Keypair::from_seed([42u8; 32]);
+        let chain: String = keypair.public.to_bytes()[0]
+            .iter()
+            .map(|byte| format!("{:02x}", byte))
+            .collect();
+
+        let debug = keypair.public.to_debug_string();
+        let lines: Vec<&str> = debug.lines().collect();
+        assert_eq!(lines.len(), CHAINS);
+        assert_eq!(lines[0], format!("chain[0]: {}", chain));
+
+        let debug = keypair.sign_unchecked(b"hello").to_debug_string();
+        assert_eq!(debug.lines().count(), CHAINS);
+        assert!(debug.starts_with("chain[0]: "));
+    }
+
+    #[test]
+    fn batch_matches_verify() {
+        let keypair = Keypair::from_seed([42u8; 32]);
+
+        let hello = keypair.sign_unchecked(b"hello");
+        let world = keypair.sign_unchecked(b"world");
+        let items: [(&[u8], &Signature); 4] = [
+            (b"hello", &hello),
+            (b"world", &world),
+            (b"hello", &world),
+            (b"world", &world),
+        ];
+
+        for item in items.iter() {
+            let expected = keypair
+                .public
+                .verify(item.0, Signature::from(item.1.to_bytes()));
+            assert_eq!(keypair.public.verify_batch_all(&[*item], true), expected);
+        }
+
+        assert!(keypair.public.verify_batch_all(&items[..2], false));
+        assert!(!keypair.public.verify_batch_all(&items, false));
+        assert!(!keypair.public.verify_batch_all(&items, true));
+    }
+
+    #[test]
+    fn address_depends_on_nonce() {
+        let keypair = Keypair::from_seed([42u8; 32]);
+
+        let address = keypair.public.address(b"nonce-1");
+        assert_eq!(address, keypair.public.address(b"nonce-1"));
+        assert_ne!(address, keypair.public.address(b"nonce-2"));
+
+        let commitment = keypair.public.commitment();
+        let expected = sha256_rs::sha256(&[commitment.as_ref(), b"nonce-1"].concat());
+        assert_eq!(address[..], expected[..20]);
+    }
+
+    #[test]
+    fn verify_all_mismatches_lists_every_chain() {
+        let message = b"hello";
+
+        let keypair = Keypair::from_seed([42u8; 32]);
+        let mut signature = keypair.sign_unchecked(message).to_bytes();
+
+        for i in [2, 17, CHAINS - 1] {
+            signature[i][0] ^= 1;
+        }
+
+        assert_eq!(
+            keypair
+                .public
+                .verify_all_mismatches(message, &Signature::from(signature)),
+            Err(vec![2, 17, CHAINS - 1])
+        );
+    }
+
+    #[test]
+    fn generate_many_reports_each_keypair() {
+        let mut progress = Vec::new();
+        let keypairs =
+            Keypair::generate_many_with_progress(&mut OsRng {}, 4, |done| progress.push(done));
+
+        assert_eq!(keypairs.len(), 4);
+        assert_eq!(progress, [1, 2, 3, 4]);
+    }
+
+    #[test]
+    fn signatures_sort_by_bytes() {
+        let keypair = Keypair::from_seed([42u8; 32]);
+
+        let mut signatures: Vec<Signature> = [&b"a"[..], b"b", b"c", b"d"]
+            .iter()
+            .map(|message| keypair.sign_unchecked(message))
+            .collect();
+        signatures.sort();
+
+        for pair in signatures.windows(2) {
+            assert!(pair[0].to_flat_bytes() <= pair[1].to_flat_bytes());
+        }
+
+        let sign = |message: &[u8]| keypair.sign_unchecked(message);
+        assert_eq!(sign(b"a").cmp(&sign(b"a")), core::cmp::Ordering::Equal);
+    }
+
+    #[test]
+    fn advancing_a_chain_is_rejected() {
+        let message = b"hello";
+
+        let keypair = Keypair::from_seed([42u8; 32]);
+        let signature = keypair.sign_unchecked(message);
+        let digits = sha256_rs::sha256(message);
+
+        let mut forged = signature.to_bytes();
+        forged[0] = sha256_rs::sha256(&forged[0]);
+
+        // One more hash on a message chain signs the next lower digit, so
+        // chain 0 accepts the forgery and only the checksum can reject it.
+        let mut lowered = digits;
+        assert_ne!(lowered[0], 0);
+        lowered[0] -= 1;
+        assert_eq!(keypair.sign_digits(&lowered).to_bytes()[0], forged[0]);
+
+        assert!(!keypair.verify_digits(&lowered, &Signature::from(forged)));
+        assert!(!keypair.verify(message, Signature::from(forged)));
+    }
+
+    #[test]
+    fn zero_hash_fails_verification_gracefully() {
+        let message = b"hello";
+
+        let keypair = Keypair::from_seed([7u8; 32]);
+        let signature = keypair.sign_unchecked(message);
+
+        hash::with_fault_hash(
+            |_| [0u8; 32],
+            || {
+                assert_eq!(
+                    keypair.public.verify_strict(message, &signature),
+                    Err(WotsError::ChainMismatch { index: 0 })
+                );
+                assert!(!keypair
+                    .public
+                    .verify(message, Signature::from(signature.to_bytes())));
+            },
+        );
+
+        assert_eq!(keypair.public.verify_strict(message, &signature), Ok(()));
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn serde_round_trip_each_type() {
+        let keypair = Keypair::from_seed([7u8; 32]);
+        let signature = keypair.sign_unchecked(b"hello");
+
+        let bytes = bincode::serialize(&keypair.secret).unwrap();
+        assert_eq!(bytes.len(), 8 + SIGNATURE_LENGTH);
+        assert!(bincode::deserialize::<SecretKey>(&bytes).unwrap() == keypair.secret);
+
+        let bytes = bincode::serialize(&keypair.public).unwrap();
+        assert_eq!(bytes.len(), 8 + SIGNATURE_LENGTH);
+        assert!(bincode::deserialize::<PublicKey>(&bytes).unwrap() == keypair.public);
+
+        let bytes = bincode::serialize(&signature).unwrap();
+        assert_eq!(bytes.len(), 8 + SIGNATURE_LENGTH);
+        assert!(bincode::deserialize::<Signature>(&bytes).unwrap() == signature);
+
+        let bytes = bincode::serialize(&keypair).unwrap();
+        let decoded: Keypair = bincode::deserialize(&bytes).unwrap();
+        assert!(decoded.secret == keypair.secret && decoded.public == keypair.public);
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn serde_rejects_wrong_length() {
+        for len in [SIGNATURE_LENGTH - 1, SIGNATURE_LENGTH + 1] {
+            let bytes = bincode::serialize(&vec![0u8; len].as_slice()).unwrap();
+
+            assert!(bincode::deserialize::<SecretKey>(&bytes).is_err());
+            assert!(bincode::deserialize::<PublicKey>(&bytes).is_err());
+            assert!(bincode::deserialize::<Signature>(&bytes).is_err());
+        }
+    }
+
+    #[test]
+    fn combine_is_order_independent() {
+        let keys = [1u8, 2, 3].map(|seed| Keypair::from_seed([seed; 32]).public);
+        let reversed = [3u8, 2, 1].map(|seed| Keypair::from_seed([seed; 32]).public);
+
+        assert_eq!(PublicKey::combine(&keys), PublicKey::combine(&reversed));
+        assert_ne!(PublicKey::combine(&keys), PublicKey::combine(&keys[..2]));
+    }
+
+    #[test]
+    fn from_bytes_checks_length() {
+        let keypair = Keypair::from_seed([7u8; 32]);
+        let signature = keypair.sign_unchecked(b"hello");
+
+        let secret = keypair.secret.to_bytes();
+        let public = keypair.public.to_bytes();
+        let flat = signature.to_flat_bytes();
+
+        assert!(SecretKey::from_bytes(secret.as_flattened()).unwrap() == keypair.secret);
+        assert!(PublicKey::from_bytes(public.as_flattened()).unwrap() == keypair.public);
+        assert!(Signature::from_bytes(&flat).unwrap() == signature);
+
+        for len in [0, SIGNATURE_LENGTH - 1, SIGNATURE_LENGTH + 1] {
+            let bytes = vec![0u8; len];
+            let error = Some(WotsError::LengthMismatch {
+                expected: SIGNATURE_LENGTH,
+                actual: len,
+            });
+
+            assert_eq!(SecretKey::from_bytes(&bytes).err(), error);
+            assert_eq!(PublicKey::from_bytes(&bytes).err(), error);
+            assert_eq!(Signature::from_bytes(&bytes).err(), error);
+        }
+    }
+
+    #[test]
+    fn verify_low_mem_matches_verify() {
+        let keypair = Keypair::from_seed([7u8; 32]);
+        let signature = keypair.sign_unchecked(b"hello");
+
+        for message in [b"hello", b"world"] {
+            let mut chains = signature.to_bytes().into_iter();
+            let mut keys = keypair.public.to_bytes().into_iter();
+
+            assert_eq!(
+                verify_low_mem(message, || chains.next().unwrap(), || keys.next().unwrap()),
+                keypair
+                    .public
+                    .verify(message, Signature::from(signature.to_bytes()))
+            );
+        }
+    }
+
+    #[test]
+    fn recover_returns_signing_key() {
+        let keypair = Keypair::from_seed([7u8; 32]);
+        let signature = keypair.sign_unchecked(b"hello");
+
+        assert!(PublicKey::recover(b"hello", &signature) == keypair.public);
+        assert!(PublicKey::recover(b"world", &signature) != keypair.public);
+    }
+
+    #[test]
+    fn counter_signatures_commit_to_their_counter() {
+        let message = b"hello";
+
+        let keypair = Keypair::from_seed([7u8; 32]);
+        let first = keypair.sign_counter(message, 1);
+        let mut second = keypair.sign_counter(message, 2);
+
+        assert!(first.signature != second.signature);
+        assert_eq!(keypair.public.verify_counter(message, &first), Some(1));
+        assert_eq!(keypair.public.verify_counter(message, &second), Some(2));
+        assert_eq!(keypair.public.verify_counter(b"world", &second), None);
+
+        second.counter = 1;
+        assert_eq!(keypair.public.verify_counter(message, &second), None);
+
+        let mut framed = 1u64.to_be_bytes().to_vec();
+        framed.extend_from_slice(message);
+        let plain = CounterSignature {
+            counter: 1,
+            signature: keypair.sign_unchecked(&framed),
+        };
+        assert_eq!(keypair.public.verify_counter(message, &plain), None);
+    }
+
+    #[test]
+    fn chain_loops_match_serial_hashing() {
+        let message = b"hello";
+
+        let keypair = Keypair::from_seed([7u8; 32]);
+        let signature = keypair.sign_unchecked(message);
+        let digits = checksum::with_checksum(&hash::sha256(message));
+
+        for (i, secret) in keypair.secret.to_bytes().iter().enumerate() {
+            let chain = hash::iterate(*secret, CHAIN_LENGTH - steps(digits[i]));
+
+            assert_eq!(signature.to_bytes()[i], chain);
+            assert_eq!(
+                keypair.public.to_bytes()[i],
+                hash::iterate(chain, steps(digits[i]))
+            );
+        }
+
+        assert!(keypair.public.verify(message, signature));
+    }
+
+    #[test]
+    fn constant_time_verification_accepts_and_rejects() {
+        use subtle::ConstantTimeEq;
+
+        let keypair = Keypair::from_seed([42u8; 32]);
+        let other = Keypair::from_seed([43u8; 32]);
+        let signature = keypair.sign_unchecked(b"hello");
+
+        assert!(keypair.verify(b"hello", Signature::from(signature.to_bytes())));
+        assert!(!keypair.verify(b"world", Signature::from(signature.to_bytes())));
+        assert!(!other.verify(b"hello", Signature::from(signature.to_bytes())));
+        assert!(keypair.verify_batch_all(&[(b"hello", &signature)], true));
+        assert!(!keypair.verify_batch_all(&[(b"world", &signature)], true));
+
+        assert!(bool::from(
+            keypair.public.ct_eq(&Keypair::from_seed([42u8; 32]).public)
+        ));
+        assert!(!bool::from(keypair.public.ct_eq(&other.public)));
+    }
+
+    #[test]
+    fn hex_round_trip() {
+        let keypair = Keypair::from_seed([7u8; 32]);
+        let signature = keypair.sign_unchecked(b"hello");
+
+        let hex = signature.to_hex();
+        assert_eq!(hex.len(), 2 * SIGNATURE_LENGTH);
+        assert!(Signature::from_hex(&hex).unwrap() == signature);
+        assert!(Signature::from_hex(&hex.to_uppercase()).unwrap() == signature);
+        assert!(PublicKey::from_hex(&keypair.public.to_hex()).unwrap() == keypair.public);
+        assert!(SecretKey::from_hex(&keypair.secret.to_hex()).unwrap() == keypair.secret);
+    }
+
+    #[test]
+    fn hex_rejects_malformed_input() {
+        let hex = Keypair::from_seed([7u8; 32]).public.to_hex();
+
+        assert_eq!(
+            PublicKey::from_hex(&hex[2..]).err(),
+            Some(WotsError::LengthMismatch {
+                expected: SIGNATURE_LENGTH,
+                actual: SIGNATURE_LENGTH - 1
+            })
+        );
+        assert_eq!(
+            PublicKey::from_hex(&hex[1..]).err(),
+            Some(WotsError::InvalidEncoding)
+        );
+        assert_eq!(
+            PublicKey::from_hex(&format!("zz{}", &hex[2..])).err(),
+            Some(WotsError::InvalidEncoding)
+        );
+    }
+
+    #[cfg(feature = "base64")]
+    #[test]
+    fn base64_round_trip() {
+        let keypair = Keypair::from_seed([7u8; 32]);
+        let signature = keypair.sign_unchecked(b"hello");
+
+        assert!(Signature::from_base64(&signature.to_base64()).unwrap() == signature);
+        assert!(PublicKey::from_base64(&keypair.public.to_base64()).unwrap() == keypair.public);
+        assert!(SecretKey::from_base64(&keypair.secret.to_base64()).unwrap() == keypair.secret);
+        assert_eq!(
+            Signature::from_base64("not base64").err(),
+            Some(WotsError::InvalidEncoding)
+        );
+    }
+
+    #[test]
+    fn keys_and_signatures_are_copy_and_debug() {
+        let keypair = Keypair::from_seed([42u8; 32]);
+        let signature = keypair.sign_unchecked(b"hello");
+
+        let public = keypair.public;
+        #[allow(clippy::clone_on_copy)]
+        let cloned = public.clone();
+        assert_eq!(public, cloned);
+        assert!(keypair.verify(b"hello", signature));
+
+        assert_eq!(
+            format!("{:?}", signature),
+            format!("Signature({}...)", &signature.to_hex()[..16])
+        );
+        assert_eq!(format!("{:?}", keypair.secret), "SecretKey(***)");
+        assert!(format!("{:?}", public).starts_with("PublicKey("));
+    }
+
+    #[test]
+    fn verify_batch_reports_each_item() {
+        let keypair = Keypair::from_seed([42u8; 32]);
+        let hello = keypair.sign_unchecked(b"hello");
+        let world = keypair.sign_unchecked(b"world");
+
+        let mut tampered = hello.to_bytes();
+        tampered[7][0] ^= 1;
+        let tampered = Signature::from(tampered);
+
+        let items: [(&[u8], &Signature); 4] = [
+            (b"hello", &hello),
+            (b"hello", &tampered),
+            (b"world", &world),
+            (b"world", &hello),
+        ];
+
+        assert_eq!(keypair.verify_batch(&items), [true, false, true, false]);
+        assert!(!keypair.verify_batch_all(&items, false));
+        assert!(keypair.verify_batch_all(&[items[0], items[2]], true));
+    }
+
+    #[test]
+    fn generate_accepts_any_crypto_rng() {
+        let message = b"hello";
+
+        let keypair = Keypair::generate(&mut FixedRng(7));
+        let signature = keypair.sign_unchecked(message);
+
+        assert!(keypair.secret == SecretKey::from([[7u8; 32]; CHAINS]));
+        assert!(keypair.verify(message, signature));
+    }
+
+    #[test]
+    fn verify_compressed_checks_signature() {
+        let keypair = Keypair::from_seed([42u8; 32]);
+        let compressed = keypair.public.compress();
+        assert_eq!(compressed, Keypair::from_seed([42u8; 32]).public.compress());
+        assert_eq!(
+            compressed,
+            hash::sha256(keypair.public.to_bytes().as_flattened())
+        );
+
+        let signature = keypair.sign_unchecked(b"hello");
+        let other = Keypair::from_seed([43u8; 32]).sign_unchecked(b"hello");
+
+        assert!(PublicKey::verify_compressed(
+            b"hello",
+            &signature,
+            &compressed
+        ));
+        assert!(!PublicKey::verify_compressed(
+            b"world",
+            &signature,
+            &compressed
+        ));
+        assert!(!PublicKey::verify_compressed(b"hello", &other, &compressed));
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn stream_matches_one_shot() {
+        use std::io::Read;
+
+        let keypair = Keypair::from_seed([42u8; 32]);
+        let message = [7u8; 10_000];
+
+        let chunked = message[..5_000].chain(&message[5_000..]);
+        let signature = keypair.sign_stream(chunked).unwrap();
+        assert!(signature == keypair.sign_unchecked(&message));
+        assert!(keypair.verify_stream(&message[..], &signature).unwrap());
+        assert!(!keypair.verify_stream(&message[1..], &signature).unwrap());
+
+        let empty = keypair.sign_stream(std::io::empty()).unwrap();
+        assert!(empty == keypair.sign_unchecked(b""));
+    }
+
+    #[test]
+    fn context_separates_signatures() {
+        let message = b"hello";
+
+        let keypair = Keypair::from_seed([42u8; 32]);
+        let signature = keypair.sign_with_context(message, b"appA");
+
+        assert!(keypair.verify_with_context(message, b"appA", &signature));
+        assert!(!keypair.verify_with_context(message, b"appB", &signature));
+        assert!(!keypair.verify_with_context(message, b"", &signature));
+        assert!(!keypair.verify_with_context(b"world", b"appA", &signature));
+        assert!(!keypair.verify(message, signature));
+
+        let plain = keypair.sign_unchecked(message);
+        assert!(keypair.sign_with_context(message, b"") != plain);
+        assert!(!keypair.verify_with_context(message, b"", &plain));
+
+        // Plain signing of the framed input no longer passes as a context
+        // signature.
+        let mut framed = 4u64.to_be_bytes().to_vec();
+        framed.extend_from_slice(b"appA");
+        framed.extend_from_slice(message);
+        let plain = keypair.sign_unchecked(&framed);
+        assert!(!keypair.verify_with_context(message, b"appA", &plain));
+
+        let split = keypair.sign_with_context(b"Ahello", b"app");
+        assert!(!keypair.verify_with_context(message, b"appA", &split));
+    }
+
+    #[test]
+    fn verify_detailed_tells_chain_from_checksum() {
+        let message = b"hello";
+
+        let keypair = Keypair::from_seed([42u8; 32]);
+        let signature = keypair.sign_unchecked(message);
+        assert_eq!(keypair.public.verify_detailed(message, &signature), Ok(()));
+
+        let mut chain = signature.to_bytes();
+        chain[5][0] ^= 1;
+        assert_eq!(
+            keypair
+                .public
+                .verify_detailed(message, &Signature::from(chain)),
+            Err(VerifyError::ChainMismatch { index: 5 })
+        );
+
+        let mut checksum = signature.to_bytes();
+        checksum[MESSAGE_CHAINS + 1][0] ^= 1;
+        assert_eq!(
+            keypair
+                .public
+                .verify_detailed(message, &Signature::from(checksum)),
+            Err(VerifyError::ChecksumMismatch {
+                index: MESSAGE_CHAINS + 1
+            })
+        );
+
+        assert_eq!(
+            keypair
+                .public
+                .verify_bytes_detailed(message, &signature.to_flat_bytes()[1..]),
+            Err(VerifyError::MalformedSignature(WotsError::LengthMismatch {
+                expected: SIGNATURE_LENGTH,
+                actual: SIGNATURE_LENGTH - 1
+            }))
+        );
+    }
+
+    #[test]
+    fn try_from_flat_bytes_round_trip() {
+        let keypair = Keypair::from_seed([42u8; 32]);
+        let signature = keypair.sign_unchecked(b"hello");
+
+        let secret = keypair.secret.to_flat_bytes();
+        let public = keypair.public.to_flat_bytes();
+        let flat = signature.to_flat_bytes();
+
+        assert_eq!(SecretKey::try_from(&secret[..]), Ok(keypair.secret));
+        assert_eq!(PublicKey::try_from(&public[..]), Ok(keypair.public));
+        assert_eq!(Signature::try_from(&flat[..]), Ok(signature));
+        assert_eq!(
+            PublicKey::from_hex(&keypair.public.to_hex()),
+            PublicKey::try_from(&public[..])
+        );
+
+        let error = Some(WotsError::LengthMismatch {
+            expected: SIGNATURE_LENGTH,
+            actual: 1024,
+        });
+        assert_eq!(SecretKey::try_from(&secret[..1024]).err(), error);
+        assert_eq!(PublicKey::try_from(&public[..1024]).err(), error);
+        assert_eq!(Signature::try_from(&flat[..1024]).err(), error);
+    }
+
+    #[test]
+    fn as_bytes_matches_flat_bytes() {
+        let keypair = Keypair::from_seed([42u8; 32]);
+        let signature = keypair.sign_unchecked(b"hello");
+
+        assert_eq!(keypair.secret.as_bytes(), keypair.secret.to_flat_bytes());
+        assert_eq!(keypair.public.as_bytes(), keypair.public.to_flat_bytes());
+        assert_eq!(signature.as_bytes(), signature.to_flat_bytes());
+        assert_eq!(
+            Signature::from_bytes(signature.as_bytes()).as_ref(),
+            Ok(&signature)
+        );
+    }
+
+    #[test]
+    fn secret_keys_and_commitments_compare_in_constant_time() {
+        use subtle::ConstantTimeEq;
+
+        let keypair = Keypair::from_seed([42u8; 32]);
+        let other = Keypair::from_seed([7u8; 32]);
+
+        assert!(bool::from(
+            keypair.secret.ct_eq(&SecretKey::from_seed([42u8; 32]))
+        ));
+        assert!(!bool::from(keypair.secret.ct_eq(&other.secret)));
+        assert!(keypair.secret != other.secret);
+
+        let commitment = keypair.public.commitment();
+        assert!(bool::from(commitment.ct_eq(&keypair.public.commitment())));
+        assert!(!bool::from(commitment.ct_eq(&other.public.commitment())));
+    }
+
+    #[test]
+    fn verify_strict_reports_crate_error() {
+        let keypair = Keypair::from_seed([42u8; 32]);
+        let signature = keypair.sign_unchecked(b"hello");
+
+        let result: Result<(), crate::Error> = keypair.verify_strict(b"hello", &signature);
+        assert_eq!(result, Ok(()));
+        assert!(matches!(
+            keypair.verify_strict(b"world", &signature),
+            Err(crate::Error::ChainMismatch { .. })
+        ));
+        assert!(!keypair.verify(b"world", signature));
+    }
+
+    #[test]
+    fn sign_once_matches_sign_unchecked() {
+        let keypair = Keypair::from_seed([7u8; 32]);
+        let signature = keypair.sign_unchecked(b"hello");
+
+        let (once, public_key) = keypair.sign_once(b"hello");
+        assert_eq!(once, signature);
+        assert!(public_key.verify(b"hello", once));
+    }
+
+    #[test]
+    fn derive_is_domain_separated() {
+        let master_seed = [7u8; 32];
+        let keypair = Keypair::derive(master_seed, 0);
+
+        assert!(keypair.public == Keypair::derive(master_seed, 0).public);
+        assert!(keypair.public != Keypair::derive(master_seed, 1).public);
+        assert!(keypair.public != Keypair::derive([8u8; 32], 0).public);
+        assert!(keypair.public != Keypair::from_seed(master_seed).public);
+    }
+
+    #[test]
+    fn verify_batch_across_keys() {
+        let keypairs = [1u8, 2, 3].map(|seed| Keypair::from_seed([seed; 32]));
+        let messages: [&[u8]; 3] = [b"one", b"two", b"three"];
+        let signatures: Vec<Signature> = keypairs
+            .iter()
+            .zip(messages)
+            .map(|(keypair, message)| keypair.sign_unchecked(message))
+            .collect();
+
+        let mut items: Vec<(&PublicKey, &[u8], &Signature)> = keypairs
+            .iter()
+            .zip(messages)
+            .zip(&signatures)
+            .map(|((keypair, message), signature)| (&keypair.public, message, signature))
+            .collect();
+        assert!(verify_batch(&items));
+        assert!(verify_batch_failures(&items).is_empty());
+
+        items[0].0 = &keypairs[1].public;
+        items[2].1 = b"four";
+        assert!(!verify_batch(&items));
+        assert_eq!(verify_batch_failures(&items), [0, 2]);
+    }
+
+    #[test]
+    fn prehashed_matches_hashing_the_message() {
+        let keypair = Keypair::from_seed([7u8; 32]);
+        let digest = hash::sha256(b"hello");
+
+        let signature = keypair.sign_prehashed(&digest);
+        assert!(signature == keypair.sign_unchecked(b"hello"));
+        assert!(keypair.verify_prehashed(&digest, &signature));
+        assert!(!keypair.verify_prehashed(&hash::sha256(b"world"), &signature));
+    }
+
+    #[test]
+    fn randomized_signatures_bind_the_randomizer() {
+        let keypair = Keypair::from_seed([7u8; 32]);
+
+        let mut signature = keypair.sign_randomized(&mut FixedRng(9), b"hello");
+        assert_eq!(signature.r, [9u8; 32]);
+        assert!(keypair.public.verify_randomized(b"hello", &signature));
+        assert!(!keypair.public.verify_randomized(b"world", &signature));
+        assert!(!keypair.public.verify(b"hello", signature.signature));
+
+        signature.r[0] ^= 1;
+        assert!(!keypair.public.verify_randomized(b"hello", &signature));
+
+        let plain = RandomizedSignature {
+            r: [9u8; 32],
+            signature: keypair.sign_unchecked(&[&[9u8; 32][..], b"hello"].concat()),
+        };
+        assert!(!keypair.public.verify_randomized(b"hello", &plain));
+    }
+
+    #[test]
+    fn display_and_from_str_round_trip() {
+        let keypair = Keypair::from_seed([7u8; 32]);
+        let signature = keypair.sign_unchecked(b"hello");
+
+        let text = keypair.public.to_string();
+        assert_eq!(text, keypair.public.to_hex());
+        assert_eq!(format!("{:x}", keypair.public), text);
+        assert!(text.parse::<PublicKey>().unwrap() == keypair.public);
+
+        let text = signature.to_string();
+        assert_eq!(text, signature.to_hex());
+        assert!(text.parse::<Signature>().unwrap() == signature);
+        assert_eq!(
+            "zz".parse::<Signature>().err(),
+            Some(WotsError::InvalidEncoding)
+        );
+    }
+
+    #[test]
+    fn fingerprints_identify_keys() {
+        let keypair = Keypair::from_seed([7u8; 32]);
+        let other = Keypair::from_seed([8u8; 32]);
+
+        assert_eq!(
+            keypair.fingerprint(),
+            hash::sha256(keypair.public.as_bytes())
+        );
+        assert_ne!(keypair.fingerprint(), other.fingerprint());
+        assert_eq!(
+            keypair.short_fingerprint(),
+            hex::encode(&keypair.fingerprint()[..8])
+        );
+    }
+
+    #[test]
+    fn generate_batch_matches_generate() {
+        let keypairs = Keypair::generate_batch(&mut FixedRng(3), 2);
+        let expected = Keypair::generate(&mut FixedRng(3));
+
+        assert_eq!(keypairs.len(), 2);
+        for keypair in &keypairs {
+            assert!(keypair.secret == expected.secret);
+            assert!(keypair.public == expected.public);
+        }
+        assert!(Keypair::generate_batch(&mut OsRng {}, 0).is_empty());
+    }
+
+    #[test]
+    fn entropy_constructors_need_no_rng() {
+        assert!(
+            Keypair::generate_from_seed([7u8; 32]).public == Keypair::from_seed([7u8; 32]).public
+        );
+
+        let keypair = Keypair::from_entropy(&[9u8; 48]).unwrap();
+        assert!(keypair.secret == SecretKey::from_entropy(&[9u8; 48]).unwrap());
+        assert!(keypair.secret != SecretKey::from_entropy(&[9u8; 32]).unwrap());
+        assert_eq!(
+            SecretKey::from_entropy(&[9u8; 16]).err(),
+            Some(WotsError::LengthMismatch {
+                expected: 32,
+                actual: 16
+            })
+        );
     }
 }
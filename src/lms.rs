@@ -0,0 +1,1160 @@
+//! LMS and HSS signatures as specified by RFC 8554.
+//!
+//! This implements LM-OTS, LMS and the HSS hierarchy for the SHA-256
+//! parameter sets with `n = m = 32`, using the RFC's typecodes and wire
+//! format. Private keys are derived from a per-tree identifier `I` and a
+//! `SEED` as in appendix A of the RFC. Like [`xmss`](crate::xmss), a private
+//! key hashes every leaf once and then keeps a logarithmic traversal of its
+//! tree, so each signature computes at most `height` further leaves.
+use crate::{
+    error::WotsError,
+    hash::sha256,
+    merkle::{Traversal, TreeHasher},
+};
+use alloc::vec::Vec;
+use rand::{CryptoRng, RngCore};
+use subtle::ConstantTimeEq;
+
+/// Domain separator for the LM-OTS public key hash.
+const D_PBLC: u16 = 0x8080;
+
+/// Domain separator for the LM-OTS message hash.
+const D_MESG: u16 = 0x8181;
+
+/// Domain separator for Merkle leaves.
+const D_LEAF: u16 = 0x8282;
+
+/// Domain separator for interior Merkle nodes.
+const D_INTR: u16 = 0x8383;
+
+/// An LM-OTS parameter set.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct OtsParams {
+    typecode: u32,
+    w: u8,
+    p: usize,
+    ls: u32,
+}
+
+impl OtsParams {
+    /// LMOTS_SHA256_N32_W1.
+    pub const LMOTS_SHA256_N32_W1: OtsParams = OtsParams {
+        typecode: 1,
+        w: 1,
+        p: 265,
+        ls: 7,
+    };
+
+    /// LMOTS_SHA256_N32_W2.
+    pub const LMOTS_SHA256_N32_W2: OtsParams = OtsParams {
+        typecode: 2,
+        w: 2,
+        p: 133,
+        ls: 6,
+    };
+
+    /// LMOTS_SHA256_N32_W4.
+    pub const LMOTS_SHA256_N32_W4: OtsParams = OtsParams {
+        typecode: 3,
+        w: 4,
+        p: 67,
+        ls: 4,
+    };
+
+    /// LMOTS_SHA256_N32_W8.
+    pub const LMOTS_SHA256_N32_W8: OtsParams = OtsParams {
+        typecode: 4,
+        w: 8,
+        p: 34,
+        ls: 0,
+    };
+
+    /// Look up the parameter set registered under `typecode`.
+    pub fn from_typecode(typecode: u32) -> Option<OtsParams> {
+        [
+            OtsParams::LMOTS_SHA256_N32_W1,
+            OtsParams::LMOTS_SHA256_N32_W2,
+            OtsParams::LMOTS_SHA256_N32_W4,
+            OtsParams::LMOTS_SHA256_N32_W8,
+        ]
+        .into_iter()
+        .find(|params| params.typecode == typecode)
+    }
+
+    /// The typecode identifying this parameter set.
+    pub fn typecode(&self) -> u32 {
+        self.typecode
+    }
+
+    /// The Winternitz parameter, in bits per digit.
+    pub fn w(&self) -> u8 {
+        self.w
+    }
+
+    /// The number of chains in a key or signature.
+    pub fn chains(&self) -> usize {
+        self.p
+    }
+
+    /// The length of a serialized LM-OTS signature in bytes.
+    pub fn signature_length(&self) -> usize {
+        4 + 32 + 32 * self.p
+    }
+
+    /// The largest digit value, `2^w - 1`.
+    fn max_digit(&self) -> u8 {
+        ((1u16 << self.w) - 1) as u8
+    }
+
+    /// Digit `i` of `s` in base `2^w`, `coef` in the RFC.
+    fn coef(&self, s: &[u8], i: usize) -> u8 {
+        let per_byte = 8 / self.w as usize;
+        let shift = 8 - (self.w as usize * (i % per_byte) + self.w as usize);
+
+        (s[i / per_byte] >> shift) & self.max_digit()
+    }
+
+    /// The digits signed for the message hash `q`, checksum included.
+    fn digits(&self, q: &[u8; 32]) -> Vec<u8> {
+        let message_digits = 256 / self.w as usize;
+        let checksum: u32 = (0..message_digits)
+            .map(|i| u32::from(self.max_digit() - self.coef(q, i)))
+            .sum();
+
+        let mut s = [0u8; 34];
+        s[..32].copy_from_slice(q);
+        s[32..].copy_from_slice(&((checksum << self.ls) as u16).to_be_bytes());
+
+        (0..self.p).map(|i| self.coef(&s, i)).collect()
+    }
+}
+
+/// An LMS parameter set.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct LmsParams {
+    typecode: u32,
+    height: u32,
+}
+
+impl LmsParams {
+    /// LMS_SHA256_M32_H5.
+    pub const LMS_SHA256_M32_H5: LmsParams = LmsParams {
+        typecode: 5,
+        height: 5,
+    };
+
+    /// LMS_SHA256_M32_H10.
+    pub const LMS_SHA256_M32_H10: LmsParams = LmsParams {
+        typecode: 6,
+        height: 10,
+    };
+
+    /// LMS_SHA256_M32_H15.
+    pub const LMS_SHA256_M32_H15: LmsParams = LmsParams {
+        typecode: 7,
+        height: 15,
+    };
+
+    /// LMS_SHA256_M32_H20.
+    pub const LMS_SHA256_M32_H20: LmsParams = LmsParams {
+        typecode: 8,
+        height: 20,
+    };
+
+    /// LMS_SHA256_M32_H25.
+    pub const LMS_SHA256_M32_H25: LmsParams = LmsParams {
+        typecode: 9,
+        height: 25,
+    };
+
+    /// Look up the parameter set registered under `typecode`.
+    pub fn from_typecode(typecode: u32) -> Option<LmsParams> {
+        [
+            LmsParams::LMS_SHA256_M32_H5,
+            LmsParams::LMS_SHA256_M32_H10,
+            LmsParams::LMS_SHA256_M32_H15,
+            LmsParams::LMS_SHA256_M32_H20,
+            LmsParams::LMS_SHA256_M32_H25,
+        ]
+        .into_iter()
+        .find(|params| params.typecode == typecode)
+    }
+
+    /// The typecode identifying this parameter set.
+    pub fn typecode(&self) -> u32 {
+        self.typecode
+    }
+
+    /// The height of the tree.
+    pub fn height(&self) -> u32 {
+        self.height
+    }
+
+    /// The number of signatures a key can make.
+    pub fn capacity(&self) -> u32 {
+        1 << self.height
+    }
+}
+
+/// Hash `I || u32str(q) || u16str(tag)` followed by `parts`.
+fn hash(id: &[u8; 16], q: u32, tag: u16, parts: &[&[u8]]) -> [u8; 32] {
+    let mut input = Vec::with_capacity(22 + parts.iter().map(|part| part.len()).sum::<usize>());
+    input.extend_from_slice(id);
+    input.extend_from_slice(&q.to_be_bytes());
+    input.extend_from_slice(&tag.to_be_bytes());
+    for part in parts {
+        input.extend_from_slice(part);
+    }
+
+    sha256(&input)
+}
+
+/// Advance chain `i` of the LM-OTS key `q` from step `from` to step `to`.
+fn chain(id: &[u8; 16], q: u32, i: usize, mut x: [u8; 32], from: u8, to: u8) -> [u8; 32] {
+    let mut input = [0u8; 55];
+    input[..16].copy_from_slice(id);
+    input[16..20].copy_from_slice(&q.to_be_bytes());
+    input[20..22].copy_from_slice(&(i as u16).to_be_bytes());
+
+    for j in from..to {
+        input[22] = j;
+        input[23..].copy_from_slice(&x);
+        x = sha256(&input);
+    }
+
+    x
+}
+
+/// Compress the chain ends `z` of the LM-OTS key `q` into its public key.
+fn ots_public_key(id: &[u8; 16], q: u32, z: &[[u8; 32]]) -> [u8; 32] {
+    hash(id, q, D_PBLC, &[z.as_flattened()])
+}
+
+/// The hash of leaf `q` of a tree of `height`.
+fn leaf(id: &[u8; 16], height: u32, q: u32, ots_public_key: &[u8; 32]) -> [u8; 32] {
+    hash(id, (1 << height) + q, D_LEAF, &[ots_public_key])
+}
+
+/// Read a big-endian `u32` at `offset`, if `bytes` is long enough.
+fn read_u32(bytes: &[u8], offset: usize) -> Result<u32, WotsError> {
+    bytes
+        .get(offset..offset + 4)
+        .map(|word| u32::from_be_bytes(word.try_into().unwrap()))
+        .ok_or(WotsError::LengthMismatch {
+            expected: offset + 4,
+            actual: bytes.len(),
+        })
+}
+
+/// Split `bytes` into 32-byte values, checking the length is `32 * count`.
+fn read_nodes(bytes: &[u8], count: usize) -> Result<Vec<[u8; 32]>, WotsError> {
+    if bytes.len() != 32 * count {
+        return Err(WotsError::LengthMismatch {
+            expected: 32 * count,
+            actual: bytes.len(),
+        });
+    }
+
+    Ok(bytes
+        .chunks_exact(32)
+        .map(|chunk| chunk.try_into().unwrap())
+        .collect())
+}
+
+/// The parameters and secrets that determine the leaves of an LMS tree.
+struct Tree {
+    lms: LmsParams,
+    ots: OtsParams,
+    id: [u8; 16],
+    seed: [u8; 32],
+}
+
+impl Tree {
+    /// Chain `i` of the LM-OTS private key `q`, as in appendix A.
+    fn ots_secret(&self, q: u32, i: usize) -> [u8; 32] {
+        hash(&self.id, q, i as u16, &[&[0xff], &self.seed])
+    }
+}
+
+impl TreeHasher for Tree {
+    fn leaf(&self, index: usize) -> [u8; 32] {
+        let q = index as u32;
+        let z: Vec<[u8; 32]> = (0..self.ots.p)
+            .map(|i| {
+                let x = self.ots_secret(q, i);
+                chain(&self.id, q, i, x, 0, self.ots.max_digit())
+            })
+            .collect();
+
+        leaf(
+            &self.id,
+            self.lms.height,
+            q,
+            &ots_public_key(&self.id, q, &z),
+        )
+    }
+
+    /// The node numbered `2^(h - height) + index` in the RFC's numbering,
+    /// with the root at 1.
+    fn parent(&self, height: u32, index: usize, left: &[u8; 32], right: &[u8; 32]) -> [u8; 32] {
+        let r = (1 << (self.lms.height - height)) + index as u32;
+
+        hash(&self.id, r, D_INTR, &[left, right])
+    }
+}
+
+/// An LMS private key.
+///
+/// Signing advances the key to its next leaf. The key must be persisted
+/// with [`to_bytes`](Self::to_bytes) after every signature, before the
+/// signature is released, and restored with
+/// [`from_bytes`](Self::from_bytes); deriving it again from its seed starts
+/// over at leaf 0 and reuses one-time keys.
+pub struct SecretKey {
+    tree: Tree,
+    traversal: Traversal,
+}
+
+impl SecretKey {
+    /// The length of a serialized LMS private key in bytes.
+    pub const LENGTH: usize = 60;
+
+    /// Generate an LMS private key with a random `I` and `SEED`.
+    pub fn generate<R>(csprng: &mut R, lms: LmsParams, ots: OtsParams) -> Self
+    where
+        R: CryptoRng + RngCore,
+    {
+        let mut id = [0u8; 16];
+        let mut seed = [0u8; 32];
+        csprng.fill_bytes(&mut id);
+        csprng.fill_bytes(&mut seed);
+
+        Self::from_seed(lms, ots, id, seed)
+    }
+
+    /// Derive an LMS private key from its identifier `id` and `seed`.
+    ///
+    /// The key starts at leaf 0, so this is only for new keys; restore a
+    /// key that has signed with [`from_bytes`](Self::from_bytes).
+    pub fn from_seed(lms: LmsParams, ots: OtsParams, id: [u8; 16], seed: [u8; 32]) -> Self {
+        Self::at_index(lms, ots, id, seed, 0)
+    }
+
+    /// Derive the key from its seed, with leaf `q` the next to sign.
+    fn at_index(lms: LmsParams, ots: OtsParams, id: [u8; 16], seed: [u8; 32], q: u32) -> Self {
+        let tree = Tree { lms, ots, id, seed };
+        let traversal = Traversal::new(&tree, lms.height, q as usize);
+
+        SecretKey { tree, traversal }
+    }
+
+    /// The index of the next leaf to sign with.
+    pub fn index(&self) -> u32 {
+        self.traversal.index() as u32
+    }
+
+    /// The number of signatures this key can still make.
+    pub fn remaining(&self) -> u32 {
+        self.tree.lms.capacity() - self.index()
+    }
+
+    /// The public key of this private key.
+    pub fn public_key(&self) -> PublicKey {
+        PublicKey {
+            lms: self.tree.lms,
+            ots: self.tree.ots,
+            id: self.tree.id,
+            root: self.traversal.root(),
+        }
+    }
+
+    /// Sign a `message` with the next unused leaf, drawing the randomizer
+    /// `C` from `csprng`.
+    ///
+    /// # Errors
+    ///
+    /// Returns `WotsError::IndexOutOfRange` once every leaf has been used.
+    pub fn sign<R>(&mut self, csprng: &mut R, message: &[u8]) -> Result<Signature, WotsError>
+    where
+        R: CryptoRng + RngCore,
+    {
+        let Tree { lms, ots, id, .. } = self.tree;
+        let q = self.index();
+        if q >= lms.capacity() {
+            return Err(WotsError::IndexOutOfRange {
+                index: q as usize,
+                len: lms.capacity() as usize,
+            });
+        }
+
+        let mut c = [0u8; 32];
+        csprng.fill_bytes(&mut c);
+
+        let digest = hash(&id, q, D_MESG, &[&c, message]);
+        let y = ots
+            .digits(&digest)
+            .into_iter()
+            .enumerate()
+            .map(|(i, digit)| chain(&id, q, i, self.tree.ots_secret(q, i), 0, digit))
+            .collect();
+
+        let path = self.traversal.auth_path().to_vec();
+        self.traversal.advance(&self.tree);
+
+        Ok(Signature {
+            q,
+            ots,
+            c,
+            y,
+            lms,
+            path,
+        })
+    }
+
+    /// Serialize this private key as
+    /// `lmstype || otstype || I || SEED || u32str(q)`.
+    ///
+    /// The bytes are as secret as the key and hold its next leaf index.
+    pub fn to_bytes(&self) -> [u8; Self::LENGTH] {
+        let mut bytes = [0u8; Self::LENGTH];
+        bytes[..4].copy_from_slice(&self.tree.lms.typecode.to_be_bytes());
+        bytes[4..8].copy_from_slice(&self.tree.ots.typecode.to_be_bytes());
+        bytes[8..24].copy_from_slice(&self.tree.id);
+        bytes[24..56].copy_from_slice(&self.tree.seed);
+        bytes[56..].copy_from_slice(&self.index().to_be_bytes());
+
+        bytes
+    }
+
+    /// Restore a private key serialized by [`to_bytes`](Self::to_bytes),
+    /// resuming at its next leaf.
+    ///
+    /// Like generation, this computes every leaf of the tree.
+    ///
+    /// # Errors
+    ///
+    /// Returns `WotsError::LengthMismatch` if `bytes` has the wrong length,
+    /// `WotsError::BadMagic` if it names an unsupported typecode and
+    /// `WotsError::IndexOutOfRange` if its index is beyond the capacity of
+    /// the tree.
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self, WotsError> {
+        if bytes.len() != Self::LENGTH {
+            return Err(WotsError::LengthMismatch {
+                expected: Self::LENGTH,
+                actual: bytes.len(),
+            });
+        }
+
+        let lms = LmsParams::from_typecode(read_u32(bytes, 0)?).ok_or(WotsError::BadMagic)?;
+        let ots = OtsParams::from_typecode(read_u32(bytes, 4)?).ok_or(WotsError::BadMagic)?;
+        let q = read_u32(bytes, 56)?;
+        if q > lms.capacity() {
+            return Err(WotsError::IndexOutOfRange {
+                index: q as usize,
+                len: lms.capacity() as usize,
+            });
+        }
+
+        Ok(Self::at_index(
+            lms,
+            ots,
+            bytes[8..24].try_into().unwrap(),
+            bytes[24..56].try_into().unwrap(),
+            q,
+        ))
+    }
+}
+
+/// An LMS public key.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct PublicKey {
+    lms: LmsParams,
+    ots: OtsParams,
+    id: [u8; 16],
+    root: [u8; 32],
+}
+
+impl PublicKey {
+    /// The length of a serialized LMS public key in bytes.
+    pub const LENGTH: usize = 56;
+
+    /// Verify an LMS `signature` on a `message` with this public key.
+    pub fn verify(&self, message: &[u8], signature: &Signature) -> bool {
+        if signature.lms != self.lms
+            || signature.ots != self.ots
+            || signature.q >= self.lms.capacity()
+            || signature.y.len() != self.ots.p
+            || signature.path.len() != self.lms.height as usize
+        {
+            return false;
+        }
+
+        let q = signature.q;
+        let digest = hash(&self.id, q, D_MESG, &[&signature.c, message]);
+        let z: Vec<[u8; 32]> = self
+            .ots
+            .digits(&digest)
+            .into_iter()
+            .zip(signature.y.iter())
+            .enumerate()
+            .map(|(i, (digit, y))| chain(&self.id, q, i, *y, digit, self.ots.max_digit()))
+            .collect();
+
+        let mut node = (1 << self.lms.height) + q;
+        let mut tmp = leaf(
+            &self.id,
+            self.lms.height,
+            q,
+            &ots_public_key(&self.id, q, &z),
+        );
+        for sibling in signature.path.iter() {
+            tmp = if node & 1 == 1 {
+                hash(&self.id, node / 2, D_INTR, &[sibling, &tmp])
+            } else {
+                hash(&self.id, node / 2, D_INTR, &[&tmp, sibling])
+            };
+            node /= 2;
+        }
+
+        tmp.ct_eq(&self.root).into()
+    }
+
+    /// Serialize this public key as `lmstype || otstype || I || T[1]`.
+    pub fn to_bytes(&self) -> [u8; Self::LENGTH] {
+        let mut bytes = [0u8; Self::LENGTH];
+        bytes[..4].copy_from_slice(&self.lms.typecode.to_be_bytes());
+        bytes[4..8].copy_from_slice(&self.ots.typecode.to_be_bytes());
+        bytes[8..24].copy_from_slice(&self.id);
+        bytes[24..].copy_from_slice(&self.root);
+
+        bytes
+    }
+
+    /// Parse a public key serialized by [`to_bytes`](Self::to_bytes).
+    ///
+    /// # Errors
+    ///
+    /// Returns `WotsError::LengthMismatch` if `bytes` has the wrong length
+    /// and `WotsError::BadMagic` if it names an unsupported typecode.
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self, WotsError> {
+        if bytes.len() != Self::LENGTH {
+            return Err(WotsError::LengthMismatch {
+                expected: Self::LENGTH,
+                actual: bytes.len(),
+            });
+        }
+
+        Ok(PublicKey {
+            lms: LmsParams::from_typecode(read_u32(bytes, 0)?).ok_or(WotsError::BadMagic)?,
+            ots: OtsParams::from_typecode(read_u32(bytes, 4)?).ok_or(WotsError::BadMagic)?,
+            id: bytes[8..24].try_into().unwrap(),
+            root: bytes[24..].try_into().unwrap(),
+        })
+    }
+}
+
+/// An LMS signature.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Signature {
+    /// The index of the leaf that made this signature.
+    pub q: u32,
+    /// The LM-OTS parameter set of the signing leaf.
+    pub ots: OtsParams,
+    /// The randomizer of the message hash.
+    pub c: [u8; 32],
+    /// The LM-OTS signature chains.
+    pub y: Vec<[u8; 32]>,
+    /// The LMS parameter set of the signing tree.
+    pub lms: LmsParams,
+    /// The authentication path of the leaf, from the bottom up.
+    pub path: Vec<[u8; 32]>,
+}
+
+impl Signature {
+    /// The length of this signature when serialized.
+    pub fn encoded_len(&self) -> usize {
+        4 + self.ots.signature_length() + 4 + 32 * self.path.len()
+    }
+
+    /// Serialize this signature as
+    /// `u32str(q) || lmots_signature || lmstype || path`.
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let mut bytes = Vec::with_capacity(self.encoded_len());
+        bytes.extend_from_slice(&self.q.to_be_bytes());
+        bytes.extend_from_slice(&self.ots.typecode.to_be_bytes());
+        bytes.extend_from_slice(&self.c);
+        bytes.extend_from_slice(self.y.as_flattened());
+        bytes.extend_from_slice(&self.lms.typecode.to_be_bytes());
+        bytes.extend_from_slice(self.path.as_flattened());
+
+        bytes
+    }
+
+    /// Parse a signature serialized by [`to_bytes`](Self::to_bytes).
+    ///
+    /// # Errors
+    ///
+    /// Returns `WotsError::LengthMismatch` if `bytes` has the wrong length
+    /// for the typecodes it names and `WotsError::BadMagic` if they are
+    /// unsupported.
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self, WotsError> {
+        let (ots, lms) = Self::parse_params(bytes)?;
+        let lms_offset = 4 + ots.signature_length();
+
+        Ok(Signature {
+            q: read_u32(bytes, 0)?,
+            ots,
+            c: bytes[8..40].try_into().unwrap(),
+            y: read_nodes(&bytes[40..lms_offset], ots.p)?,
+            lms,
+            path: read_nodes(&bytes[lms_offset + 4..], lms.height as usize)?,
+        })
+    }
+
+    /// The parameter sets named by the signature at the start of `bytes`.
+    fn parse_params(bytes: &[u8]) -> Result<(OtsParams, LmsParams), WotsError> {
+        let ots = OtsParams::from_typecode(read_u32(bytes, 4)?).ok_or(WotsError::BadMagic)?;
+        let lms = LmsParams::from_typecode(read_u32(bytes, 4 + ots.signature_length())?)
+            .ok_or(WotsError::BadMagic)?;
+
+        Ok((ots, lms))
+    }
+
+    /// The length of the signature at the start of `bytes`, read from the
+    /// typecodes it names.
+    fn parse_len(bytes: &[u8]) -> Result<usize, WotsError> {
+        let (ots, lms) = Self::parse_params(bytes)?;
+
+        Ok(4 + ots.signature_length() + 4 + 32 * lms.height as usize)
+    }
+}
+
+/// The maximum number of levels in an HSS hierarchy.
+pub const MAX_LEVELS: usize = 8;
+
+/// An HSS private key, a hierarchy of LMS private keys.
+///
+/// The top level signs the public key of the level below it, down to the
+/// bottom level, which signs messages. When the bottom key is used up it is
+/// replaced by a fresh key signed by the next leaf of its parent.
+///
+/// As with [`SecretKey`], the key must be persisted with
+/// [`to_bytes`](Self::to_bytes) after every signature, before the
+/// signature is released.
+pub struct HssSecretKey {
+    levels: Vec<(LmsParams, OtsParams)>,
+    keys: Vec<SecretKey>,
+    signed: Vec<Signature>,
+}
+
+impl HssSecretKey {
+    /// Generate an HSS private key with one LMS tree per entry of `levels`,
+    /// from the top down.
+    ///
+    /// # Errors
+    ///
+    /// Returns `WotsError::IndexOutOfRange` unless there are between 1 and
+    /// [`MAX_LEVELS`] levels.
+    pub fn generate<R>(csprng: &mut R, levels: &[(LmsParams, OtsParams)]) -> Result<Self, WotsError>
+    where
+        R: CryptoRng + RngCore,
+    {
+        if levels.is_empty() || levels.len() > MAX_LEVELS {
+            return Err(WotsError::IndexOutOfRange {
+                index: levels.len(),
+                len: MAX_LEVELS + 1,
+            });
+        }
+
+        let (lms, ots) = levels[0];
+        let mut secret_key = HssSecretKey {
+            levels: levels.to_vec(),
+            keys: Vec::from([SecretKey::generate(csprng, lms, ots)]),
+            signed: Vec::new(),
+        };
+        secret_key.refill(csprng, 1)?;
+
+        Ok(secret_key)
+    }
+
+    /// The public key of this private key.
+    pub fn public_key(&self) -> HssPublicKey {
+        HssPublicKey {
+            levels: self.levels.len() as u32,
+            top: self.keys[0].public_key(),
+        }
+    }
+
+    /// Sign a `message` with the next unused leaf of the bottom tree.
+    ///
+    /// # Errors
+    ///
+    /// Returns `WotsError::IndexOutOfRange` once every leaf of every tree
+    /// has been used.
+    pub fn sign<R>(&mut self, csprng: &mut R, message: &[u8]) -> Result<HssSignature, WotsError>
+    where
+        R: CryptoRng + RngCore,
+    {
+        let bottom = self.keys.len() - 1;
+        if self.keys[bottom].remaining() == 0 {
+            let level = self
+                .keys
+                .iter()
+                .rposition(|key| key.remaining() > 0)
+                .ok_or(WotsError::IndexOutOfRange {
+                    index: bottom,
+                    len: self.keys.len(),
+                })?;
+            self.keys.truncate(level + 1);
+            self.signed.truncate(level);
+            self.refill(csprng, level + 1)?;
+        }
+
+        let signature = self.keys[bottom].sign(csprng, message)?;
+
+        Ok(HssSignature {
+            signed_public_keys: self
+                .signed
+                .iter()
+                .cloned()
+                .zip(self.keys[1..].iter().map(SecretKey::public_key))
+                .collect(),
+            signature,
+        })
+    }
+
+    /// Serialize this private key as `u32str(L)`, the [`SecretKey`] of every
+    /// level from the top down, then the signature of each level on the
+    /// public key of the level below it.
+    ///
+    /// The bytes are as secret as the key and hold the next leaf index of
+    /// every level.
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let mut bytes = Vec::new();
+        bytes.extend_from_slice(&(self.keys.len() as u32).to_be_bytes());
+        for key in self.keys.iter() {
+            bytes.extend_from_slice(&key.to_bytes());
+        }
+        for signature in self.signed.iter() {
+            bytes.extend_from_slice(&signature.to_bytes());
+        }
+
+        bytes
+    }
+
+    /// Restore a private key serialized by [`to_bytes`](Self::to_bytes),
+    /// resuming every level at its next leaf.
+    ///
+    /// Like generation, this computes every leaf of every level.
+    ///
+    /// # Errors
+    ///
+    /// Returns the errors of [`SecretKey::from_bytes`] and
+    /// [`Signature::from_bytes`], `WotsError::IndexOutOfRange` for an
+    /// unsupported number of levels and `WotsError::InvalidEncoding` if a
+    /// level's signature does not verify the public key below it.
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self, WotsError> {
+        let count = read_u32(bytes, 0)? as usize;
+        if count == 0 || count > MAX_LEVELS {
+            return Err(WotsError::IndexOutOfRange {
+                index: count,
+                len: MAX_LEVELS + 1,
+            });
+        }
+
+        let keys_end = 4 + count * SecretKey::LENGTH;
+        if bytes.len() < keys_end {
+            return Err(WotsError::LengthMismatch {
+                expected: keys_end,
+                actual: bytes.len(),
+            });
+        }
+        let keys = bytes[4..keys_end]
+            .chunks_exact(SecretKey::LENGTH)
+            .map(SecretKey::from_bytes)
+            .collect::<Result<Vec<_>, _>>()?;
+
+        let mut rest = &bytes[keys_end..];
+        let mut signed = Vec::with_capacity(count - 1);
+        for level in 1..count {
+            let len = Signature::parse_len(rest)?;
+            if rest.len() < len {
+                return Err(WotsError::LengthMismatch {
+                    expected: len,
+                    actual: rest.len(),
+                });
+            }
+
+            let signature = Signature::from_bytes(&rest[..len])?;
+            let child = keys[level].public_key().to_bytes();
+            if !keys[level - 1].public_key().verify(&child, &signature) {
+                return Err(WotsError::InvalidEncoding);
+            }
+            signed.push(signature);
+            rest = &rest[len..];
+        }
+        if !rest.is_empty() {
+            return Err(WotsError::LengthMismatch {
+                expected: bytes.len() - rest.len(),
+                actual: bytes.len(),
+            });
+        }
+
+        Ok(HssSecretKey {
+            levels: keys
+                .iter()
+                .map(|key| (key.tree.lms, key.tree.ots))
+                .collect(),
+            keys,
+            signed,
+        })
+    }
+
+    /// Generate fresh keys for the levels from `from` down, each signed by
+    /// its parent.
+    fn refill<R>(&mut self, csprng: &mut R, from: usize) -> Result<(), WotsError>
+    where
+        R: CryptoRng + RngCore,
+    {
+        for level in from..self.levels.len() {
+            let (lms, ots) = self.levels[level];
+            let key = SecretKey::generate(csprng, lms, ots);
+            let signature = self.keys[level - 1].sign(csprng, &key.public_key().to_bytes())?;
+
+            self.keys.push(key);
+            self.signed.push(signature);
+        }
+
+        Ok(())
+    }
+}
+
+/// An HSS public key, the number of levels and the top-level LMS key.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct HssPublicKey {
+    levels: u32,
+    top: PublicKey,
+}
+
+impl HssPublicKey {
+    /// Verify an HSS `signature` on a `message` with this public key.
+    pub fn verify(&self, message: &[u8], signature: &HssSignature) -> bool {
+        if signature.signed_public_keys.len() + 1 != self.levels as usize {
+            return false;
+        }
+
+        let mut key = self.top;
+        for (signed, public_key) in signature.signed_public_keys.iter() {
+            if !key.verify(&public_key.to_bytes(), signed) {
+                return false;
+            }
+            key = *public_key;
+        }
+
+        key.verify(message, &signature.signature)
+    }
+
+    /// Serialize this public key as `u32str(L) || pub[0]`.
+    pub fn to_bytes(&self) -> [u8; 4 + PublicKey::LENGTH] {
+        let mut bytes = [0u8; 4 + PublicKey::LENGTH];
+        bytes[..4].copy_from_slice(&self.levels.to_be_bytes());
+        bytes[4..].copy_from_slice(&self.top.to_bytes());
+
+        bytes
+    }
+
+    /// Parse a public key serialized by [`to_bytes`](Self::to_bytes).
+    ///
+    /// # Errors
+    ///
+    /// Returns `WotsError::LengthMismatch` if `bytes` has the wrong length,
+    /// `WotsError::IndexOutOfRange` for an unsupported number of levels and
+    /// `WotsError::BadMagic` for unsupported typecodes.
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self, WotsError> {
+        if bytes.len() != 4 + PublicKey::LENGTH {
+            return Err(WotsError::LengthMismatch {
+                expected: 4 + PublicKey::LENGTH,
+                actual: bytes.len(),
+            });
+        }
+
+        let levels = read_u32(bytes, 0)?;
+        if levels == 0 || levels as usize > MAX_LEVELS {
+            return Err(WotsError::IndexOutOfRange {
+                index: levels as usize,
+                len: MAX_LEVELS + 1,
+            });
+        }
+
+        Ok(HssPublicKey {
+            levels,
+            top: PublicKey::from_bytes(&bytes[4..])?,
+        })
+    }
+}
+
+/// An HSS signature.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct HssSignature {
+    /// The public keys of the lower levels, each with its parent's
+    /// signature on it, from the top down.
+    pub signed_public_keys: Vec<(Signature, PublicKey)>,
+    /// The bottom-level signature on the message.
+    pub signature: Signature,
+}
+
+impl HssSignature {
+    /// Serialize this signature as
+    /// `u32str(Nspk) || signed_pub_key[0] || ... || sig[Nspk]`.
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let mut bytes = Vec::new();
+        bytes.extend_from_slice(&(self.signed_public_keys.len() as u32).to_be_bytes());
+        for (signature, public_key) in self.signed_public_keys.iter() {
+            bytes.extend_from_slice(&signature.to_bytes());
+            bytes.extend_from_slice(&public_key.to_bytes());
+        }
+        bytes.extend_from_slice(&self.signature.to_bytes());
+
+        bytes
+    }
+
+    /// Parse a signature serialized by [`to_bytes`](Self::to_bytes).
+    ///
+    /// # Errors
+    ///
+    /// Returns `WotsError::LengthMismatch` if `bytes` is truncated or too
+    /// long, `WotsError::IndexOutOfRange` for an unsupported number of
+    /// levels and `WotsError::BadMagic` for unsupported typecodes.
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self, WotsError> {
+        let count = read_u32(bytes, 0)? as usize;
+        if count >= MAX_LEVELS {
+            return Err(WotsError::IndexOutOfRange {
+                index: count + 1,
+                len: MAX_LEVELS + 1,
+            });
+        }
+
+        let mut rest = &bytes[4..];
+        let mut signed_public_keys = Vec::with_capacity(count);
+        for _ in 0..count {
+            let len = Signature::parse_len(rest)?;
+            let end = len + PublicKey::LENGTH;
+            if rest.len() < end {
+                return Err(WotsError::LengthMismatch {
+                    expected: end,
+                    actual: rest.len(),
+                });
+            }
+
+            signed_public_keys.push((
+                Signature::from_bytes(&rest[..len])?,
+                PublicKey::from_bytes(&rest[len..end])?,
+            ));
+            rest = &rest[end..];
+        }
+
+        Ok(HssSignature {
+            signed_public_keys,
+            signature: Signature::from_bytes(rest)?,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// An RNG replaying fixed bytes, standing in for the RFC's randomizers.
+    struct Replay<'a>(&'a [u8]);
+
+    impl RngCore for Replay<'_> {
+        fn next_u32(&mut self) -> u32 {
+            let mut bytes = [0u8; 4];
+            self.fill_bytes(&mut bytes);
+            u32::from_le_bytes(bytes)
+        }
+
+        fn next_u64(&mut self) -> u64 {
+            let mut bytes = [0u8; 8];
+            self.fill_bytes(&mut bytes);
+            u64::from_le_bytes(bytes)
+        }
+
+        fn fill_bytes(&mut self, dest: &mut [u8]) {
+            dest.copy_from_slice(&self.0[..dest.len()]);
+            self.0 = &self.0[dest.len()..];
+        }
+
+        fn try_fill_bytes(&mut self, dest: &mut [u8]) -> Result<(), rand::Error> {
+            self.fill_bytes(dest);
+            Ok(())
+        }
+    }
+
+    impl CryptoRng for Replay<'_> {}
+
+    fn unhex<const N: usize>(text: &str) -> [u8; N] {
+        crate::hex::decode(text).unwrap().try_into().unwrap()
+    }
+
+    // RFC 8554 appendix F, test case 2, second-level LMS key.
+    #[test]
+    fn matches_rfc8554_test_case_2() {
+        let secret_key = SecretKey::from_seed(
+            LmsParams::LMS_SHA256_M32_H5,
+            OtsParams::LMOTS_SHA256_N32_W8,
+            unhex("215f83b7ccb9acbcd08db97b0d04dc2b"),
+            unhex("a1c4696e2608035a886100d05cd99945eb3370731884a8235e2fb3d4d71f2547"),
+        );
+        let public_key = secret_key.public_key();
+        assert_eq!(
+            crate::hex::encode(&public_key.root),
+            "a1cd035833e0e90059603f26e07ad2aad152338e7a5e5984bcd5f7bb4eba40b7"
+        );
+
+        let message = b"The enumeration in the Constitution, of certain rights, shall not be \
+            construed to deny or disparage others retained by the people.\n";
+        let c: [u8; 32] = unhex("0eb1ed54a2460d512388cad533138d240534e97b1e82d33bd927d201dfc24ebb");
+
+        let mut bytes = secret_key.to_bytes();
+        bytes[56..].copy_from_slice(&4u32.to_be_bytes());
+        let mut secret_key = SecretKey::from_bytes(&bytes).unwrap();
+        let signature = secret_key.sign(&mut Replay(&c), message).unwrap();
+        let bytes = signature.to_bytes();
+
+        assert_eq!(bytes.len(), 1292);
+        assert_eq!(
+            crate::hex::encode(&sha256(&bytes)),
+            "987a83f7670a93837c484888fde579ca3653db8b66c9339b3c03b1e9b949d771"
+        );
+        assert!(public_key.verify(message, &signature));
+        assert!(!public_key.verify(b"hello", &signature));
+        assert_eq!(Signature::from_bytes(&bytes), Ok(signature));
+    }
+
+    #[test]
+    fn public_key_round_trip() {
+        let secret_key = SecretKey::from_seed(
+            LmsParams::LMS_SHA256_M32_H5,
+            OtsParams::LMOTS_SHA256_N32_W8,
+            [1u8; 16],
+            [2u8; 32],
+        );
+        let public_key = secret_key.public_key();
+
+        let bytes = public_key.to_bytes();
+        assert_eq!(&bytes[..8], &[0, 0, 0, 5, 0, 0, 0, 4]);
+        assert_eq!(PublicKey::from_bytes(&bytes), Ok(public_key));
+
+        let mut unknown = bytes;
+        unknown[3] = 42;
+        assert_eq!(PublicKey::from_bytes(&unknown), Err(WotsError::BadMagic));
+    }
+
+    #[test]
+    fn secret_key_resumes_from_bytes() {
+        let mut csprng = rand::rngs::OsRng;
+        let mut secret_key = SecretKey::from_seed(
+            LmsParams::LMS_SHA256_M32_H5,
+            OtsParams::LMOTS_SHA256_N32_W4,
+            [1u8; 16],
+            [2u8; 32],
+        );
+        let public_key = secret_key.public_key();
+        for _ in 0..5 {
+            secret_key.sign(&mut csprng, b"hello").unwrap();
+        }
+
+        let bytes = secret_key.to_bytes();
+        assert_eq!(&bytes[56..], &[0, 0, 0, 5]);
+
+        let mut restored = SecretKey::from_bytes(&bytes).unwrap();
+        assert_eq!(restored.index(), 5);
+        assert_eq!(restored.public_key(), public_key);
+        for _ in 5..32 {
+            let c = [9u8; 32];
+            let signature = restored.sign(&mut Replay(&c), b"hello").unwrap();
+            assert_eq!(
+                signature,
+                secret_key.sign(&mut Replay(&c), b"hello").unwrap()
+            );
+            assert!(public_key.verify(b"hello", &signature));
+        }
+        assert_eq!(
+            SecretKey::from_bytes(&restored.to_bytes())
+                .unwrap()
+                .remaining(),
+            0
+        );
+
+        let mut beyond = bytes;
+        beyond[56..].copy_from_slice(&33u32.to_be_bytes());
+        assert_eq!(
+            SecretKey::from_bytes(&beyond).err(),
+            Some(WotsError::IndexOutOfRange { index: 33, len: 32 })
+        );
+        assert_eq!(
+            SecretKey::from_bytes(&bytes[1..]).err(),
+            Some(WotsError::LengthMismatch {
+                expected: SecretKey::LENGTH,
+                actual: SecretKey::LENGTH - 1,
+            })
+        );
+    }
+
+    #[test]
+    fn hss_secret_key_resumes_from_bytes() {
+        let mut csprng = rand::rngs::OsRng;
+        let level = (LmsParams::LMS_SHA256_M32_H5, OtsParams::LMOTS_SHA256_N32_W4);
+        let mut secret_key = HssSecretKey::generate(&mut csprng, &[level, level]).unwrap();
+        let public_key = secret_key.public_key();
+        secret_key.sign(&mut csprng, b"hello").unwrap();
+
+        let bytes = secret_key.to_bytes();
+        let mut restored = HssSecretKey::from_bytes(&bytes).unwrap();
+        assert_eq!(restored.public_key(), public_key);
+        assert_eq!(restored.keys[0].index(), 1);
+        assert_eq!(restored.keys[1].index(), 1);
+
+        let signature = restored.sign(&mut csprng, b"hello").unwrap();
+        assert_eq!(signature.signature.q, 1);
+        assert!(public_key.verify(b"hello", &signature));
+
+        let mut tampered = bytes.clone();
+        tampered[4 + SecretKey::LENGTH + 30] ^= 1;
+        assert_eq!(
+            HssSecretKey::from_bytes(&tampered).err(),
+            Some(WotsError::InvalidEncoding)
+        );
+        assert!(HssSecretKey::from_bytes(&bytes[..bytes.len() - 1]).is_err());
+    }
+
+    #[test]
+    fn hss_sign_and_verify_across_trees() {
+        let mut csprng = rand::rngs::OsRng;
+        let level = (LmsParams::LMS_SHA256_M32_H5, OtsParams::LMOTS_SHA256_N32_W4);
+        let mut secret_key = HssSecretKey::generate(&mut csprng, &[level, level]).unwrap();
+        let public_key = secret_key.public_key();
+
+        // Use up the first bottom tree so the next signature needs a new one.
+        let mut bytes = secret_key.keys[1].to_bytes();
+        bytes[56..].copy_from_slice(&31u32.to_be_bytes());
+        secret_key.keys[1] = SecretKey::from_bytes(&bytes).unwrap();
+        let first = secret_key.sign(&mut csprng, b"hello").unwrap();
+        let second = secret_key.sign(&mut csprng, b"hello").unwrap();
+
+        assert!(public_key.verify(b"hello", &first));
+        assert!(public_key.verify(b"hello", &second));
+        assert!(!public_key.verify(b"world", &second));
+        assert_eq!(second.signed_public_keys[0].0.q, 1);
+        assert!(first.signed_public_keys[0].1 != second.signed_public_keys[0].1);
+
+        let bytes = second.to_bytes();
+        assert_eq!(HssSignature::from_bytes(&bytes), Ok(second));
+        assert_eq!(
+            HssPublicKey::from_bytes(&public_key.to_bytes()),
+            Ok(public_key)
+        );
+        assert!(HssSignature::from_bytes(&bytes[..bytes.len() - 1]).is_err());
+    }
+}
@@ -0,0 +1,682 @@
+//! Merkle trees of WOTS keypairs, signing many messages under one root.
+use crate::{
+    digest32::{Digest32, Seed},
+    error::WotsError,
+    hash::sha256,
+    keypair::Keypair,
+    public::recover,
+    signature::Signature,
+};
+use alloc::{vec, vec::Vec};
+use rand::{CryptoRng, RngCore};
+use subtle::ConstantTimeEq;
+
+/// Hash two sibling nodes into their parent node.
+pub(crate) fn parent(left: &[u8; 32], right: &[u8; 32]) -> [u8; 32] {
+    let mut input = [0u8; 64];
+    input[..32].copy_from_slice(left);
+    input[32..].copy_from_slice(right);
+
+    sha256(&input)
+}
+
+/// A Merkle tree of WOTS keypairs.
+///
+/// Each leaf is the [commitment](crate::PublicKey::commitment) of one
+/// keypair's public key, and the root serves as the public key for all of
+/// them. Every keypair must still sign at most one message. The leaves are
+/// padded with zeros to a power of two.
+pub struct MerkleKeypair {
+    keypairs: Vec<Keypair>,
+    levels: Vec<Vec<[u8; 32]>>,
+}
+
+impl MerkleKeypair {
+    /// Generate a Merkle tree of `2^height` fresh WOTS keypairs.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use rand::rngs::OsRng;
+    /// use wots_rs::MerkleKeypair;
+    ///
+    /// let mut csprng = OsRng{};
+    /// let tree = MerkleKeypair::generate(&mut csprng, 2);
+    ///
+    /// assert_eq!(tree.len(), 4);
+    ///
+    /// ```
+    pub fn generate<R>(csprng: &mut R, height: u32) -> Self
+    where
+        R: CryptoRng + RngCore,
+    {
+        (0..1usize << height)
+            .map(|_| Keypair::generate(csprng))
+            .collect()
+    }
+
+    /// Build a Merkle tree with the `keypairs` as its leaves, in order.
+    pub fn from_keypairs(keypairs: Vec<Keypair>) -> Self {
+        let mut leaves: Vec<[u8; 32]> = keypairs
+            .iter()
+            .map(|keypair| keypair.public.commitment().to_bytes())
+            .collect();
+        leaves.resize(keypairs.len().next_power_of_two(), [0u8; 32]);
+
+        let mut levels = vec![leaves];
+        while levels[levels.len() - 1].len() > 1 {
+            let level = levels[levels.len() - 1]
+                .chunks_exact(2)
+                .map(|pair| parent(&pair[0], &pair[1]))
+                .collect();
+            levels.push(level);
+        }
+
+        MerkleKeypair { keypairs, levels }
+    }
+
+    /// The number of keypairs in this tree.
+    pub fn len(&self) -> usize {
+        self.keypairs.len()
+    }
+
+    /// Check whether this tree holds no keypairs.
+    pub fn is_empty(&self) -> bool {
+        self.keypairs.is_empty()
+    }
+
+    /// The root of this tree.
+    pub fn root(&self) -> Digest32 {
+        Digest32::from(self.levels[self.levels.len() - 1][0])
+    }
+
+    /// The public key of this tree, its root.
+    pub fn public_key(&self) -> MerklePublicKey {
+        MerklePublicKey(self.root().to_bytes())
+    }
+
+    /// Sign a `message` with the keypair at `index`.
+    ///
+    /// # Returns
+    ///
+    /// A [`MerkleSignature`] carrying the WOTS signature and the
+    /// authentication path of its leaf, or `WotsError::IndexOutOfRange` if
+    /// there is no keypair at `index`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use rand::rngs::OsRng;
+    /// use wots_rs::{Keypair, MerkleKeypair};
+    ///
+    /// let mut csprng = OsRng{};
+    /// let tree: MerkleKeypair = (0..4).map(|_| Keypair::generate(&mut csprng)).collect();
+    ///
+    /// let message = b"hello";
+    /// let signature = tree.sign(2, message).unwrap();
+    ///
+    /// assert!(tree.public_key().verify(message, &signature));
+    ///
+    /// ```
+    pub fn sign(&self, index: usize, message: &[u8]) -> Result<MerkleSignature, WotsError> {
+        let keypair = self.keypairs.get(index).ok_or(WotsError::IndexOutOfRange {
+            index,
+            len: self.keypairs.len(),
+        })?;
+
+        let auth_path = self.levels[..self.levels.len() - 1]
+            .iter()
+            .enumerate()
+            .map(|(height, level)| level[(index >> height) ^ 1])
+            .collect();
+
+        Ok(MerkleSignature {
+            index,
+            signature: keypair.sign_unchecked(message),
+            auth_path,
+        })
+    }
+}
+
+/// Collect keypairs into the leaves of a Merkle tree, in order.
+impl FromIterator<Keypair> for MerkleKeypair {
+    fn from_iter<I: IntoIterator<Item = Keypair>>(iter: I) -> Self {
+        MerkleKeypair::from_keypairs(iter.into_iter().collect())
+    }
+}
+
+/// The public key of a [`MerkleKeypair`], the root of its tree.
+#[derive(Eq, PartialEq)]
+pub struct MerklePublicKey([u8; 32]);
+
+impl MerklePublicKey {
+    /// Verify a Merkle `signature` on a `message`.
+    ///
+    /// The WOTS public key is recovered from the `message` and signature,
+    /// and its commitment is hashed up the authentication path to the root.
+    pub fn verify(&self, message: &[u8], signature: &MerkleSignature) -> bool {
+        verify_with_merkle(
+            message,
+            &signature.signature,
+            signature.index,
+            &signature.auth_path,
+            &Digest32::from(self.0),
+        )
+    }
+
+    /// Convert this public key to its root bytes.
+    pub fn to_bytes(&self) -> [u8; 32] {
+        self.0
+    }
+}
+
+/// Construct a `MerklePublicKey` from a root.
+impl From<[u8; 32]> for MerklePublicKey {
+    fn from(value: [u8; 32]) -> Self {
+        Self(value)
+    }
+}
+
+/// Verify a WOTS `signature` on a `message` against a Merkle `root`.
+///
+/// The public key is recovered from the `message` and `signature`, its
+/// [commitment](crate::PublicKey::commitment) taken as the leaf at
+/// `leaf_index`, and the leaf hashed up the `auth_path` of sibling nodes.
+///
+/// # Returns
+///
+/// Returns `true` if the path ends at `root` and `leaf_index` fits within
+/// a tree of the path's height.
+///
+/// # Example
+///
+/// ```
+/// use wots_rs::{verify_with_merkle, Keypair, MerkleKeypair};
+///
+/// let tree: MerkleKeypair = (0..4u8).map(|i| Keypair::from_seed(&[i; 32])).collect();
+///
+/// let message = b"hello";
+/// let signed = tree.sign(1, message).unwrap();
+///
+/// assert!(verify_with_merkle(message, &signed.signature, 1, &signed.auth_path, &tree.root()));
+///
+/// ```
+pub fn verify_with_merkle(
+    message: &[u8],
+    signature: &Signature,
+    leaf_index: usize,
+    auth_path: &[[u8; 32]],
+    root: &Digest32,
+) -> bool {
+    let public_key = recover(&sha256(message), signature);
+    let leaf = sha256(public_key.as_flattened());
+
+    let node = auth_path
+        .iter()
+        .enumerate()
+        .fold(leaf, |node, (height, sibling)| {
+            if (leaf_index >> height) & 1 == 0 {
+                parent(&node, sibling)
+            } else {
+                parent(sibling, &node)
+            }
+        });
+
+    leaf_index.checked_shr(auth_path.len() as u32).unwrap_or(0) == 0
+        && bool::from(Digest32::from(node).ct_eq(root))
+}
+
+/// A signature by one keypair of a [`MerkleKeypair`].
+pub struct MerkleSignature {
+    /// The index of the signing keypair.
+    pub index: usize,
+    /// The WOTS signature on the message.
+    pub signature: Signature,
+    /// The sibling nodes from the signing leaf up to the root.
+    pub auth_path: Vec<[u8; 32]>,
+}
+
+/// The leaves and interior nodes of a Merkle tree walked by a
+/// [`Traversal`].
+pub(crate) trait TreeHasher {
+    /// The leaf at `index`.
+    fn leaf(&self, index: usize) -> [u8; 32];
+
+    /// The node at `index` within level `height`, the parent of `left`
+    /// and `right`.
+    fn parent(&self, height: u32, index: usize, left: &[u8; 32], right: &[u8; 32]) -> [u8; 32];
+}
+
+/// The leaves of a [`MerkleTraversal`], derived from its master seed.
+struct Derived(Seed);
+
+impl TreeHasher for Derived {
+    /// The commitment of the keypair at `index` derived from the master
+    /// seed.
+    fn leaf(&self, index: usize) -> [u8; 32] {
+        Keypair::derive(&self.0, index as u64)
+            .public
+            .commitment()
+            .to_bytes()
+    }
+
+    fn parent(&self, _: u32, _: usize, left: &[u8; 32], right: &[u8; 32]) -> [u8; 32] {
+        parent(left, right)
+    }
+}
+
+/// A treehash instance of a [`Traversal`], building the node of height
+/// `height` over the leaves `next..end` one leaf at a time.
+#[derive(Clone)]
+struct Treehash {
+    height: u32,
+    next: usize,
+    end: usize,
+    stack: Vec<([u8; 32], u32)>,
+    node: Option<[u8; 32]>,
+}
+
+impl Treehash {
+    /// Start building the node whose leftmost leaf is `leaf`.
+    fn start(&mut self, leaf: usize) {
+        self.next = leaf;
+        self.end = leaf + (1 << self.height);
+        self.stack.clear();
+        self.node = None;
+    }
+
+    /// The lowest height on the stack, or `u32::MAX` if there is no work.
+    fn low(&self) -> u32 {
+        if self.node.is_some() || self.next >= self.end {
+            return u32::MAX;
+        }
+
+        self.stack
+            .iter()
+            .map(|&(_, height)| height)
+            .min()
+            .unwrap_or(self.height)
+    }
+
+    /// Hash the next leaf into the stack, merging equal-height nodes.
+    fn update(&mut self, tree: &impl TreeHasher) {
+        let leaf = self.next;
+        self.stack.push((tree.leaf(leaf), 0));
+        self.next += 1;
+
+        while let [.., (left, l), (right, r)] = self.stack[..] {
+            if l != r {
+                break;
+            }
+            self.stack.truncate(self.stack.len() - 2);
+            self.stack
+                .push((tree.parent(l + 1, leaf >> (l + 1), &left, &right), l + 1));
+        }
+
+        if let [(node, height)] = self.stack[..] {
+            if height == self.height {
+                self.stack.clear();
+                self.node = Some(node);
+            }
+        }
+    }
+}
+
+/// The authentication path of the next leaf of a Merkle tree and the state
+/// to move it along, following Szydlo's "Merkle Tree Traversal in Log
+/// Space and Time".
+///
+/// The traversal keeps one treehash instance per height, each building the
+/// node the authentication path needs next at that height. Every
+/// [`advance`](Self::advance) computes at most `height` leaves, and the
+/// retained state is `O(height^2)` nodes.
+#[derive(Clone)]
+pub(crate) struct Traversal {
+    height: u32,
+    index: usize,
+    root: [u8; 32],
+    auth: Vec<[u8; 32]>,
+    treehash: Vec<Treehash>,
+}
+
+impl Traversal {
+    /// Hash every leaf of the tree of `height` once and set up the
+    /// traversal at leaf `index`.
+    ///
+    /// `index` may be `2^height`, for a tree whose leaves are all used.
+    pub(crate) fn new(tree: &impl TreeHasher, height: u32, index: usize) -> Self {
+        let levels = height as usize;
+        let mut auth = vec![[0u8; 32]; levels];
+        let mut treehash: Vec<Treehash> = (0..height)
+            .map(|height| Treehash {
+                height,
+                next: 0,
+                end: 0,
+                stack: Vec::new(),
+                node: None,
+            })
+            .collect();
+
+        // At leaf `index`, the path holds the sibling of the node above it
+        // at each height, and the treehash instance of that height holds
+        // the node the path needs next. That node is complete here, which
+        // only leaves the instance less work than it would have had.
+        let mut stack: Vec<([u8; 32], u32, usize)> = Vec::new();
+        for leaf in 0..1usize << height {
+            let mut node = (tree.leaf(leaf), 0, leaf);
+            loop {
+                let (value, h, position) = node;
+                if (h as usize) < levels {
+                    let above = index >> h;
+                    if position == above ^ 1 {
+                        auth[h as usize] = value;
+                    }
+                    if position == (above + 1) ^ 1 {
+                        treehash[h as usize].node = Some(value);
+                    }
+                }
+
+                match stack.last() {
+                    Some(&(left, l, _)) if l == h => {
+                        stack.pop();
+                        let position = position >> 1;
+                        node = (tree.parent(h + 1, position, &left, &value), h + 1, position);
+                    }
+                    _ => break,
+                }
+            }
+            stack.push(node);
+        }
+
+        Traversal {
+            height,
+            index,
+            root: stack[0].0,
+            auth,
+            treehash,
+        }
+    }
+
+    /// The index of the leaf the authentication path belongs to.
+    pub(crate) fn index(&self) -> usize {
+        self.index
+    }
+
+    /// The root of the tree.
+    pub(crate) fn root(&self) -> [u8; 32] {
+        self.root
+    }
+
+    /// The sibling nodes from leaf [`index`](Self::index) up to the root.
+    pub(crate) fn auth_path(&self) -> &[[u8; 32]] {
+        &self.auth
+    }
+
+    /// Move the authentication path on to the next leaf and spend the
+    /// update budget of this round on the treehash instances.
+    pub(crate) fn advance(&mut self, tree: &impl TreeHasher) {
+        self.index += 1;
+        let (next, len) = (self.index, 1usize << self.height);
+        if next >= len {
+            return;
+        }
+
+        for h in 0..self.height as usize {
+            if next % (1 << h) != 0 {
+                continue;
+            }
+
+            // The update budget below finishes every instance before its
+            // node is needed; finishing it here only guards that invariant.
+            let treehash = &mut self.treehash[h];
+            while treehash.node.is_none() {
+                treehash.update(tree);
+            }
+            self.auth[h] = treehash.node.take().unwrap();
+
+            let start = (next + (1 << h)) ^ (1 << h);
+            if start < len {
+                treehash.start(start);
+            }
+        }
+
+        for _ in 0..self.height {
+            let Some(treehash) = self
+                .treehash
+                .iter_mut()
+                .filter(|treehash| treehash.low() != u32::MAX)
+                .min_by_key(|treehash| (treehash.low(), treehash.height))
+            else {
+                break;
+            };
+            treehash.update(tree);
+        }
+    }
+}
+
+/// A Merkle tree of keypairs derived from a master seed, signed in order
+/// with logarithmic traversal.
+///
+/// Leaf `i` is the commitment of [`Keypair::derive`] at index `i`, so the
+/// root is the same as that of a [`MerkleKeypair`] of the same derived
+/// keypairs. Rather than holding every keypair and node, the traversal
+/// keeps the current authentication path and one treehash instance per
+/// height, following Szydlo's "Merkle Tree Traversal in Log Space and
+/// Time". Building the tree hashes every leaf once; after that each
+/// signature derives at most `height` leaves in addition to its own, and
+/// the retained state is `O(height^2)` nodes.
+///
+/// Signatures are produced for the leaves in order, each exactly once.
+pub struct MerkleTraversal {
+    leaves: Derived,
+    traversal: Traversal,
+}
+
+impl MerkleTraversal {
+    /// Build the tree of `2^height` keypairs derived from `master_seed`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use wots_rs::MerkleTraversal;
+    ///
+    /// let mut tree = MerkleTraversal::new(&[7u8; 32], 3);
+    ///
+    /// let message = b"hello";
+    /// let signature = tree.sign(message).unwrap();
+    ///
+    /// assert_eq!(signature.index, 0);
+    /// assert!(tree.public_key().verify(message, &signature));
+    ///
+    /// ```
+    pub fn new(master_seed: impl Into<Seed>, height: u32) -> Self {
+        let leaves = Derived(master_seed.into());
+        let traversal = Traversal::new(&leaves, height, 0);
+
+        MerkleTraversal { leaves, traversal }
+    }
+
+    /// The number of keypairs in this tree.
+    pub fn len(&self) -> usize {
+        1 << self.traversal.height
+    }
+
+    /// Check whether this tree holds no keypairs, which is never the case.
+    pub fn is_empty(&self) -> bool {
+        false
+    }
+
+    /// The index of the keypair the next signature will use.
+    pub fn index(&self) -> usize {
+        self.traversal.index()
+    }
+
+    /// The root of this tree.
+    pub fn root(&self) -> Digest32 {
+        Digest32::from(self.traversal.root())
+    }
+
+    /// The public key of this tree, its root.
+    pub fn public_key(&self) -> MerklePublicKey {
+        MerklePublicKey(self.traversal.root())
+    }
+
+    /// Sign a `message` with the next unused keypair.
+    ///
+    /// # Returns
+    ///
+    /// A [`MerkleSignature`] carrying the WOTS signature and the
+    /// authentication path of its leaf, or `WotsError::IndexOutOfRange`
+    /// once every keypair has been used.
+    pub fn sign(&mut self, message: &[u8]) -> Result<MerkleSignature, WotsError> {
+        let index = self.index();
+        if index >= self.len() {
+            return Err(WotsError::IndexOutOfRange {
+                index,
+                len: self.len(),
+            });
+        }
+
+        let signature = MerkleSignature {
+            index,
+            signature: Keypair::derive(&self.leaves.0, index as u64).sign_unchecked(message),
+            auth_path: self.traversal.auth_path().to_vec(),
+        };
+        self.traversal.advance(&self.leaves);
+
+        Ok(signature)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn collect_and_sign_at_index() {
+        let message = b"hello";
+
+        let tree: MerkleKeypair = (0..4u8).map(|i| Keypair::from_seed([i; 32])).collect();
+        assert_eq!(tree.len(), 4);
+
+        let public_key = tree.public_key();
+        for index in 0..4 {
+            let signature = tree.sign(index, message).unwrap();
+            assert_eq!(signature.auth_path.len(), 2);
+            assert!(public_key.verify(message, &signature));
+            assert!(!public_key.verify(b"world", &signature));
+        }
+
+        let mut signature = tree.sign(1, message).unwrap();
+        signature.index = 0;
+        assert!(!public_key.verify(message, &signature));
+
+        assert_eq!(
+            tree.sign(4, message).err(),
+            Some(WotsError::IndexOutOfRange { index: 4, len: 4 })
+        );
+    }
+
+    #[test]
+    fn leaves_are_padded_to_a_power_of_two() {
+        let message = b"hello";
+
+        let tree: MerkleKeypair = (0..3u8).map(|i| Keypair::from_seed([i; 32])).collect();
+
+        let signature = tree.sign(2, message).unwrap();
+        assert_eq!(signature.auth_path, [[0u8; 32], tree.levels[1][0]]);
+        assert!(tree.public_key().verify(message, &signature));
+    }
+
+    #[test]
+    fn verify_with_merkle_checks_path() {
+        let message = b"hello";
+
+        let tree: MerkleKeypair = (0..4u8).map(|i| Keypair::from_seed([i; 32])).collect();
+        let root = tree.root();
+        let signed = tree.sign(3, message).unwrap();
+
+        assert!(verify_with_merkle(
+            message,
+            &signed.signature,
+            3,
+            &signed.auth_path,
+            &root
+        ));
+        assert!(!verify_with_merkle(
+            message,
+            &signed.signature,
+            2,
+            &signed.auth_path,
+            &root
+        ));
+        assert!(!verify_with_merkle(
+            message,
+            &signed.signature,
+            7,
+            &signed.auth_path,
+            &root
+        ));
+    }
+
+    #[test]
+    fn traversal_matches_full_tree() {
+        let master_seed = [7u8; 32];
+        let message = b"hello";
+
+        for height in 0..4u32 {
+            let tree: MerkleKeypair = (0..1u64 << height)
+                .map(|i| Keypair::derive(master_seed, i))
+                .collect();
+            let mut traversal = MerkleTraversal::new(master_seed, height);
+            assert!(traversal.root() == tree.root());
+
+            for index in 0..tree.len() {
+                let signature = traversal.sign(message).unwrap();
+                let expected = tree.sign(index, message).unwrap();
+                assert_eq!(signature.index, index);
+                assert_eq!(signature.auth_path, expected.auth_path);
+                assert!(signature.signature == expected.signature);
+            }
+            assert_eq!(
+                traversal.sign(message).err(),
+                Some(WotsError::IndexOutOfRange {
+                    index: tree.len(),
+                    len: tree.len()
+                })
+            );
+        }
+    }
+
+    #[test]
+    fn traversal_resumes_at_any_leaf() {
+        let leaves = Derived(Seed::from([7u8; 32]));
+        let tree: MerkleKeypair = (0..8).map(|i| Keypair::derive(&leaves.0, i)).collect();
+
+        for start in 0..=8 {
+            let mut traversal = Traversal::new(&leaves, 3, start);
+            assert!(Digest32::from(traversal.root()) == tree.root());
+
+            for index in start..8 {
+                assert_eq!(traversal.index(), index);
+                assert_eq!(
+                    traversal.auth_path(),
+                    tree.sign(index, b"hello").unwrap().auth_path
+                );
+                traversal.advance(&leaves);
+            }
+        }
+    }
+
+    #[test]
+    fn generate_builds_full_tree() {
+        let message = b"hello";
+
+        let tree = MerkleKeypair::generate(&mut rand::rngs::OsRng, 3);
+        assert_eq!(tree.len(), 8);
+
+        let signature = tree.sign(5, message).unwrap();
+        assert_eq!(signature.auth_path.len(), 3);
+        assert!(tree.public_key().verify(message, &signature));
+    }
+}
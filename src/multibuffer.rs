@@ -0,0 +1,184 @@
+//! Multi-buffer SHA-256 for advancing many chains in lockstep.
+//!
+//! Every chain step hashes exactly 32 bytes, which pads to a single
+//! 64-byte block with a fixed second half. [`iterate_lanes`] keeps
+//! [`LANES`] chains in flight and runs their compressions together, with
+//! the working variables of all lanes stored side by side so that each
+//! round is a handful of operations on `[u32; LANES]` arrays, which the
+//! compiler turns into SIMD instructions where the target has them. A lane
+//! whose chain is finished is refilled with the next chain, so chains of
+//! different lengths share the lanes.
+//!
+//! With the `zeroize` feature the message schedule, the working variables
+//! and the lanes are wiped once they are no longer needed, as they hold
+//! intermediate chain values.
+#[cfg(feature = "zeroize")]
+use zeroize::Zeroize;
+
+/// The number of chains hashed in lockstep.
+pub(crate) const LANES: usize = 8;
+
+const K: [u32; 64] = [
+    0x428a2f98, 0x71374491, 0xb5c0fbcf, 0xe9b5dba5, 0x3956c25b, 0x59f111f1, 0x923f82a4, 0xab1c5ed5,
+    0xd807aa98, 0x12835b01, 0x243185be, 0x550c7dc3, 0x72be5d74, 0x80deb1fe, 0x9bdc06a7, 0xc19bf174,
+    0xe49b69c1, 0xefbe4786, 0x0fc19dc6, 0x240ca1cc, 0x2de92c6f, 0x4a7484aa, 0x5cb0a9dc, 0x76f988da,
+    0x983e5152, 0xa831c66d, 0xb00327c8, 0xbf597fc7, 0xc6e00bf3, 0xd5a79147, 0x06ca6351, 0x14292967,
+    0x27b70a85, 0x2e1b2138, 0x4d2c6dfc, 0x53380d13, 0x650a7354, 0x766a0abb, 0x81c2c92e, 0x92722c85,
+    0xa2bfe8a1, 0xa81a664b, 0xc24b8b70, 0xc76c51a3, 0xd192e819, 0xd6990624, 0xf40e3585, 0x106aa070,
+    0x19a4c116, 0x1e376c08, 0x2748774c, 0x34b0bcb5, 0x391c0cb3, 0x4ed8aa4a, 0x5b9cca4f, 0x682e6ff3,
+    0x748f82ee, 0x78a5636f, 0x84c87814, 0x8cc70208, 0x90befffa, 0xa4506ceb, 0xbef9a3f7, 0xc67178f2,
+];
+
+const H: [u32; 8] = [
+    0x6a09e667, 0xbb67ae85, 0x3c6ef372, 0xa54ff53a, 0x510e527f, 0x9b05688c, 0x1f83d9ab, 0x5be0cd19,
+];
+
+/// The words of one lane-interleaved value: `words[j][lane]` is word `j`
+/// of the 32-byte value in `lane`.
+type Words = [[u32; LANES]; 8];
+
+/// Hash the 32-byte value in every lane of `words` with SHA-256, in place.
+///
+/// The loops index every lane explicitly so they vectorize.
+#[allow(clippy::needless_range_loop)]
+fn compress(words: &mut Words) {
+    let mut w = [[0u32; LANES]; 64];
+    w[..8].copy_from_slice(words);
+    w[8] = [0x8000_0000; LANES];
+    w[15] = [256; LANES];
+    for t in 16..64 {
+        for lane in 0..LANES {
+            let s0 = w[t - 15][lane].rotate_right(7)
+                ^ w[t - 15][lane].rotate_right(18)
+                ^ (w[t - 15][lane] >> 3);
+            let s1 = w[t - 2][lane].rotate_right(17)
+                ^ w[t - 2][lane].rotate_right(19)
+                ^ (w[t - 2][lane] >> 10);
+            w[t][lane] = w[t - 16][lane]
+                .wrapping_add(s0)
+                .wrapping_add(w[t - 7][lane])
+                .wrapping_add(s1);
+        }
+    }
+
+    let mut v = H.map(|h| [h; LANES]);
+    let mut next = v;
+    for t in 0..64 {
+        let [a, b, c, d, e, f, g, h] = &v;
+        for lane in 0..LANES {
+            let s1 = e[lane].rotate_right(6) ^ e[lane].rotate_right(11) ^ e[lane].rotate_right(25);
+            let ch = (e[lane] & f[lane]) ^ (!e[lane] & g[lane]);
+            let t1 = h[lane]
+                .wrapping_add(s1)
+                .wrapping_add(ch)
+                .wrapping_add(K[t])
+                .wrapping_add(w[t][lane]);
+            let s0 = a[lane].rotate_right(2) ^ a[lane].rotate_right(13) ^ a[lane].rotate_right(22);
+            let maj = (a[lane] & b[lane]) ^ (a[lane] & c[lane]) ^ (b[lane] & c[lane]);
+            let t2 = s0.wrapping_add(maj);
+
+            next[0][lane] = t1.wrapping_add(t2);
+            next[1][lane] = a[lane];
+            next[2][lane] = b[lane];
+            next[3][lane] = c[lane];
+            next[4][lane] = d[lane].wrapping_add(t1);
+            next[5][lane] = e[lane];
+            next[6][lane] = f[lane];
+            next[7][lane] = g[lane];
+        }
+        v = next;
+    }
+
+    for (word, (value, h)) in words.iter_mut().zip(v.iter().zip(H)) {
+        for lane in 0..LANES {
+            word[lane] = value[lane].wrapping_add(h);
+        }
+    }
+
+    #[cfg(feature = "zeroize")]
+    {
+        w.zeroize();
+        v.zeroize();
+        next.zeroize();
+    }
+}
+
+/// Load the next chain from `*next` on that needs any steps into `lane`.
+///
+/// Returns the index of the chain and its number of steps, or `None` if
+/// every chain has been loaded.
+fn load(
+    chains: &[[u8; 32]],
+    steps: &impl Fn(usize) -> usize,
+    words: &mut Words,
+    lane: usize,
+    next: &mut usize,
+) -> Option<(usize, usize)> {
+    while *next < chains.len() && steps(*next) == 0 {
+        *next += 1;
+    }
+
+    let chain = *next;
+    let bytes = chains.get(chain)?;
+    for (word, bytes) in words.iter_mut().zip(bytes.chunks_exact(4)) {
+        word[lane] = u32::from_be_bytes(bytes.try_into().unwrap());
+    }
+    *next += 1;
+
+    Some((chain, steps(chain)))
+}
+
+/// Advance each of `chains` by `steps(i)` hash steps, in place.
+///
+/// The result is identical to hashing chain `i` with SHA-256 `steps(i)`
+/// times.
+pub(crate) fn iterate_lanes(chains: &mut [[u8; 32]], steps: impl Fn(usize) -> usize) {
+    let mut words: Words = [[0; LANES]; 8];
+    let mut jobs: [Option<(usize, usize)>; LANES] = [None; LANES];
+    let mut next = 0;
+
+    for (lane, job) in jobs.iter_mut().enumerate() {
+        *job = load(chains, &steps, &mut words, lane, &mut next);
+    }
+
+    while jobs.iter().any(Option::is_some) {
+        compress(&mut words);
+
+        for (lane, job) in jobs.iter_mut().enumerate() {
+            let Some((chain, left)) = job else { continue };
+
+            *left -= 1;
+            if *left == 0 {
+                for (word, bytes) in words.iter().zip(chains[*chain].chunks_exact_mut(4)) {
+                    bytes.copy_from_slice(&word[lane].to_be_bytes());
+                }
+                *job = load(chains, &steps, &mut words, lane, &mut next);
+            }
+        }
+    }
+
+    #[cfg(feature = "zeroize")]
+    words.zeroize();
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::hash::sha256;
+
+    #[test]
+    fn matches_repeated_hashing() {
+        let mut chains: [[u8; 32]; 19] = core::array::from_fn(|i| [i as u8; 32]);
+        let steps = |i: usize| (i * 7) % 12;
+
+        let expected = chains.map(|chain| chain);
+        iterate_lanes(&mut chains, steps);
+
+        for (i, (chain, mut x)) in chains.iter().zip(expected).enumerate() {
+            for _ in 0..steps(i) {
+                x = sha256(&x);
+            }
+            assert_eq!(*chain, x, "chain {}", i);
+        }
+    }
+}
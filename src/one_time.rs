@@ -0,0 +1,76 @@
+//! Keypairs that can sign only once.
+use crate::{keypair::Keypair, public::PublicKey, signature::Signature};
+
+/// An WOTS keypair whose signing consumes it.
+///
+/// Signing two different messages with one WOTS key lets anyone forge
+/// signatures on further messages. [`sign`](Self::sign) takes `self` by
+/// value, so signing a second time is a compile error:
+///
+/// ```compile_fail
+/// use wots_rs::{Keypair, OneTimeKeypair};
+///
+/// let keypair = OneTimeKeypair::new(Keypair::from_seed(&[7u8; 32]));
+///
+/// let hello = keypair.sign(b"hello");
+/// let world = keypair.sign(b"world");
+/// ```
+///
+/// The guard only covers this value: the wrapped key can still be signed
+/// with again if it was copied or persisted elsewhere, so state that
+/// outlives the process must track used keys separately.
+pub struct OneTimeKeypair(Keypair);
+
+impl OneTimeKeypair {
+    /// Wrap a `keypair` that has not signed anything yet.
+    pub fn new(keypair: Keypair) -> Self {
+        OneTimeKeypair(keypair)
+    }
+
+    /// The public key which verifies the signature of this keypair.
+    pub fn public_key(&self) -> &PublicKey {
+        &self.0.public
+    }
+
+    /// Sign a `message`, consuming this keypair.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use wots_rs::{Keypair, OneTimeKeypair};
+    ///
+    /// let keypair = OneTimeKeypair::new(Keypair::from_seed(&[7u8; 32]));
+    /// let public_key = *keypair.public_key();
+    ///
+    /// let signature = keypair.sign(b"hello");
+    ///
+    /// assert!(public_key.verify(b"hello", signature));
+    ///
+    /// ```
+    pub fn sign(self, message: &[u8]) -> Signature {
+        self.0.sign_once(message).0
+    }
+}
+
+impl From<Keypair> for OneTimeKeypair {
+    fn from(value: Keypair) -> Self {
+        OneTimeKeypair::new(value)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn signs_once() {
+        let message = b"hello";
+
+        let keypair = OneTimeKeypair::from(Keypair::from_seed([42u8; 32]));
+        let public_key = *keypair.public_key();
+        let signature = keypair.sign(message);
+
+        assert!(public_key.verify(message, signature));
+        assert!(!public_key.verify(b"world", signature));
+    }
+}
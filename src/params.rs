@@ -0,0 +1,998 @@
+//! WOTS with explicit scheme parameters.
+//!
+//! The types in this module mirror the crate's default [`Keypair`](crate::Keypair),
+//! [`PublicKey`](crate::PublicKey), [`SecretKey`](crate::SecretKey) and
+//! [`Signature`](crate::Signature), but carry the [`Params`] they were created
+//! with so that generation, signing and verification always agree.
+//!
+//! They are also generic over the [`Digest`] used for both the message hash
+//! and the chain function, defaulting to SHA-256. The number of chains and
+//! the length of each chain value follow from the digest's output size, so
+//! `Keypair<sha2::Sha512>` has 64-byte chains for a higher security margin:
+//!
+//! ```
+//! use rand::rngs::OsRng;
+//! use sha2::Sha512;
+//! use wots_rs::params::{Keypair, Params};
+//!
+//! let keypair: Keypair<Sha512> = Keypair::generate(&mut OsRng, Params::new());
+//! let signature = keypair.sign(b"hello");
+//!
+//! assert_eq!(signature.chains()[0].len(), 64);
+//! assert!(keypair.verify(b"hello", &signature));
+//! ```
+//!
+//! Keys and signatures over different digests are different types:
+//!
+//! ```compile_fail
+//! use rand::rngs::OsRng;
+//! use sha2::{Sha256, Sha512_256};
+//! use wots_rs::params::{Keypair, Params};
+//!
+//! let mut csprng = OsRng{};
+//! let sha256: Keypair<Sha256> = Keypair::generate(&mut csprng, Params::new());
+//! let sha512_256: Keypair<Sha512_256> = Keypair::generate(&mut csprng, Params::new());
+//!
+//! sha256.verify(b"hello", &sha512_256.sign(b"hello"));
+//! ```
+use crate::{constants::CHAIN_LENGTH, error::WotsError};
+use alloc::{format, string::String, vec::Vec};
+use digest::{Digest, Output};
+use rand::{CryptoRng, RngCore};
+#[cfg(feature = "serde")]
+use serde::{de, Deserialize, Deserializer, Serialize, Serializer};
+use sha2::Sha256;
+use subtle::{Choice, ConstantTimeEq};
+
+/// WOTS scheme parameters.
+///
+/// The default parameters produce keys and signatures identical to the
+/// crate's default types.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serde", serde(try_from = "ParamsRepr"))]
+pub struct Params {
+    w: usize,
+    bind_index: bool,
+}
+
+impl Params {
+    /// The default WOTS parameters.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Set the Winternitz parameter `w`, one of 4, 16 or 256.
+    ///
+    /// Each chain signs one base-`w` digit of the message digest, so a
+    /// smaller `w` means more, but shorter, chains: faster signing and
+    /// verification at the cost of larger signatures.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use wots_rs::Params;
+    ///
+    /// let params = Params::new().with_w(4).unwrap();
+    ///
+    /// assert_eq!(params.message_chains(), 128);
+    ///
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// Returns `WotsError::UnsupportedW` for any other `w`.
+    pub fn with_w(mut self, w: usize) -> Result<Self, WotsError> {
+        if !matches!(w, 4 | 16 | 256) {
+            return Err(WotsError::UnsupportedW { w });
+        }
+
+        self.w = w;
+        Ok(self)
+    }
+
+    /// Bind each chain step to the index of its chain.
+    ///
+    /// With `bind_index` set, every step of chain `i` computes `H(i || x)`,
+    /// with `i` as a big-endian `u16`, instead of `H(x)`, so chains can't be
+    /// moved between positions of a key or signature.
+    pub fn with_bind_index(mut self, bind_index: bool) -> Self {
+        self.bind_index = bind_index;
+        self
+    }
+
+    /// Whether chain steps are bound to their chain index.
+    pub fn bind_index(&self) -> bool {
+        self.bind_index
+    }
+
+    /// The Winternitz parameter: the number of values a single chain signs.
+    pub fn w(&self) -> usize {
+        self.w
+    }
+
+    /// The number of chains signing a 256-bit message digest, such as
+    /// SHA-256, one per base-`w` digit.
+    pub fn message_chains(&self) -> usize {
+        self.message_chains_for(32)
+    }
+
+    /// The number of chains signing the checksum of the digits of a 256-bit
+    /// message digest.
+    ///
+    /// These are the base-`w` digits needed to represent the largest
+    /// checksum, `message_chains * (w - 1)`.
+    pub fn checksum_chains(&self) -> usize {
+        self.checksum_chains_for(self.message_chains())
+    }
+
+    /// The total number of hash chains in a key or signature.
+    pub fn chains(&self) -> usize {
+        self.message_chains() + self.checksum_chains()
+    }
+
+    /// The number of message bits signed by a signature.
+    pub fn bits_signed(&self) -> usize {
+        self.message_chains() * self.w().trailing_zeros() as usize
+    }
+
+    /// The size of a signature, in bytes.
+    pub fn signature_size(&self) -> usize {
+        self.chains() * 32
+    }
+
+    /// Estimate the bits of security left once one signature is published.
+    ///
+    /// A published signature reveals an intermediate value of every chain,
+    /// so a forger may attack any of the `w` positions along any of the
+    /// chains. Following the security reduction of WOTS+, the estimate is
+    /// `n - log2(w^2 * chains + w)` for the 256-bit hash output `n`, about
+    /// 235 bits for the default parameters. Signing a second message with
+    /// the same key voids the estimate entirely.
+    ///
+    /// Requires the `std` feature for floating-point logarithms.
+    #[cfg(feature = "std")]
+    pub fn security_after_one_signature(&self) -> f64 {
+        let w = self.w() as f64;
+
+        256.0 - (w * w * self.chains() as f64 + w).log2()
+    }
+
+    /// Summarize these parameters for comparing parameter sets.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use wots_rs::Params;
+    ///
+    /// println!("{}", Params::new().describe());
+    ///
+    /// ```
+    pub fn describe(&self) -> String {
+        format!(
+            "WOTS w={}: {} chains for the message and {} checksum chains ({} chains total), \
+             {} bits signed, each signature chain revealed between 1 and {} hash steps past its \
+             secret, {}-byte signatures, index binding {}",
+            self.w(),
+            self.message_chains(),
+            self.checksum_chains(),
+            self.chains(),
+            self.bits_signed(),
+            self.w(),
+            self.signature_size(),
+            if self.bind_index { "on" } else { "off" },
+        )
+    }
+
+    /// The number of chains signing an `n`-byte message digest.
+    fn message_chains_for(&self, n: usize) -> usize {
+        8 * n / self.log_w()
+    }
+
+    /// The number of chains signing the checksum of `message_chains` digits.
+    fn checksum_chains_for(&self, message_chains: usize) -> usize {
+        let max = message_chains * (self.w - 1);
+        let mut chains = 1;
+
+        while max >> (chains * self.log_w()) != 0 {
+            chains += 1;
+        }
+
+        chains
+    }
+
+    /// The total number of chains for an `n`-byte digest.
+    fn chains_for(&self, n: usize) -> usize {
+        let message_chains = self.message_chains_for(n);
+
+        message_chains + self.checksum_chains_for(message_chains)
+    }
+
+    /// The number of bits in a base-`w` digit.
+    fn log_w(&self) -> usize {
+        self.w.trailing_zeros() as usize
+    }
+
+    /// The number of hash steps from a secret chain value to its public value.
+    fn chain_length(&self) -> usize {
+        self.w.min(CHAIN_LENGTH)
+    }
+
+    /// The number of hash steps that sign `digit`.
+    fn steps(&self, digit: u8) -> usize {
+        digit as usize % self.chain_length()
+    }
+
+    /// Split the digest of a `message` into base-`w` digits, most
+    /// significant first, followed by the big-endian base-`w` digits of the
+    /// checksum `sum(w - 1 - d_i)`.
+    fn digits<D: Digest>(&self, message: &[u8]) -> Vec<u8> {
+        let log_w = self.log_w();
+        let mask = (self.w - 1) as u8;
+
+        let digits = D::digest(message)
+            .iter()
+            .flat_map(|&byte| {
+                (0..8 / log_w)
+                    .rev()
+                    .map(move |i| (byte >> (i * log_w)) & mask)
+            })
+            .collect();
+
+        self.with_checksum(digits)
+    }
+
+    /// Extend the message `digits` with the big-endian base-`w` digits of
+    /// their checksum.
+    fn with_checksum(&self, mut digits: Vec<u8>) -> Vec<u8> {
+        let log_w = self.log_w();
+        let mask = (self.w - 1) as u8;
+
+        let checksum: usize = digits.iter().map(|&d| self.w - 1 - d as usize).sum();
+        digits.extend(
+            (0..self.checksum_chains_for(digits.len()))
+                .rev()
+                .map(|i| ((checksum >> (i * log_w)) as u8) & mask),
+        );
+
+        digits
+    }
+
+    /// Apply `steps` hash steps to the value `x` of chain `index`.
+    fn chain<D: Digest>(&self, index: usize, mut x: Output<D>, steps: usize) -> Output<D> {
+        for _ in 0..steps {
+            let mut hasher = D::new();
+            if self.bind_index {
+                hasher.update((index as u16).to_be_bytes());
+            }
+            hasher.update(&x);
+            x = hasher.finalize();
+        }
+
+        x
+    }
+
+    /// Check that `digits` holds one digit below `w` per message chain of
+    /// the digest `D`, and extend them with their checksum digits.
+    fn check_digits<D: Digest>(&self, digits: &[u8]) -> Result<Vec<u8>, WotsError> {
+        let message_chains = self.message_chains_for(<D as Digest>::output_size());
+
+        if digits.len() != message_chains {
+            return Err(WotsError::LengthMismatch {
+                expected: message_chains,
+                actual: digits.len(),
+            });
+        }
+
+        if let Some(index) = digits.iter().position(|&digit| digit as usize >= self.w) {
+            return Err(WotsError::InvalidDigit {
+                index,
+                digit: digits[index],
+            });
+        }
+
+        Ok(self.with_checksum(digits.to_vec()))
+    }
+
+    /// Check that `chains` holds exactly one value of the output size of
+    /// the digest `D` per chain.
+    fn check_chains<D, C>(&self, chains: &[C]) -> Result<Vec<Output<D>>, WotsError>
+    where
+        D: Digest,
+        C: AsRef<[u8]>,
+    {
+        let n = <D as Digest>::output_size();
+
+        if chains.len() != self.chains_for(n) {
+            return Err(WotsError::LengthMismatch {
+                expected: self.chains_for(n),
+                actual: chains.len(),
+            });
+        }
+
+        chains
+            .iter()
+            .map(|chain| {
+                let chain = chain.as_ref();
+                if chain.len() != n {
+                    return Err(WotsError::LengthMismatch {
+                        expected: n,
+                        actual: chain.len(),
+                    });
+                }
+
+                Ok(Output::<D>::clone_from_slice(chain))
+            })
+            .collect()
+    }
+}
+
+/// The default parameters, `w = 256` without index binding.
+impl Default for Params {
+    fn default() -> Self {
+        Params {
+            w: 256,
+            bind_index: false,
+        }
+    }
+}
+
+/// The serialized form of [`Params`], validated on deserialization.
+#[cfg(feature = "serde")]
+#[derive(Deserialize)]
+struct ParamsRepr {
+    w: usize,
+    bind_index: bool,
+}
+
+#[cfg(feature = "serde")]
+impl TryFrom<ParamsRepr> for Params {
+    type Error = WotsError;
+
+    fn try_from(value: ParamsRepr) -> Result<Self, Self::Error> {
+        Params::new()
+            .with_w(value.w)
+            .map(|params| params.with_bind_index(value.bind_index))
+    }
+}
+
+/// The serialized form of keys and signatures, validated on deserialization.
+#[cfg(feature = "serde")]
+#[derive(Deserialize)]
+struct ChainsRepr {
+    params: Params,
+    chains: Vec<Vec<u8>>,
+}
+
+/// The serialized form of keys and signatures, borrowed for serialization.
+#[cfg(feature = "serde")]
+#[derive(Serialize)]
+struct ChainsReprRef<'a> {
+    params: Params,
+    chains: Vec<&'a [u8]>,
+}
+
+#[cfg(feature = "serde")]
+impl<'a> ChainsReprRef<'a> {
+    fn new<D: Digest>(params: Params, chains: &'a [Output<D>]) -> Self {
+        ChainsReprRef {
+            params,
+            chains: chains.iter().map(|chain| chain.as_slice()).collect(),
+        }
+    }
+}
+
+/// An WOTS keypair with explicit parameters.
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serde", serde(bound = ""))]
+pub struct Keypair<D: Digest = Sha256> {
+    /// The secret half of this keypair.
+    pub secret: SecretKey<D>,
+    /// The public half of this keypair.
+    pub public: PublicKey<D>,
+}
+
+impl<D: Digest> Keypair<D> {
+    /// Generate an WOTS keypair with the given `params`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use rand::rngs::OsRng;
+    /// use wots_rs::params::{Keypair, Params};
+    ///
+    /// let mut csprng = OsRng{};
+    /// let params = Params::new().with_bind_index(true);
+    /// let keypair: Keypair = Keypair::generate(&mut csprng, params);
+    ///
+    /// let message = b"hello";
+    /// let signature = keypair.sign(message);
+    ///
+    /// assert!(keypair.verify(message, &signature));
+    ///
+    /// ```
+    pub fn generate<R>(csprng: &mut R, params: Params) -> Self
+    where
+        R: CryptoRng + RngCore,
+    {
+        let sk = SecretKey::generate(csprng, params);
+        let pk = PublicKey::from(&sk);
+
+        Keypair {
+            secret: sk,
+            public: pk,
+        }
+    }
+
+    /// Sign a `message` with this `Keypair`.
+    pub fn sign(&self, message: &[u8]) -> Signature<D> {
+        self.secret.sign(message)
+    }
+
+    /// Verify a `signature` on a `message` with this `Keypair`.
+    pub fn verify(&self, message: &[u8], signature: &Signature<D>) -> bool {
+        self.public.verify(message, signature)
+    }
+}
+
+/// An WOTS secret key with explicit parameters.
+pub struct SecretKey<D: Digest = Sha256> {
+    params: Params,
+    chains: Vec<Output<D>>,
+}
+
+impl<D: Digest> SecretKey<D> {
+    /// Generate a `SecretKey` with the given `params` from a `csprng`.
+    pub fn generate<R>(csprng: &mut R, params: Params) -> Self
+    where
+        R: CryptoRng + RngCore,
+    {
+        let chains = (0..params.chains_for(<D as Digest>::output_size()))
+            .map(|_| {
+                let mut chain = Output::<D>::default();
+                csprng.fill_bytes(&mut chain);
+                chain
+            })
+            .collect();
+
+        SecretKey { params, chains }
+    }
+
+    /// Construct a `SecretKey` from its `params` and chain values.
+    ///
+    /// Returns `WotsError::LengthMismatch` if `chains` does not contain
+    /// exactly one value per chain, each as long as the digest output.
+    pub fn from_chains<C: AsRef<[u8]>>(params: Params, chains: &[C]) -> Result<Self, WotsError> {
+        Ok(SecretKey {
+            params,
+            chains: params.check_chains::<D, C>(chains)?,
+        })
+    }
+
+    /// Sign a `message` with this `SecretKey`.
+    pub fn sign(&self, message: &[u8]) -> Signature<D> {
+        self.sign_all_digits(&self.params.digits::<D>(message))
+    }
+
+    /// Sign a message already reduced to base-`w` `digits`, one per message
+    /// chain, bypassing the message hash.
+    ///
+    /// # Errors
+    ///
+    /// Returns `WotsError::LengthMismatch` if there isn't exactly one digit
+    /// per message chain, or `WotsError::InvalidDigit` if a digit is not
+    /// below `w`.
+    pub fn sign_digit_slice(&self, digits: &[u8]) -> Result<Signature<D>, WotsError> {
+        let digits = self.params.check_digits::<D>(digits)?;
+
+        Ok(self.sign_all_digits(&digits))
+    }
+
+    /// Sign `digits`, including their checksum digits.
+    fn sign_all_digits(&self, digits: &[u8]) -> Signature<D> {
+        let params = self.params;
+        let chains = digits
+            .iter()
+            .zip(self.chains.iter())
+            .enumerate()
+            .map(|(i, (&digit, x))| {
+                params.chain::<D>(i, x.clone(), params.chain_length() - params.steps(digit))
+            })
+            .collect();
+
+        Signature { params, chains }
+    }
+
+    /// The parameters of this secret key.
+    pub fn params(&self) -> Params {
+        self.params
+    }
+
+    /// The chain values of this secret key.
+    pub fn chains(&self) -> &[Output<D>] {
+        &self.chains
+    }
+}
+
+/// An WOTS public key with explicit parameters.
+pub struct PublicKey<D: Digest = Sha256> {
+    params: Params,
+    chains: Vec<Output<D>>,
+}
+
+impl<D: Digest> PublicKey<D> {
+    /// Construct a `PublicKey` from its `params` and chain values.
+    ///
+    /// Returns `WotsError::LengthMismatch` if `chains` does not contain
+    /// exactly one value per chain, each as long as the digest output.
+    pub fn from_chains<C: AsRef<[u8]>>(params: Params, chains: &[C]) -> Result<Self, WotsError> {
+        Ok(PublicKey {
+            params,
+            chains: params.check_chains::<D, C>(chains)?,
+        })
+    }
+
+    /// Verify a `signature` on a `message` using this public key's parameters.
+    ///
+    /// A signature created with different parameters never verifies.
+    pub fn verify(&self, message: &[u8], signature: &Signature<D>) -> bool {
+        self.verify_all_digits(&self.params.digits::<D>(message), signature)
+    }
+
+    /// Verify a `signature` on a message already reduced to base-`w`
+    /// `digits`, one per message chain, bypassing the message hash.
+    ///
+    /// The checksum chains are verified as usual.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use rand::rngs::OsRng;
+    /// use wots_rs::params::{Keypair, Params};
+    ///
+    /// let mut csprng = OsRng{};
+    /// let params = Params::new().with_w(16).unwrap();
+    /// let keypair: Keypair = Keypair::generate(&mut csprng, params);
+    ///
+    /// let digits = [3u8; 64];
+    /// let signature = keypair.secret.sign_digit_slice(&digits).unwrap();
+    ///
+    /// assert_eq!(keypair.public.verify_digit_slice(&digits, &signature), Ok(true));
+    ///
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// Returns `WotsError::LengthMismatch` if there isn't exactly one digit
+    /// per message chain, or `WotsError::InvalidDigit` if a digit is not
+    /// below `w`.
+    pub fn verify_digit_slice(
+        &self,
+        digits: &[u8],
+        signature: &Signature<D>,
+    ) -> Result<bool, WotsError> {
+        let digits = self.params.check_digits::<D>(digits)?;
+
+        Ok(self.verify_all_digits(&digits, signature))
+    }
+
+    /// Verify a `signature` on `digits`, including their checksum digits.
+    fn verify_all_digits(&self, digits: &[u8], signature: &Signature<D>) -> bool {
+        let params = self.params;
+
+        signature.params == params
+            && digits
+                .iter()
+                .zip(signature.chains.iter().zip(self.chains.iter()))
+                .enumerate()
+                .fold(Choice::from(1), |valid, (i, (&digit, (s, key)))| {
+                    valid
+                        & params
+                            .chain::<D>(i, s.clone(), params.steps(digit))
+                            .ct_eq(key)
+                })
+                .into()
+    }
+
+    /// The parameters of this public key.
+    pub fn params(&self) -> Params {
+        self.params
+    }
+
+    /// The chain values of this public key.
+    pub fn chains(&self) -> &[Output<D>] {
+        &self.chains
+    }
+}
+
+/// Construct a `PublicKey` from a `SecretKey`.
+impl<D: Digest> From<&SecretKey<D>> for PublicKey<D> {
+    fn from(value: &SecretKey<D>) -> Self {
+        let params = value.params;
+        let chains = value
+            .chains
+            .iter()
+            .enumerate()
+            .map(|(i, x)| params.chain::<D>(i, x.clone(), params.chain_length()))
+            .collect();
+
+        PublicKey { params, chains }
+    }
+}
+
+/// An WOTS signature created with explicit parameters.
+pub struct Signature<D: Digest = Sha256> {
+    params: Params,
+    chains: Vec<Output<D>>,
+}
+
+impl<D: Digest> Signature<D> {
+    /// Construct a `Signature` from its `params` and chain values.
+    ///
+    /// Returns `WotsError::LengthMismatch` if `chains` does not contain
+    /// exactly one value per chain, each as long as the digest output.
+    pub fn from_chains<C: AsRef<[u8]>>(params: Params, chains: &[C]) -> Result<Self, WotsError> {
+        Ok(Signature {
+            params,
+            chains: params.check_chains::<D, C>(chains)?,
+        })
+    }
+
+    /// The parameters this signature was created with.
+    pub fn params(&self) -> Params {
+        self.params
+    }
+
+    /// The chain values of this signature.
+    pub fn chains(&self) -> &[Output<D>] {
+        &self.chains
+    }
+}
+
+macro_rules! impl_chains_traits {
+    ($name:ident) => {
+        /// Compare the chains in constant time. The parameters and the
+        /// number of chains are public and compared directly.
+        impl<D: Digest> ConstantTimeEq for $name<D> {
+            fn ct_eq(&self, other: &Self) -> Choice {
+                if self.params != other.params || self.chains.len() != other.chains.len() {
+                    return Choice::from(0);
+                }
+
+                self.chains
+                    .iter()
+                    .zip(other.chains.iter())
+                    .fold(Choice::from(1), |equal, (a, b)| {
+                        equal & a.as_slice().ct_eq(b.as_slice())
+                    })
+            }
+        }
+
+        impl<D: Digest> PartialEq for $name<D> {
+            fn eq(&self, other: &Self) -> bool {
+                self.ct_eq(other).into()
+            }
+        }
+
+        impl<D: Digest> Eq for $name<D> {}
+
+        #[cfg(feature = "serde")]
+        impl<D: Digest> Serialize for $name<D> {
+            fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+                ChainsReprRef::new::<D>(self.params, &self.chains).serialize(serializer)
+            }
+        }
+
+        #[cfg(feature = "serde")]
+        impl<'de, D: Digest> Deserialize<'de> for $name<D> {
+            fn deserialize<De: Deserializer<'de>>(deserializer: De) -> Result<Self, De::Error> {
+                let repr = ChainsRepr::deserialize(deserializer)?;
+
+                $name::from_chains(repr.params, &repr.chains).map_err(de::Error::custom)
+            }
+        }
+    };
+}
+
+impl_chains_traits!(SecretKey);
+impl_chains_traits!(PublicKey);
+impl_chains_traits!(Signature);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rand::rngs::OsRng;
+
+    /// Swap chains 3 and 4 of `chains`.
+    fn swapped(chains: &[Output<Sha256>]) -> Vec<Output<Sha256>> {
+        let mut chains = chains.to_vec();
+        chains.swap(3, 4);
+        chains
+    }
+
+    /// Sign a message whose digest has equal digits for chains 3 and 4, then
+    /// check whether the signature still verifies with both the secret key
+    /// and the signature chains swapped.
+    fn swapped_chains_verify(params: Params) -> bool {
+        let message = (0u32..)
+            .map(|i| i.to_be_bytes())
+            .find(|m| {
+                let hash = Sha256::digest(m);
+                hash[3] == hash[4]
+            })
+            .unwrap();
+
+        let mut csprng = OsRng {};
+        let keypair: Keypair = Keypair::generate(&mut csprng, params);
+        let signature = keypair.sign(&message);
+
+        let secret: SecretKey =
+            SecretKey::from_chains(params, &swapped(keypair.secret.chains())).unwrap();
+        let public = PublicKey::from(&secret);
+        let signature = Signature::from_chains(params, &swapped(signature.chains())).unwrap();
+
+        public.verify(&message, &signature)
+    }
+
+    #[test]
+    fn default_params_match_default_scheme() {
+        let message = b"hello";
+
+        let keypair = crate::Keypair::from_seed([42u8; 32]);
+        let secret: SecretKey =
+            SecretKey::from_chains(Params::new(), &keypair.secret.to_bytes()).unwrap();
+        let public = PublicKey::from(&secret);
+
+        assert_eq!(public.chains().concat(), keypair.public.to_bytes().concat());
+        assert_eq!(
+            secret.sign(message).chains().concat(),
+            keypair.sign_unchecked(message).to_bytes().concat()
+        );
+    }
+
+    #[test]
+    fn ct_eq_compares_params_and_chains() {
+        let mut csprng = OsRng {};
+        let keypair: Keypair = Keypair::generate(&mut csprng, Params::new());
+        let signature = keypair.sign(b"hello");
+
+        assert!(bool::from(signature.ct_eq(&keypair.sign(b"hello"))));
+        assert!(!bool::from(signature.ct_eq(&keypair.sign(b"world"))));
+        assert!(keypair.public == PublicKey::from(&keypair.secret));
+
+        let bound = Signature::from_chains(Params::new().with_bind_index(true), signature.chains())
+            .unwrap();
+        assert!(!bool::from(signature.ct_eq(&bound)));
+    }
+
+    #[test]
+    fn bind_index_round_trip() {
+        let message = b"hello";
+
+        let mut csprng = OsRng {};
+        let keypair: Keypair = Keypair::generate(&mut csprng, Params::new().with_bind_index(true));
+
+        let signature = keypair.sign(message);
+        assert!(keypair.verify(message, &signature));
+        assert!(!keypair.verify(b"world", &signature));
+    }
+
+    #[test]
+    fn bind_index_rejects_swapped_chains() {
+        assert!(swapped_chains_verify(Params::new()));
+        assert!(!swapped_chains_verify(Params::new().with_bind_index(true)));
+    }
+
+    #[test]
+    fn describe_default_params() {
+        let description = Params::new().describe();
+
+        assert!(description.contains("256"));
+        assert!(description.contains("32 chains"));
+        assert!(description.contains("34 chains"));
+        assert!(description.contains("1088-byte"));
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn serde_round_trip_keeps_params() {
+        let message = b"hello";
+
+        let params = Params::new().with_bind_index(true);
+        let keypair: Keypair = Keypair::generate(&mut OsRng {}, params);
+        let signature = keypair.sign(message);
+
+        let bytes = bincode::serialize(&keypair).unwrap();
+        let keypair: Keypair = bincode::deserialize(&bytes).unwrap();
+        assert_eq!(keypair.public.params(), params);
+        assert_eq!(keypair.secret.params(), params);
+
+        let bytes = bincode::serialize(&signature).unwrap();
+        let signature: Signature = bincode::deserialize(&bytes).unwrap();
+        assert!(keypair.verify(message, &signature));
+
+        let bytes = bincode::serialize(&params).unwrap();
+        assert_eq!(bincode::deserialize::<Params>(&bytes).unwrap(), params);
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn serde_rejects_wrong_length() {
+        let bytes = bincode::serialize(&[0u8; 32].as_slice()).unwrap();
+        assert!(bincode::deserialize::<Signature>(&bytes).is_err());
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn security_after_one_signature_is_in_range() {
+        let bits = Params::new().security_after_one_signature();
+
+        assert!((234.0..236.0).contains(&bits), "{}", bits);
+    }
+
+    #[test]
+    fn w4_round_trip() {
+        let message = b"hello";
+
+        let params = Params::new().with_w(4).unwrap();
+        assert_eq!(params.message_chains(), 128);
+        assert_eq!(params.checksum_chains(), 5);
+
+        let keypair: Keypair = Keypair::generate(&mut OsRng {}, params);
+        let signature = keypair.sign(message);
+
+        assert_eq!(signature.chains().len() * 32, params.signature_size());
+        assert!(keypair.verify(message, &signature));
+        assert!(!keypair.verify(b"world", &signature));
+    }
+
+    #[test]
+    fn w16_round_trip() {
+        let message = b"hello";
+
+        let params = Params::new().with_w(16).unwrap();
+        assert_eq!(params.message_chains(), 64);
+        assert_eq!(params.checksum_chains(), 3);
+
+        let keypair: Keypair = Keypair::generate(&mut OsRng {}, params);
+        let signature = keypair.sign(message);
+
+        assert_eq!(signature.chains().len(), params.chains());
+        assert_eq!(signature.chains().len(), 67);
+        assert!(keypair.verify(message, &signature));
+        assert!(!keypair.verify(b"world", &signature));
+    }
+
+    #[test]
+    fn w4_digits_split_bytes_most_significant_first() {
+        let params = Params::new().with_w(4).unwrap();
+        let digest = Sha256::digest(b"hello");
+
+        let digits = params.digits::<Sha256>(b"hello");
+        assert_eq!(digits.len(), params.chains());
+        assert_eq!(
+            digits[..4],
+            [
+                digest[0] >> 6,
+                (digest[0] >> 4) & 3,
+                (digest[0] >> 2) & 3,
+                digest[0] & 3
+            ]
+        );
+
+        let checksum: usize = digits[..128].iter().map(|&d| 3 - d as usize).sum();
+        let encoded = digits[128..].iter().fold(0, |sum, &d| sum * 4 + d as usize);
+        assert_eq!(encoded, checksum);
+    }
+
+    #[test]
+    fn unsupported_w_is_rejected() {
+        assert_eq!(
+            Params::new().with_w(8),
+            Err(WotsError::UnsupportedW { w: 8 })
+        );
+    }
+
+    #[test]
+    fn mismatched_params_do_not_verify() {
+        let message = b"hello";
+
+        let keypair: Keypair = Keypair::generate(&mut OsRng {}, Params::new());
+        let signature = keypair.sign(message);
+
+        let params = Params::new().with_bind_index(true);
+        let signature = Signature::from_chains(params, signature.chains()).unwrap();
+        assert!(!keypair.verify(message, &signature));
+    }
+
+    #[test]
+    fn digit_slice_round_trip() {
+        let params = Params::new().with_w(16).unwrap();
+        let keypair: Keypair = Keypair::generate(&mut OsRng {}, params);
+
+        let digits: Vec<u8> = (0..64).map(|i| i % 16).collect();
+        let signature = keypair.secret.sign_digit_slice(&digits).unwrap();
+        assert_eq!(
+            keypair.public.verify_digit_slice(&digits, &signature),
+            Ok(true)
+        );
+
+        let mut other = digits.clone();
+        other[0] = 1;
+        assert_eq!(
+            keypair.public.verify_digit_slice(&other, &signature),
+            Ok(false)
+        );
+    }
+
+    #[test]
+    fn digit_slice_rejects_wrong_length() {
+        let params = Params::new().with_w(16).unwrap();
+        let keypair: Keypair = Keypair::generate(&mut OsRng {}, params);
+        let signature = keypair.sign(b"hello");
+
+        assert_eq!(
+            keypair.public.verify_digit_slice(&[0u8; 32], &signature),
+            Err(WotsError::LengthMismatch {
+                expected: 64,
+                actual: 32
+            })
+        );
+    }
+
+    #[test]
+    fn digit_slice_rejects_out_of_range_digit() {
+        let params = Params::new().with_w(16).unwrap();
+        let keypair: Keypair = Keypair::generate(&mut OsRng {}, params);
+        let signature = keypair.sign(b"hello");
+
+        let mut digits = [0u8; 64];
+        digits[9] = 16;
+        assert_eq!(
+            keypair.public.verify_digit_slice(&digits, &signature),
+            Err(WotsError::InvalidDigit {
+                index: 9,
+                digit: 16
+            })
+        );
+        assert!(keypair.secret.sign_digit_slice(&digits).is_err());
+    }
+
+    #[test]
+    fn from_chains_rejects_wrong_length() {
+        assert_eq!(
+            Signature::<Sha256>::from_chains(Params::new(), &[[0u8; 32]; 32]).err(),
+            Some(WotsError::LengthMismatch {
+                expected: 34,
+                actual: 32
+            })
+        );
+        assert_eq!(
+            Signature::<Sha256>::from_chains(Params::new(), &[[0u8; 31]; 34]).err(),
+            Some(WotsError::LengthMismatch {
+                expected: 32,
+                actual: 31
+            })
+        );
+    }
+
+    #[test]
+    fn chains_follow_digest_size() {
+        let message = b"hello";
+        let params = Params::new();
+
+        let sha256: Keypair<Sha256> = Keypair::generate(&mut OsRng {}, params);
+        let sha512: Keypair<sha2::Sha512> = Keypair::generate(&mut OsRng {}, params);
+
+        let signature = sha256.sign(message);
+        assert_eq!(signature.chains().len(), 34);
+        assert!(sha256.verify(message, &signature));
+        assert!(!sha256.verify(b"world", &signature));
+
+        let signature = sha512.sign(message);
+        assert_eq!(signature.chains().len(), 66);
+        assert_eq!(signature.chains()[0].len(), 64);
+        assert!(sha512.verify(message, &signature));
+        assert!(!sha512.verify(b"world", &signature));
+    }
+}
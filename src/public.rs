@@ -1,10 +1,37 @@
 //! WOTS public keys.
-use crate::{secret::SecretKey, signature::Signature};
-use sha256_rs::*;
+#[cfg(feature = "std")]
+use crate::hash::sha256_reader;
+use crate::{
+    checksum::with_checksum,
+    constants::{steps, CHAINS, CHAIN_LENGTH, MESSAGE_CHAINS, SIGNATURE_LENGTH},
+    context::context_digest,
+    counter::{counter_digest, CounterSignature},
+    digest32::{Commitment, CompactPublicKey},
+    encoding::Encoding,
+    error::{VerifyError, WotsError},
+    hash::{iterate, iterate_chains, sha256},
+    hex::{self, chains_debug_string},
+    randomized::{randomized_digest, RandomizedSignature},
+    secret::SecretKey,
+    signature::{chains_from_bytes, Signature},
+};
+use alloc::{string::String, vec::Vec};
+use core::{fmt, str::FromStr};
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use subtle::{Choice, ConstantTimeEq};
 
 /// An WOTS public key.
-#[derive(Eq, PartialEq)]
-pub struct PublicKey([[u8; 32]; 32]);
+///
+/// Verification compares the recovered key in constant time. Comparing
+/// public keys with `==` is variable-time; use [`ConstantTimeEq::ct_eq`]
+/// when the comparison must not leak timing.
+#[derive(Clone, Copy, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct PublicKey<const N: usize = 32, const L: usize = CHAINS>(
+    #[cfg_attr(feature = "serde", serde(with = "crate::serialization"))] [[u8; N]; L],
+);
 
 impl PublicKey {
     /// Verify a `signature` on a `message` using the WOTS algorithm.
@@ -36,54 +63,952 @@ impl PublicKey {
     ///
     /// ```
     pub fn verify(&self, message: &[u8], signature: Signature) -> bool {
-        let signature = signature.to_bytes();
+        self.verify_detailed(message, &signature).is_ok()
+    }
+
+    /// Verify a `signature` on a `message`, reporting why verification failed.
+    ///
+    /// The recovered public key is compared in constant time. Only once it
+    /// is known not to match is the first mismatching chain looked up.
+    ///
+    /// # Returns
+    ///
+    /// Returns `Ok(())` if the `signature` is valid,
+    /// `VerifyError::ChainMismatch` if the first mismatching chain signs a
+    /// message digit, or `VerifyError::ChecksumMismatch` if it signs a
+    /// checksum digit.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use wots_rs::{Keypair, VerifyError};
+    ///
+    /// let keypair = Keypair::from_seed(&[7u8; 32]);
+    /// let signature = keypair.sign_unchecked(b"hello");
+    ///
+    /// assert_eq!(keypair.public.verify_detailed(b"hello", &signature), Ok(()));
+    ///
+    /// let mut chains = signature.to_bytes();
+    /// chains[3][0] ^= 1;
+    ///
+    /// assert_eq!(
+    ///     keypair.public.verify_detailed(b"hello", &chains.into()),
+    ///     Err(VerifyError::ChainMismatch { index: 3 })
+    /// );
+    ///
+    /// ```
+    pub fn verify_detailed(
+        &self,
+        message: &[u8],
+        signature: &Signature,
+    ) -> Result<(), VerifyError> {
+        let public_key = recover(&sha256(message), signature);
+
+        if self.matches(&public_key) {
+            return Ok(());
+        }
+
+        match self
+            .0
+            .iter()
+            .zip(public_key.iter())
+            .position(|(a, b)| a != b)
+        {
+            Some(index) if index >= MESSAGE_CHAINS => Err(VerifyError::ChecksumMismatch { index }),
+            Some(index) => Err(VerifyError::ChainMismatch { index }),
+            None => unreachable!("mismatching public keys have a mismatching chain"),
+        }
+    }
+
+    /// Parse a flat `signature` and verify it on a `message`, reporting why
+    /// verification failed.
+    ///
+    /// # Returns
+    ///
+    /// Returns `VerifyError::MalformedSignature` if `signature` isn't exactly
+    /// [`SIGNATURE_LENGTH`](crate::SIGNATURE_LENGTH) bytes long, and
+    /// otherwise the result of [`verify_detailed`](Self::verify_detailed).
+    pub fn verify_bytes_detailed(
+        &self,
+        message: &[u8],
+        signature: &[u8],
+    ) -> Result<(), VerifyError> {
+        self.verify_detailed(message, &Signature::from_bytes(signature)?)
+    }
+
+    /// Recover the public key a `signature` on a `message` verifies under.
+    ///
+    /// Every signature recovers some public key; it is only valid if the
+    /// recovered key is the expected one, e.g. one whose
+    /// [`commitment`](Self::commitment) is stored on chain.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use wots_rs::{Keypair, PublicKey};
+    ///
+    /// let keypair = Keypair::from_seed(&[7u8; 32]);
+    ///
+    /// let message = b"hello";
+    /// let signature = keypair.sign_unchecked(message);
+    ///
+    /// assert!(PublicKey::recover(message, &signature) == keypair.public);
+    ///
+    /// ```
+    pub fn recover(message: &[u8], signature: &Signature) -> PublicKey {
+        PublicKey(recover(&sha256(message), signature))
+    }
+
+    /// Verify a `signature` on a `message` supplied as a sequence of `chunks`.
+    ///
+    /// The chunks are hashed incrementally, so the result is the same as
+    /// verifying their concatenation without building it.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use rand::rngs::OsRng;
+    /// use wots_rs::Keypair;
+    ///
+    /// let mut csprng = OsRng{};
+    /// let keypair: Keypair = Keypair::generate(&mut csprng);
+    ///
+    /// let signature = keypair.sign_unchecked(b"hello world");
+    ///
+    /// assert!(keypair.public.verify_chunks(&[b"hello", b" ", b"world"], &signature));
+    ///
+    /// ```
+    pub fn verify_chunks(&self, chunks: &[&[u8]], signature: &Signature) -> bool {
+        let mut hasher = Sha256::new();
+
+        for chunk in chunks {
+            hasher.update(chunk);
+        }
+
+        self.verify_digest(&hasher.finalize(), signature)
+            .unwrap_or(false)
+    }
+
+    /// Verify a `signature` on a message read from `reader`, hashing it
+    /// incrementally instead of buffering it.
+    ///
+    /// # Errors
+    ///
+    /// Returns any error from reading `reader`.
+    #[cfg(feature = "std")]
+    pub fn verify_stream(
+        &self,
+        reader: impl std::io::Read,
+        signature: &Signature,
+    ) -> std::io::Result<bool> {
+        Ok(self.verify_digits(&sha256_reader(reader)?, signature))
+    }
+
+    /// Verify a `signature` on a `message` made with
+    /// [`Keypair::sign_with_context`](crate::Keypair::sign_with_context)
+    /// under `context`.
+    ///
+    /// Plain signatures from [`sign`](crate::Keypair::sign) don't verify
+    /// under any context, the empty one included.
+    pub fn verify_with_context(
+        &self,
+        message: &[u8],
+        context: &[u8],
+        signature: &Signature,
+    ) -> bool {
+        self.verify_digits(&context_digest(message, context), signature)
+    }
+
+    /// Verify a `signature` on a `message` made with
+    /// [`Keypair::sign_counter`](crate::Keypair::sign_counter).
+    ///
+    /// # Returns
+    ///
+    /// Returns the committed counter if the signature is valid, or `None`
+    /// otherwise.
+    pub fn verify_counter(&self, message: &[u8], signature: &CounterSignature) -> Option<u64> {
+        let digest = counter_digest(message, signature.counter);
+
+        self.verify_digits(&digest, &signature.signature)
+            .then_some(signature.counter)
+    }
 
-        let mut public_key = [[0u8; 32]; 32];
-        let hash = sha256(message);
+    /// Verify a randomized `signature` on a `message`.
+    ///
+    /// See [`Keypair::sign_randomized`](crate::Keypair::sign_randomized).
+    pub fn verify_randomized(&self, message: &[u8], signature: &RandomizedSignature) -> bool {
+        self.verify_digits(
+            &randomized_digest(message, &signature.r),
+            &signature.signature,
+        )
+    }
 
-        for (i, key) in public_key.iter_mut().enumerate() {
-            let mut s = signature[i];
-            let n = hash[i];
+    /// Verify a batch of `(message, signature)` pairs against this public key.
+    ///
+    /// With `early_exit` set, verification stops at the first invalid pair.
+    /// Otherwise every pair is verified before the result is returned, so the
+    /// amount of work doesn't depend on which pair failed.
+    ///
+    /// # Returns
+    ///
+    /// Returns `true` if every signature in `items` is valid.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use rand::rngs::OsRng;
+    /// use wots_rs::Keypair;
+    ///
+    /// let mut csprng = OsRng{};
+    /// let keypair: Keypair = Keypair::generate(&mut csprng);
+    ///
+    /// let hello = keypair.sign_unchecked(b"hello");
+    /// let world = keypair.sign_unchecked(b"world");
+    /// let items: [(&[u8], _); 2] = [(b"hello", &hello), (b"world", &world)];
+    ///
+    /// assert!(keypair.public.verify_batch_all(&items, true));
+    ///
+    /// ```
+    pub fn verify_batch_all(&self, items: &[(&[u8], &Signature)], early_exit: bool) -> bool {
+        let mut verify = items
+            .iter()
+            .map(|(message, signature)| self.verify_ref(message, signature));
 
-            for _ in 0..n as usize {
-                s = sha256(&s);
+        if early_exit {
+            verify.all(|valid| valid)
+        } else {
+            verify.fold(true, |all, valid| all & valid)
+        }
+    }
+
+    /// Verify a batch of `(message, signature)` pairs against this public key,
+    /// reporting the result of each.
+    ///
+    /// With the `rayon` feature enabled the pairs are verified in parallel.
+    ///
+    /// # Returns
+    ///
+    /// One result per pair, in the order of `items`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use wots_rs::Keypair;
+    ///
+    /// let keypair = Keypair::from_seed(&[7u8; 32]);
+    ///
+    /// let signature = keypair.sign_unchecked(b"hello");
+    /// let items: [(&[u8], _); 2] = [(b"hello", &signature), (b"world", &signature)];
+    ///
+    /// assert_eq!(keypair.public.verify_batch(&items), [true, false]);
+    ///
+    /// ```
+    pub fn verify_batch(&self, items: &[(&[u8], &Signature)]) -> Vec<bool> {
+        #[cfg(feature = "rayon")]
+        {
+            use rayon::prelude::*;
+
+            items
+                .par_iter()
+                .map(|(message, signature)| self.verify_ref(message, signature))
+                .collect()
+        }
+
+        #[cfg(not(feature = "rayon"))]
+        items
+            .iter()
+            .map(|(message, signature)| self.verify_ref(message, signature))
+            .collect()
+    }
+
+    /// Verify a `signature` on an already computed message `digest`.
+    ///
+    /// # Inputs
+    ///
+    /// * `digest` is the hash of the message, one byte per message chain.
+    /// * `signature` is a purported WOTS [`Signature`] on the `digest`.
+    ///
+    /// # Returns
+    ///
+    /// Returns `Ok(true)` if the `signature` is valid for the `digest`, or
+    /// `WotsError::LengthMismatch` if the `digest` does not hold exactly one
+    /// byte per message chain.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use rand::rngs::OsRng;
+    /// use sha256_rs::sha256;
+    /// use wots_rs::Keypair;
+    ///
+    /// let mut csprng = OsRng{};
+    /// let keypair: Keypair = Keypair::generate(&mut csprng);
+    ///
+    /// let message = b"hello";
+    /// let signature = keypair.sign_unchecked(message);
+    ///
+    /// assert_eq!(keypair.public.verify_digest(&sha256(message), &signature), Ok(true));
+    ///
+    /// ```
+    pub fn verify_digest(&self, digest: &[u8], signature: &Signature) -> Result<bool, WotsError> {
+        let digest = digest_chains(digest)?;
+
+        Ok(self.verify_digits(digest, signature))
+    }
+
+    /// Verify a `signature` on a message given by its SHA-256 `digest`.
+    ///
+    /// A signature made with [`SecretKey::sign_prehashed`] verifies here,
+    /// and a signature from [`SecretKey::sign`] verifies against the
+    /// SHA-256 digest of its message. The caller is responsible for hashing
+    /// the message with a collision-resistant 32-byte hash; the digest is
+    /// trusted as is.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use sha2::{Digest, Sha256};
+    /// use wots_rs::Keypair;
+    ///
+    /// let keypair = Keypair::from_seed(&[7u8; 32]);
+    ///
+    /// let digest: [u8; 32] = Sha256::digest(b"a large artifact").into();
+    /// let signature = keypair.sign_prehashed(&digest);
+    ///
+    /// assert!(keypair.public.verify_prehashed(&digest, &signature));
+    /// assert!(keypair.public.verify(b"a large artifact", signature));
+    ///
+    /// ```
+    pub fn verify_prehashed(&self, digest: &[u8; 32], signature: &Signature) -> bool {
+        self.verify_digits(digest, signature)
+    }
+
+    /// Verify a `signature` on an explicit array of base-`w` `digits`, one
+    /// per message chain, bypassing the message hash.
+    ///
+    /// With `w = 256` every byte is a valid digit. The checksum chains are
+    /// verified as usual.
+    pub fn verify_digits(&self, digits: &[u8; MESSAGE_CHAINS], signature: &Signature) -> bool {
+        self.matches(&recover(digits, signature))
+    }
+
+    /// Verify a `signature` on a `message`, reporting why verification failed.
+    ///
+    /// With the `tracing` feature enabled, a failed verification emits a
+    /// `debug` event with the index of the first mismatching chain and the
+    /// length of the message. Key and signature bytes are never logged.
+    ///
+    /// # Inputs
+    ///
+    /// * `message` in bytes representation.
+    /// * `signature` is a purported WOTS [`Signature`] on the `message`.
+    ///
+    /// # Returns
+    ///
+    /// Returns `Ok(())` if the `signature` was a valid signature created by the
+    /// secret half of this public key on the `message`, or
+    /// `WotsError::ChainMismatch` naming the first chain that failed to verify.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use rand::rngs::OsRng;
+    /// use wots_rs::{PublicKey, SecretKey};
+    ///
+    /// let mut csprng = OsRng{};
+    /// let secret_key: SecretKey = SecretKey::generate(&mut csprng);
+    /// let public_key: PublicKey = PublicKey::from(&secret_key);
+    ///
+    /// let message = b"hello";
+    /// let signature = secret_key.sign(message);
+    ///
+    /// assert!(public_key.verify_strict(message, &signature).is_ok());
+    ///
+    /// ```
+    pub fn verify_strict(&self, message: &[u8], signature: &Signature) -> Result<(), WotsError> {
+        match self.first_mismatch(&sha256(message), signature) {
+            None => Ok(()),
+            Some(index) => {
+                #[cfg(feature = "tracing")]
+                tracing::debug!(
+                    chain = index,
+                    message_len = message.len(),
+                    "WOTS signature verification failed"
+                );
+
+                Err(WotsError::ChainMismatch { index })
             }
+        }
+    }
+
+    /// Verify a `signature` on a `message`, reporting every chain that
+    /// failed to verify.
+    ///
+    /// Many mismatches suggest random corruption, while a single mismatch
+    /// suggests a targeted edit of one chain.
+    ///
+    /// # Returns
+    ///
+    /// Returns `Ok(())` if the `signature` is valid, or the indices of all
+    /// mismatching chains in ascending order.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use rand::rngs::OsRng;
+    /// use wots_rs::Keypair;
+    ///
+    /// let mut csprng = OsRng{};
+    /// let keypair: Keypair = Keypair::generate(&mut csprng);
+    ///
+    /// let message = b"hello";
+    /// let signature = keypair.sign_unchecked(message);
+    ///
+    /// assert_eq!(keypair.public.verify_all_mismatches(message, &signature), Ok(()));
+    ///
+    /// ```
+    pub fn verify_all_mismatches(
+        &self,
+        message: &[u8],
+        signature: &Signature,
+    ) -> Result<(), Vec<usize>> {
+        let public_key = recover(&sha256(message), signature);
+        if self.matches(&public_key) {
+            return Ok(());
+        }
 
-            *key = s;
+        // Only a failed constant-time check reaches the per-chain comparison,
+        // which runs in variable time to locate the mismatches.
+        Err(self
+            .0
+            .iter()
+            .zip(public_key.iter())
+            .enumerate()
+            .filter(|(_, (expected, actual))| expected != actual)
+            .map(|(index, _)| index)
+            .collect())
+    }
+
+    /// Verify a borrowed `signature` on a `message`, for the batch checks.
+    ///
+    /// The recovered public key is a 1088-byte array on the stack, so a
+    /// batch allocates nothing per item and there is no buffer to reuse.
+    fn verify_ref(&self, message: &[u8], signature: &Signature) -> bool {
+        self.verify_digits(&sha256(message), signature)
+    }
+
+    /// Compare this public key to recovered `chains` in constant time.
+    fn matches(&self, chains: &[[u8; 32]; CHAINS]) -> bool {
+        self.0.as_flattened().ct_eq(chains.as_flattened()).into()
+    }
+
+    /// Find the first chain of the public key which does not match the chain
+    /// recovered from the `digest` and `signature`.
+    ///
+    /// A valid signature is accepted by the constant-time comparison; only
+    /// a failed one is searched for its first mismatch.
+    fn first_mismatch(
+        &self,
+        digest: &[u8; MESSAGE_CHAINS],
+        signature: &Signature,
+    ) -> Option<usize> {
+        let public_key = recover(digest, signature);
+        if self.matches(&public_key) {
+            return None;
         }
 
-        self.0 == public_key
+        self.0
+            .iter()
+            .zip(public_key.iter())
+            .position(|(expected, actual)| expected != actual)
+    }
+
+    /// Compute the commitment to this public key, `sha256` of its flat bytes.
+    ///
+    /// The commitment is a compact stand-in for the full public key, e.g. for
+    /// storing on chain.
+    pub fn commitment(&self) -> Commitment {
+        Commitment::from(sha256(self.0.as_flattened()))
+    }
+
+    /// Compress this public key into one 32-byte hash of its chains.
+    ///
+    /// This is the bytes of its [`commitment`](Self::commitment), so
+    /// compressed keys can be checked with
+    /// [`verify_compressed`](Self::verify_compressed). Wrap it in a
+    /// [`CompactPublicKey`] to keep it apart from other 32-byte values.
+    pub fn compress(&self) -> [u8; 32] {
+        self.commitment().to_bytes()
+    }
+
+    /// Verify a `signature` on a `message` against a `compressed` public key.
+    ///
+    /// The public key is recovered from the `message` and `signature`,
+    /// compressed, and compared to `compressed` in constant time.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use wots_rs::{Keypair, PublicKey};
+    ///
+    /// let keypair = Keypair::from_seed(&[7u8; 32]);
+    /// let compressed = keypair.public.compress();
+    ///
+    /// let message = b"hello";
+    /// let signature = keypair.sign_unchecked(message);
+    ///
+    /// assert!(PublicKey::verify_compressed(message, &signature, &compressed));
+    ///
+    /// ```
+    pub fn verify_compressed(message: &[u8], signature: &Signature, compressed: &[u8; 32]) -> bool {
+        PublicKey::recover(message, signature)
+            .compress()
+            .ct_eq(compressed)
+            .into()
+    }
+
+    /// Commit to a set of public keys, `sha256` of their sorted
+    /// [`commitment`](Self::commitment)s.
+    ///
+    /// The commitments are sorted first, so the order of `keys` doesn't
+    /// matter.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use wots_rs::{Keypair, PublicKey};
+    ///
+    /// let alice = Keypair::from_seed(&[1u8; 32]);
+    /// let bob = Keypair::from_seed(&[2u8; 32]);
+    ///
+    /// let signers = [alice.public, bob.public];
+    /// let commitment = PublicKey::combine(&signers);
+    ///
+    /// ```
+    pub fn combine(keys: &[PublicKey]) -> Commitment {
+        let mut commitments = keys
+            .iter()
+            .map(|key| key.commitment().to_bytes())
+            .collect::<Vec<_>>();
+        commitments.sort_unstable();
+
+        Commitment::from(sha256(commitments.as_flattened()))
+    }
+
+    /// Render this public key for debugging, one `chain[i]: <hex>` line per
+    /// chain.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use wots_rs::Keypair;
+    ///
+    /// let keypair = Keypair::from_seed(&[7u8; 32]);
+    ///
+    /// println!("{}", keypair.public.to_debug_string());
+    ///
+    /// ```
+    pub fn to_debug_string(&self) -> String {
+        chains_debug_string(&self.0)
+    }
+
+    /// Derive a one-time address from this public key and a `nonce`.
+    ///
+    /// The address is the first 20 bytes of `sha256(commitment || nonce)`,
+    /// where `commitment` is [`commitment`](Self::commitment), so each nonce
+    /// gives an unlinkable address for the same key.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use wots_rs::Keypair;
+    ///
+    /// let keypair = Keypair::from_seed(&[7u8; 32]);
+    ///
+    /// assert_ne!(keypair.public.address(b"1"), keypair.public.address(b"2"));
+    ///
+    /// ```
+    pub fn address(&self, nonce: &[u8]) -> [u8; 20] {
+        let mut hasher = Sha256::new();
+        hasher.update(self.commitment().as_ref());
+        hasher.update(nonce);
+
+        let mut address = [0u8; 20];
+        address.copy_from_slice(&hasher.finalize()[..20]);
+
+        address
+    }
+
+    /// Fingerprint this public key, `sha256` of its flat bytes.
+    ///
+    /// The fingerprint names the key in logs and records without carrying
+    /// its full 1088 bytes. It is the same hash as
+    /// [`commitment`](Self::commitment).
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use wots_rs::Keypair;
+    ///
+    /// let keypair = Keypair::from_seed(&[7u8; 32]);
+    ///
+    /// assert_eq!(keypair.public.fingerprint(), keypair.public.compress());
+    /// assert_eq!(keypair.public.short_fingerprint().len(), 16);
+    ///
+    /// ```
+    pub fn fingerprint(&self) -> [u8; 32] {
+        self.commitment().to_bytes()
+    }
+
+    /// The first 8 bytes of the [`fingerprint`](Self::fingerprint) as
+    /// lowercase hex, for display.
+    pub fn short_fingerprint(&self) -> String {
+        hex::encode(&self.fingerprint()[..8])
     }
 
     /// Convert this public key to a byte array.
-    pub fn to_bytes(&self) -> [[u8; 32]; 32] {
+    pub fn to_bytes(&self) -> [[u8; 32]; CHAINS] {
         self.0
     }
+
+    /// Convert this public key to a flat byte array, chain by chain.
+    pub fn to_flat_bytes(&self) -> [u8; SIGNATURE_LENGTH] {
+        let mut bytes = [0u8; SIGNATURE_LENGTH];
+        bytes.copy_from_slice(self.0.as_flattened());
+
+        bytes
+    }
+
+    /// View this public key as a flat byte slice, chain by chain.
+    pub fn as_bytes(&self) -> &[u8] {
+        self.0.as_flattened()
+    }
+
+    /// Construct a `PublicKey` from a flat byte slice, chain by chain.
+    ///
+    /// # Returns
+    ///
+    /// Returns `WotsError::LengthMismatch` if `bytes` isn't exactly
+    /// [`SIGNATURE_LENGTH`](crate::SIGNATURE_LENGTH) bytes long.
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self, WotsError> {
+        chains_from_bytes(bytes).map(PublicKey)
+    }
+
+    /// Encode this public key as lowercase hex of its flat bytes.
+    pub fn to_hex(&self) -> String {
+        Encoding::Hex.encode(self.0.as_flattened())
+    }
+
+    /// Decode a `PublicKey` from the hex of its flat bytes.
+    ///
+    /// # Returns
+    ///
+    /// Returns `WotsError::InvalidEncoding` if `hex` contains a non-hex
+    /// character or has an odd length, or `WotsError::LengthMismatch` if it
+    /// doesn't decode to exactly [`SIGNATURE_LENGTH`](crate::SIGNATURE_LENGTH)
+    /// bytes.
+    pub fn from_hex(hex: &str) -> Result<Self, WotsError> {
+        PublicKey::from_bytes(&Encoding::Hex.decode(hex)?)
+    }
+
+    /// Encode this public key as base64 of its flat bytes.
+    #[cfg(feature = "base64")]
+    pub fn to_base64(&self) -> String {
+        Encoding::Base64.encode(self.0.as_flattened())
+    }
+
+    /// Decode a `PublicKey` from the base64 of its flat bytes.
+    ///
+    /// # Returns
+    ///
+    /// Returns `WotsError::InvalidEncoding` if `base64` is not valid padded
+    /// base64, or `WotsError::LengthMismatch` if it doesn't decode to
+    /// exactly [`SIGNATURE_LENGTH`](crate::SIGNATURE_LENGTH) bytes.
+    #[cfg(feature = "base64")]
+    pub fn from_base64(base64: &str) -> Result<Self, WotsError> {
+        PublicKey::from_bytes(&Encoding::Base64.decode(base64)?)
+    }
+}
+
+/// Construct a `PublicKey` from a flat byte slice, checking its length.
+impl TryFrom<&[u8]> for PublicKey {
+    type Error = WotsError;
+
+    fn try_from(value: &[u8]) -> Result<Self, Self::Error> {
+        PublicKey::from_bytes(value)
+    }
 }
 
 /// Construct a `PublicKey` from a bytes.
-impl From<[[u8; 32]; 32]> for PublicKey {
-    fn from(value: [[u8; 32]; 32]) -> Self {
+impl<const N: usize, const L: usize> From<[[u8; N]; L]> for PublicKey<N, L> {
+    fn from(value: [[u8; N]; L]) -> Self {
         Self(value)
     }
 }
 
 /// Construct a `PublicKey` from a `SecretKey`.
-impl<'a> From<&'a SecretKey> for PublicKey {
+impl From<&SecretKey> for PublicKey {
     fn from(value: &SecretKey) -> Self {
         let bytes = value.to_bytes();
-        let mut public_key = [[0u8; 32]; 32];
+        let mut public_key = [[0u8; 32]; CHAINS];
 
-        for (i, key) in public_key.iter_mut().enumerate() {
-            let mut skey = bytes[i];
+        iterate_chains(&mut public_key, |i| bytes[i], |_| CHAIN_LENGTH);
 
-            for _ in 0..256 {
-                skey = sha256(&skey);
-            }
+        PublicKey(public_key)
+    }
+}
+
+/// Compare two public keys in constant time.
+impl<const N: usize, const L: usize> ConstantTimeEq for PublicKey<N, L> {
+    fn ct_eq(&self, other: &Self) -> Choice {
+        self.0.as_flattened().ct_eq(other.0.as_flattened())
+    }
+}
 
-            *key = skey;
+/// Format a `PublicKey` as the hex of its first bytes.
+impl<const N: usize, const L: usize> fmt::Debug for PublicKey<N, L> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "PublicKey({}...)", hex::encode(&self.0[0][..8]))
+    }
+}
+
+/// Format a `PublicKey` as lowercase hex of its flat bytes, like [`PublicKey::to_hex`].
+impl<const N: usize, const L: usize> fmt::Display for PublicKey<N, L> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::LowerHex::fmt(self, f)
+    }
+}
+
+/// Format a `PublicKey` as lowercase hex of its flat bytes.
+impl<const N: usize, const L: usize> fmt::LowerHex for PublicKey<N, L> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for byte in self.0.as_flattened() {
+            write!(f, "{:02x}", byte)?;
         }
 
-        PublicKey(public_key)
+        Ok(())
     }
 }
+
+/// Parse a `PublicKey` from the hex of its flat bytes, like [`PublicKey::from_hex`].
+impl FromStr for PublicKey {
+    type Err = WotsError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        PublicKey::from_hex(s)
+    }
+}
+
+/// View a `PublicKey` as a reference to its underlying bytes.
+impl<const N: usize, const L: usize> AsRef<[[u8; N]; L]> for PublicKey<N, L> {
+    fn as_ref(&self) -> &[[u8; N]; L] {
+        &self.0
+    }
+}
+
+impl CompactPublicKey {
+    /// Verify a `signature` on a `message` against this compact public key.
+    ///
+    /// The same as [`PublicKey::verify_compressed`], so only these 32 bytes
+    /// need to be published.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use wots_rs::{CompactPublicKey, Keypair};
+    ///
+    /// let keypair = Keypair::from_seed(&[7u8; 32]);
+    /// let compact = CompactPublicKey::from(&keypair.public);
+    ///
+    /// let message = b"hello";
+    /// let signature = keypair.sign_unchecked(message);
+    ///
+    /// assert!(compact.verify(message, &signature));
+    /// assert!(!compact.verify(b"world", &signature));
+    ///
+    /// ```
+    pub fn verify(&self, message: &[u8], signature: &Signature) -> bool {
+        PublicKey::verify_compressed(message, signature, &self.to_bytes())
+    }
+}
+
+/// Compress a `PublicKey` into the hash of its chains.
+impl From<&PublicKey> for CompactPublicKey {
+    fn from(value: &PublicKey) -> Self {
+        CompactPublicKey::from(value.compress())
+    }
+}
+
+/// Check that a `digest` holds exactly one byte per message chain.
+fn digest_chains(digest: &[u8]) -> Result<&[u8; MESSAGE_CHAINS], WotsError> {
+    digest.try_into().map_err(|_| WotsError::LengthMismatch {
+        expected: MESSAGE_CHAINS,
+        actual: digest.len(),
+    })
+}
+
+/// Recover the public key chains from a `digest` and a `signature`.
+pub(crate) fn recover(digest: &[u8; MESSAGE_CHAINS], signature: &Signature) -> [[u8; 32]; CHAINS] {
+    let digest = with_checksum(digest);
+    let mut public_key = [[0u8; 32]; CHAINS];
+    iterate_chains(
+        &mut public_key,
+        |i| signature.as_ref()[i],
+        |i| steps(digest[i]),
+    );
+
+    public_key
+}
+
+/// Complete a signature chain to its public key chain.
+///
+/// Applies `digit` hash steps to the signature chain value `sig_chain`,
+/// exactly as verification does for a chain signing `digit`.
+///
+/// # Example
+///
+/// ```
+/// use wots_rs::{finish_chain, Keypair};
+///
+/// let keypair = Keypair::from_seed(&[7u8; 32]);
+///
+/// let digits = [9u8; 32];
+/// let signature = keypair.sign_digits(&digits).to_bytes();
+///
+/// assert_eq!(finish_chain(&signature[0], digits[0]), keypair.public.to_bytes()[0]);
+///
+/// ```
+pub fn finish_chain(sig_chain: &[u8; 32], digit: u8) -> [u8; 32] {
+    iterate(*sig_chain, steps(digit))
+}
+
+/// Verify a batch of `(public_key, message, signature)` triples.
+///
+/// With the `rayon` feature enabled the triples are verified in parallel.
+/// Either way verification stops at the first invalid signature; use
+/// [`verify_batch_failures`] to find which ones failed.
+///
+/// # Example
+///
+/// ```
+/// use wots_rs::{verify_batch, Keypair};
+///
+/// let alice = Keypair::from_seed(&[1u8; 32]);
+/// let bob = Keypair::from_seed(&[2u8; 32]);
+///
+/// let hello = alice.sign(b"hello");
+/// let world = bob.sign(b"world");
+///
+/// assert!(verify_batch(&[
+///     (&alice.public, b"hello", &hello),
+///     (&bob.public, b"world", &world),
+/// ]));
+/// assert!(!verify_batch(&[(&alice.public, b"world", &world)]));
+///
+/// ```
+pub fn verify_batch(items: &[(&PublicKey, &[u8], &Signature)]) -> bool {
+    #[cfg(feature = "rayon")]
+    {
+        use rayon::prelude::*;
+
+        items
+            .par_iter()
+            .all(|(public_key, message, signature)| public_key.verify_ref(message, signature))
+    }
+
+    #[cfg(not(feature = "rayon"))]
+    items
+        .iter()
+        .all(|(public_key, message, signature)| public_key.verify_ref(message, signature))
+}
+
+/// Verify a batch of `(public_key, message, signature)` triples, reporting
+/// the positions of the invalid ones.
+///
+/// With the `rayon` feature enabled the triples are verified in parallel.
+///
+/// # Returns
+///
+/// The indices into `items` of the signatures that fail to verify, in
+/// ascending order; empty if all of them verify.
+///
+/// # Example
+///
+/// ```
+/// use wots_rs::{verify_batch_failures, Keypair};
+///
+/// let keypair = Keypair::from_seed(&[1u8; 32]);
+/// let signature = keypair.sign_unchecked(b"hello");
+///
+/// let items = [
+///     (&keypair.public, &b"hello"[..], &signature),
+///     (&keypair.public, &b"world"[..], &signature),
+/// ];
+/// assert_eq!(verify_batch_failures(&items), [1]);
+///
+/// ```
+pub fn verify_batch_failures(items: &[(&PublicKey, &[u8], &Signature)]) -> Vec<usize> {
+    #[cfg(feature = "rayon")]
+    {
+        use rayon::prelude::*;
+
+        items
+            .par_iter()
+            .enumerate()
+            .filter(|(_, (public_key, message, signature))| {
+                !public_key.verify_ref(message, signature)
+            })
+            .map(|(index, _)| index)
+            .collect()
+    }
+
+    #[cfg(not(feature = "rayon"))]
+    items
+        .iter()
+        .enumerate()
+        .filter(|(_, (public_key, message, signature))| !public_key.verify_ref(message, signature))
+        .map(|(index, _)| index)
+        .collect()
+}
+
+/// Verify a signature on a `message` holding only one chain value at a time.
+///
+/// The signature and public key chains are pulled in chain order from the
+/// `signature` and `public_key` providers, e.g. from flash or a serial link,
+/// so neither needs to be held in memory as a whole. Every chain is pulled
+/// and checked, even after a mismatch, and the results are combined in
+/// constant time.
+///
+/// # Example
+///
+/// ```
+/// use wots_rs::{verify_low_mem, Keypair};
+///
+/// let keypair = Keypair::from_seed(&[7u8; 32]);
+///
+/// let message = b"hello";
+/// let signature = keypair.sign_unchecked(message).to_bytes();
+/// let public_key = keypair.public.to_bytes();
+///
+/// let mut signature = signature.iter().copied();
+/// let mut public_key = public_key.iter().copied();
+///
+/// assert!(verify_low_mem(
+///     message,
+///     || signature.next().unwrap(),
+///     || public_key.next().unwrap(),
+/// ));
+///
+/// ```
+pub fn verify_low_mem(
+    message: &[u8],
+    mut signature: impl FnMut() -> [u8; 32],
+    mut public_key: impl FnMut() -> [u8; 32],
+) -> bool {
+    with_checksum(&sha256(message))
+        .iter()
+        .fold(Choice::from(1), |valid, &digit| {
+            valid & finish_chain(&signature(), digit).ct_eq(&public_key())
+        })
+        .into()
+}
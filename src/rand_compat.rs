@@ -0,0 +1,51 @@
+//! Use of `rand_core` 0.9 generators, as used by `rand` 0.9, for the RNG
+//! taking constructors.
+//!
+//! The crate's constructors take the `RngCore` and `CryptoRng` traits of
+//! the `rand` 0.8 ecosystem. With the `rand_core_0_9` feature a generator
+//! of the 0.9 ecosystem is wrapped in [`RandCore09`] to pass it instead.
+//! Without any generator at all, keys can be built from a seed or from
+//! entropy with [`SecretKey::from_entropy`](crate::SecretKey::from_entropy).
+
+/// Adapts a `rand_core` 0.9 generator to the `rand` 0.8 traits the
+/// constructors of this crate take.
+///
+/// # Example
+///
+/// ```
+/// use wots_rs::{Keypair, RandCore09};
+///
+/// # struct Rng09;
+/// # impl rand_core_0_9::RngCore for Rng09 {
+/// #     fn next_u32(&mut self) -> u32 { 7 }
+/// #     fn next_u64(&mut self) -> u64 { 7 }
+/// #     fn fill_bytes(&mut self, dest: &mut [u8]) { dest.fill(7) }
+/// # }
+/// # impl rand_core_0_9::CryptoRng for Rng09 {}
+/// let mut csprng = Rng09;
+/// let keypair = Keypair::generate(&mut RandCore09(&mut csprng));
+///
+/// ```
+#[derive(Debug)]
+pub struct RandCore09<R>(pub R);
+
+impl<R: rand_core_0_9::RngCore> rand::RngCore for RandCore09<R> {
+    fn next_u32(&mut self) -> u32 {
+        self.0.next_u32()
+    }
+
+    fn next_u64(&mut self) -> u64 {
+        self.0.next_u64()
+    }
+
+    fn fill_bytes(&mut self, dest: &mut [u8]) {
+        self.0.fill_bytes(dest);
+    }
+
+    fn try_fill_bytes(&mut self, dest: &mut [u8]) -> Result<(), rand::Error> {
+        self.0.fill_bytes(dest);
+        Ok(())
+    }
+}
+
+impl<R: rand_core_0_9::CryptoRng> rand::CryptoRng for RandCore09<R> {}
@@ -0,0 +1,30 @@
+//! WOTS signatures on randomized message hashes.
+use crate::{
+    context::{domain_digest, Mode},
+    signature::Signature,
+};
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+/// An WOTS signature on a randomized hash of a message, together with the
+/// randomizer.
+///
+/// The signed digest is a domain-separated hash of a fresh random `r`
+/// followed by the message, so a forger must find a second preimage under
+/// a randomizer the signer chose, rather than any collision of the hash,
+/// as in XMSS and SPHINCS+.
+/// See [`Keypair::sign_randomized`](crate::Keypair::sign_randomized).
+#[derive(Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct RandomizedSignature {
+    /// The randomizer drawn at signing time.
+    pub r: [u8; 32],
+    /// The signature itself.
+    pub signature: Signature,
+}
+
+/// The digest signed for a `message` under the randomizer `r`, domain
+/// separated from plain signing.
+pub(crate) fn randomized_digest(message: &[u8], r: &[u8; 32]) -> [u8; 32] {
+    domain_digest(Mode::Randomized, r, message)
+}
@@ -0,0 +1,315 @@
+//! WOTS+ as specified by RFC 8391 (XMSS), instantiated with SHA-256.
+//!
+//! This follows the RFC's `WOTS_genSK`, `WOTS_genPK`, `WOTS_sign` and
+//! `WOTS_pkFromSig` for `n = 32` and `w = 16`, with the hash functions of
+//! the SHA2-256 parameter sets. Secret keys are expanded from a seed as
+//! recommended in section 3.1.7 of the RFC. Keys and signatures are plain
+//! arrays of chain values, like the crate's default types.
+use crate::hash::sha256;
+use subtle::ConstantTimeEq;
+
+/// The Winternitz parameter.
+pub const W: usize = 16;
+
+/// The number of chains signing the message.
+pub const LEN_1: usize = 64;
+
+/// The number of chains signing the checksum.
+pub const LEN_2: usize = 3;
+
+/// The total number of chains in a key or signature.
+pub const LEN: usize = LEN_1 + LEN_2;
+
+/// A hash address, `ADRS` in the RFC, for the hashes of one WOTS+ key.
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub struct Address([u8; 32]);
+
+impl Address {
+    /// An all-zero OTS hash address.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Set the layer address.
+    pub fn set_layer(&mut self, layer: u32) {
+        self.0[0..4].copy_from_slice(&layer.to_be_bytes());
+    }
+
+    /// Set the tree address.
+    pub fn set_tree(&mut self, tree: u64) {
+        self.0[4..12].copy_from_slice(&tree.to_be_bytes());
+    }
+
+    /// Set the OTS address, the index of the key within its tree.
+    pub fn set_ots(&mut self, ots: u32) {
+        self.0[16..20].copy_from_slice(&ots.to_be_bytes());
+    }
+
+    /// Set the address type, clearing the words that follow it.
+    pub(crate) fn set_type(&mut self, kind: u32) {
+        self.0[12..16].copy_from_slice(&kind.to_be_bytes());
+        self.0[16..].fill(0);
+    }
+
+    /// Set the L-tree address, the index of the leaf being compressed.
+    pub(crate) fn set_ltree(&mut self, ltree: u32) {
+        self.0[16..20].copy_from_slice(&ltree.to_be_bytes());
+    }
+
+    /// Set the height of the node within its tree.
+    pub(crate) fn set_tree_height(&mut self, height: u32) {
+        self.0[20..24].copy_from_slice(&height.to_be_bytes());
+    }
+
+    /// Set the index of the node within its level.
+    pub(crate) fn set_tree_index(&mut self, index: u32) {
+        self.0[24..28].copy_from_slice(&index.to_be_bytes());
+    }
+
+    fn set_chain(&mut self, chain: u32) {
+        self.0[20..24].copy_from_slice(&chain.to_be_bytes());
+    }
+
+    fn set_hash(&mut self, hash: u32) {
+        self.0[24..28].copy_from_slice(&hash.to_be_bytes());
+    }
+
+    pub(crate) fn set_key_and_mask(&mut self, key_and_mask: u32) {
+        self.0[28..32].copy_from_slice(&key_and_mask.to_be_bytes());
+    }
+
+    /// Convert this address to its 32-byte representation.
+    pub fn to_bytes(&self) -> [u8; 32] {
+        self.0
+    }
+}
+
+/// Hash `key || m` with the 32-byte domain separator `domain`.
+fn keyed_hash(domain: u8, key: &[u8; 32], m: &[u8; 32]) -> [u8; 32] {
+    let mut input = [0u8; 96];
+    input[31] = domain;
+    input[32..64].copy_from_slice(key);
+    input[64..].copy_from_slice(m);
+
+    sha256(&input)
+}
+
+/// The chaining function `F`.
+fn f(key: &[u8; 32], m: &[u8; 32]) -> [u8; 32] {
+    keyed_hash(0, key, m)
+}
+
+/// The pseudorandom function `PRF`.
+pub(crate) fn prf(key: &[u8; 32], m: &[u8; 32]) -> [u8; 32] {
+    keyed_hash(3, key, m)
+}
+
+/// Apply `s` chain steps to `x`, starting at step `i`.
+fn chain(x: &[u8; 32], i: usize, s: usize, pub_seed: &[u8; 32], adrs: &mut Address) -> [u8; 32] {
+    let mut tmp = *x;
+
+    for j in i..i + s {
+        adrs.set_hash(j as u32);
+        adrs.set_key_and_mask(0);
+        let key = prf(pub_seed, &adrs.0);
+        adrs.set_key_and_mask(1);
+        let mask = prf(pub_seed, &adrs.0);
+
+        for (byte, mask) in tmp.iter_mut().zip(mask.iter()) {
+            *byte ^= mask;
+        }
+        tmp = f(&key, &tmp);
+    }
+
+    tmp
+}
+
+/// Split `m` into base-`w` digits, followed by the digits of its checksum.
+fn digits(m: &[u8; 32]) -> [u8; LEN] {
+    let mut digits = [0u8; LEN];
+
+    for (i, byte) in m.iter().enumerate() {
+        digits[2 * i] = byte >> 4;
+        digits[2 * i + 1] = byte & 0x0f;
+    }
+
+    let checksum: usize = digits[..LEN_1].iter().map(|&d| W - 1 - d as usize).sum();
+    // Shift the 12-bit checksum into the top of two bytes, per the RFC.
+    let checksum = ((checksum << 4) as u16).to_be_bytes();
+    digits[LEN_1] = checksum[0] >> 4;
+    digits[LEN_1 + 1] = checksum[0] & 0x0f;
+    digits[LEN_1 + 2] = checksum[1] >> 4;
+
+    digits
+}
+
+/// Expand a secret key from `sk_seed`: chain `i` is `PRF(sk_seed, toByte(i, 32))`.
+pub fn gen_sk(sk_seed: &[u8; 32]) -> [[u8; 32]; LEN] {
+    let mut sk = [[0u8; 32]; LEN];
+    let mut index = [0u8; 32];
+
+    for (i, chain) in sk.iter_mut().enumerate() {
+        index[28..].copy_from_slice(&(i as u32).to_be_bytes());
+        *chain = prf(sk_seed, &index);
+    }
+
+    sk
+}
+
+/// Compute the public key of `sk` under `pub_seed` and `adrs`.
+pub fn gen_pk(sk: &[[u8; 32]; LEN], pub_seed: &[u8; 32], adrs: &Address) -> [[u8; 32]; LEN] {
+    let mut adrs = *adrs;
+    let mut pk = [[0u8; 32]; LEN];
+
+    for (i, (key, x)) in pk.iter_mut().zip(sk.iter()).enumerate() {
+        adrs.set_chain(i as u32);
+        *key = chain(x, 0, W - 1, pub_seed, &mut adrs);
+    }
+
+    pk
+}
+
+/// Sign the 32-byte message `m` with `sk` under `pub_seed` and `adrs`.
+///
+/// # Example
+///
+/// ```
+/// use wots_rs::rfc8391::{gen_pk, gen_sk, pk_from_sig, sign, Address};
+///
+/// let sk = gen_sk(&[1u8; 32]);
+/// let pub_seed = [2u8; 32];
+/// let mut adrs = Address::new();
+/// adrs.set_ots(5);
+///
+/// let m = [7u8; 32];
+/// let signature = sign(&m, &sk, &pub_seed, &adrs);
+///
+/// assert_eq!(pk_from_sig(&signature, &m, &pub_seed, &adrs), gen_pk(&sk, &pub_seed, &adrs));
+///
+/// ```
+pub fn sign(
+    m: &[u8; 32],
+    sk: &[[u8; 32]; LEN],
+    pub_seed: &[u8; 32],
+    adrs: &Address,
+) -> [[u8; 32]; LEN] {
+    let mut adrs = *adrs;
+    let digits = digits(m);
+    let mut signature = [[0u8; 32]; LEN];
+
+    for (i, (s, x)) in signature.iter_mut().zip(sk.iter()).enumerate() {
+        adrs.set_chain(i as u32);
+        *s = chain(x, 0, digits[i] as usize, pub_seed, &mut adrs);
+    }
+
+    signature
+}
+
+/// Compute the public key a `signature` on the 32-byte message `m` was made
+/// with, under `pub_seed` and `adrs`.
+pub fn pk_from_sig(
+    signature: &[[u8; 32]; LEN],
+    m: &[u8; 32],
+    pub_seed: &[u8; 32],
+    adrs: &Address,
+) -> [[u8; 32]; LEN] {
+    let mut adrs = *adrs;
+    let digits = digits(m);
+    let mut pk = [[0u8; 32]; LEN];
+
+    for (i, (key, s)) in pk.iter_mut().zip(signature.iter()).enumerate() {
+        let digit = digits[i] as usize;
+
+        adrs.set_chain(i as u32);
+        *key = chain(s, digit, W - 1 - digit, pub_seed, &mut adrs);
+    }
+
+    pk
+}
+
+/// Verify a `signature` on the 32-byte message `m` against the public key `pk`.
+pub fn verify(
+    signature: &[[u8; 32]; LEN],
+    m: &[u8; 32],
+    pk: &[[u8; 32]; LEN],
+    pub_seed: &[u8; 32],
+    adrs: &Address,
+) -> bool {
+    pk_from_sig(signature, m, pub_seed, adrs)
+        .as_flattened()
+        .ct_eq(pk.as_flattened())
+        .into()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloc::string::String;
+
+    fn hex(bytes: &[u8]) -> String {
+        crate::hex::encode(bytes)
+    }
+
+    // Computed with an independent implementation of the RFC 8391
+    // pseudocode; the RFC itself publishes no WOTS+ test vectors.
+    #[test]
+    fn matches_reference_vector() {
+        let sk = gen_sk(&[1u8; 32]);
+        let pub_seed = [2u8; 32];
+        let mut adrs = Address::new();
+        adrs.set_ots(5);
+
+        let m = sha256(b"hello");
+        assert_eq!(digits(&m)[..4], [2, 12, 15, 2]);
+        assert_eq!(digits(&m)[LEN_1..], [1, 15, 14]);
+
+        let pk = gen_pk(&sk, &pub_seed, &adrs);
+        let signature = sign(&m, &sk, &pub_seed, &adrs);
+
+        assert_eq!(
+            hex(&sk[0]),
+            "00c2012c6c473ab8e84f776a5e53ac413a5f219e4af56ce050e9709c6c8c22f7"
+        );
+        assert_eq!(
+            hex(&pk[0]),
+            "113683f9bbb2874fe5c0bd102c427fd92c78ed3901f80007ce4e37b14976a187"
+        );
+        assert_eq!(
+            hex(&sha256(pk.as_flattened())),
+            "f5b833595578c6f1b15e7acc98227ee53bae3a45f66bf33242b02c1c2e356db0"
+        );
+        assert_eq!(
+            hex(&signature[0]),
+            "f67f5224d6436cc13695144ef28ce1da2b75109ece454b26b7ba71b983ea5e41"
+        );
+        assert_eq!(
+            hex(&sha256(signature.as_flattened())),
+            "828adc83e26a5a7fe35bd650a99d59aae9783894d05c1a4c35508e2639a0da84"
+        );
+
+        assert!(verify(&signature, &m, &pk, &pub_seed, &adrs));
+    }
+
+    #[test]
+    fn rejects_other_message_or_address() {
+        let sk = gen_sk(&[1u8; 32]);
+        let pub_seed = [2u8; 32];
+        let adrs = Address::new();
+
+        let m = sha256(b"hello");
+        let pk = gen_pk(&sk, &pub_seed, &adrs);
+        let signature = sign(&m, &sk, &pub_seed, &adrs);
+
+        assert!(!verify(
+            &signature,
+            &sha256(b"world"),
+            &pk,
+            &pub_seed,
+            &adrs
+        ));
+
+        let mut other = adrs;
+        other.set_ots(1);
+        assert!(!verify(&signature, &m, &pk, &pub_seed, &other));
+    }
+}
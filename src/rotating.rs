@@ -0,0 +1,93 @@
+//! WOTS verification across rotating public keys.
+use crate::{public::PublicKey, signature::Signature};
+use alloc::collections::VecDeque;
+
+/// A verifier accepting signatures from a bounded window of recent public keys.
+///
+/// Keys are pushed as epochs rotate. Once the window is full, pushing a new
+/// key drops the oldest one.
+pub struct RotatingVerifier {
+    keys: VecDeque<PublicKey>,
+    capacity: usize,
+}
+
+impl RotatingVerifier {
+    /// Create a verifier accepting the public keys of the last `capacity` epochs.
+    pub fn new(capacity: usize) -> Self {
+        RotatingVerifier {
+            keys: VecDeque::with_capacity(capacity),
+            capacity,
+        }
+    }
+
+    /// Push the public key of a new epoch, dropping the oldest key if the
+    /// window is full.
+    pub fn push_key(&mut self, public_key: PublicKey) {
+        if self.capacity == 0 {
+            return;
+        }
+
+        if self.keys.len() == self.capacity {
+            self.keys.pop_back();
+        }
+
+        self.keys.push_front(public_key);
+    }
+
+    /// Verify a `signature` on a `message` against the keys in the window.
+    ///
+    /// # Returns
+    ///
+    /// The epoch offset of the matching key, where `0` is the most recently
+    /// pushed key, or `None` if no key in the window accepts the `signature`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use rand::rngs::OsRng;
+    /// use wots_rs::{Keypair, RotatingVerifier};
+    ///
+    /// let mut csprng = OsRng{};
+    /// let keypair: Keypair = Keypair::generate(&mut csprng);
+    ///
+    /// let message = b"hello";
+    /// let signature = keypair.sign_unchecked(message);
+    ///
+    /// let mut verifier = RotatingVerifier::new(2);
+    /// verifier.push_key(keypair.public);
+    ///
+    /// assert_eq!(verifier.verify(message, &signature), Some(0));
+    ///
+    /// ```
+    pub fn verify(&self, message: &[u8], signature: &Signature) -> Option<usize> {
+        self.keys
+            .iter()
+            .position(|public_key| signature.matches(message, public_key))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Keypair;
+
+    #[test]
+    fn verifies_older_epoch() {
+        let message = b"hello";
+
+        let old = Keypair::from_seed([1u8; 32]);
+        let new = Keypair::from_seed([2u8; 32]);
+        let signature = old.sign_unchecked(message);
+        let current = new.sign_unchecked(message);
+
+        let mut verifier = RotatingVerifier::new(2);
+        verifier.push_key(old.public);
+        verifier.push_key(new.public);
+
+        assert_eq!(verifier.verify(message, &signature), Some(1));
+        assert_eq!(verifier.verify(message, &current), Some(0));
+
+        verifier.push_key(Keypair::from_seed([3u8; 32]).public);
+        assert_eq!(verifier.verify(message, &signature), None);
+    }
+}
@@ -1,11 +1,35 @@
 //! WOTS secret keys.
-use crate::signature::Signature;
+#[cfg(feature = "std")]
+use crate::hash::sha256_reader;
+use crate::{
+    checksum::with_checksum,
+    constants::{steps, CHAINS, CHAIN_LENGTH, MESSAGE_CHAINS, SIGNATURE_LENGTH},
+    context::context_digest,
+    digest32::Seed,
+    encoding::Encoding,
+    error::WotsError,
+    hash::{hmac_sha256, iterate_chains, iterate_in_place, sha256},
+    signature::{chains_from_bytes, Signature},
+};
+use alloc::{string::String, vec, vec::Vec};
+use core::fmt;
 use rand::{CryptoRng, RngCore};
-use sha256_rs::*;
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+use subtle::{Choice, ConstantTimeEq};
+#[cfg(feature = "zeroize")]
+use zeroize::{Zeroize, ZeroizeOnDrop};
 
 /// An WOTS secret key.
-#[derive(Eq, PartialEq)]
-pub struct SecretKey([[u8; 32]; 32]);
+///
+/// With the `zeroize` feature enabled the chains are wiped when the key is
+/// dropped. Secret keys compare in constant time, with `==` as well as
+/// [`ConstantTimeEq::ct_eq`].
+#[derive(Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct SecretKey<const N: usize = 32, const L: usize = CHAINS>(
+    #[cfg_attr(feature = "serde", serde(with = "crate::serialization"))] [[u8; N]; L],
+);
 
 impl SecretKey {
     /// Generate a `SecretKey` from a `csprng`.
@@ -28,17 +52,256 @@ impl SecretKey {
     where
         R: CryptoRng + RngCore,
     {
-        let mut secret_key = [[0u8; 32]; 32];
+        let mut secret_key = [[0u8; 32]; CHAINS];
 
         for key in secret_key.iter_mut() {
             let mut temp = [0u8; 32];
             csprng.fill_bytes(&mut temp);
             *key = temp;
+            wipe(&mut temp);
         }
 
         SecretKey(secret_key)
     }
 
+    /// Generate a `SecretKey` from a `csprng`, checking the drawn entropy.
+    ///
+    /// Unlike [`generate`](Self::generate), which trusts the `csprng`, this
+    /// reports a failing `try_fill_bytes()` and rejects draws that
+    /// [look weak](Self::looks_weak), such as an all-zero chain.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use rand::rngs::OsRng;
+    /// use wots_rs::SecretKey;
+    ///
+    /// let mut csprng = OsRng{};
+    /// let secret_key: SecretKey = SecretKey::try_generate(&mut csprng).unwrap();
+    ///
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// Returns `WotsError::RngFailure` if the `csprng` fails or its output
+    /// looks weak.
+    pub fn try_generate<R>(csprng: &mut R) -> Result<Self, WotsError>
+    where
+        R: CryptoRng + RngCore,
+    {
+        // Filled in place, so a partial key is wiped on drop like a
+        // complete one if the `csprng` fails.
+        let mut secret_key = SecretKey([[0u8; 32]; CHAINS]);
+
+        for key in secret_key.0.iter_mut() {
+            csprng
+                .try_fill_bytes(key)
+                .map_err(|_| WotsError::RngFailure)?;
+        }
+
+        if secret_key.looks_weak() {
+            return Err(WotsError::RngFailure);
+        }
+
+        Ok(secret_key)
+    }
+
+    /// Check this secret key for signs of a broken random number generator.
+    ///
+    /// A key looks weak if any chain is all zeros or two chains are equal,
+    /// which is vanishingly unlikely for properly random keys.
+    pub fn looks_weak(&self) -> bool {
+        self.0.iter().enumerate().any(|(i, chain)| {
+            chain.iter().all(|&byte| byte == 0) || self.0[i + 1..].contains(chain)
+        })
+    }
+
+    /// Generate a `SecretKey` from a `csprng`, domain-separated by a `device_id`.
+    ///
+    /// Chain `i` of the secret key is `sha256(r_i || device_id)`, where `r_i`
+    /// is 32 bytes drawn from the `csprng`. Keys for different devices stay
+    /// distinct even if their random number generators produce the same
+    /// output.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use rand::rngs::OsRng;
+    /// use wots_rs::SecretKey;
+    ///
+    /// let mut csprng = OsRng{};
+    /// let secret_key: SecretKey = SecretKey::generate_for_device(&mut csprng, b"device-1");
+    ///
+    /// ```
+    pub fn generate_for_device<R>(csprng: &mut R, device_id: &[u8]) -> Self
+    where
+        R: CryptoRng + RngCore,
+    {
+        let mut secret_key = [[0u8; 32]; CHAINS];
+        let mut input = vec![0u8; 32 + device_id.len()];
+        input[32..].copy_from_slice(device_id);
+
+        for key in secret_key.iter_mut() {
+            csprng.fill_bytes(&mut input[..32]);
+            *key = sha256(&input);
+        }
+
+        wipe(&mut input[..32]);
+
+        SecretKey(secret_key)
+    }
+
+    /// Derive a `SecretKey` deterministically from a 32-byte `seed`.
+    ///
+    /// Chain `i` of the secret key is `sha256(seed || i)`, where `i` is
+    /// encoded as a big-endian `u32`, so the result is identical on every
+    /// platform.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use wots_rs::SecretKey;
+    ///
+    /// let seed = [7u8; 32];
+    /// let secret_key: SecretKey = SecretKey::from_seed(&seed);
+    ///
+    /// assert!(secret_key == SecretKey::from_seed(&seed));
+    ///
+    /// ```
+    pub fn from_seed(seed: impl Into<Seed>) -> Self {
+        let mut secret_key = [[0u8; 32]; CHAINS];
+        let mut input = [0u8; 36];
+        input[..32].copy_from_slice(seed.into().as_bytes());
+
+        for (i, key) in secret_key.iter_mut().enumerate() {
+            input[32..].copy_from_slice(&(i as u32).to_be_bytes());
+            *key = sha256(&input);
+        }
+
+        wipe(&mut input);
+
+        SecretKey(secret_key)
+    }
+
+    /// Derive the `SecretKey` at `index` from a 32-byte `master_seed`.
+    ///
+    /// The seed of the key is
+    /// `HMAC-SHA256(master_seed, "wots-rs derive" || index)`, with `index`
+    /// encoded as a big-endian `u64`, and the key is expanded from that seed
+    /// with [`from_seed`](Self::from_seed). The HMAC is a PRF keyed by the
+    /// master seed, so a derived key reveals neither the master seed nor its
+    /// siblings, and the label keeps derived seeds apart from other uses of
+    /// the master seed.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use wots_rs::SecretKey;
+    ///
+    /// let master_seed = [7u8; 32];
+    ///
+    /// assert!(SecretKey::derive(&master_seed, 3) == SecretKey::derive(&master_seed, 3));
+    /// assert!(SecretKey::derive(&master_seed, 3) != SecretKey::derive(&master_seed, 4));
+    ///
+    /// ```
+    pub fn derive(master_seed: impl Into<Seed>, index: u64) -> Self {
+        let mut input = [0u8; 22];
+        input[..14].copy_from_slice(b"wots-rs derive");
+        input[14..].copy_from_slice(&index.to_be_bytes());
+
+        let mut seed = hmac_sha256(master_seed.into().as_bytes(), &input);
+        let secret_key = SecretKey::from_seed(seed);
+        wipe(&mut seed);
+
+        secret_key
+    }
+
+    /// Derive a `SecretKey` from caller-supplied `entropy`, without an RNG.
+    ///
+    /// The entropy is condensed into a seed with
+    /// `HMAC-SHA256("wots-rs entropy", entropy)` and the key is expanded
+    /// from that seed with [`from_seed`](Self::from_seed), so entropy from
+    /// any source, e.g. a `rand` of another major version, can be used.
+    ///
+    /// # Errors
+    ///
+    /// Returns `WotsError::LengthMismatch` if `entropy` is shorter than 32
+    /// bytes.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use wots_rs::SecretKey;
+    ///
+    /// let entropy = [7u8; 64];
+    ///
+    /// assert!(SecretKey::from_entropy(&entropy).unwrap() == SecretKey::from_entropy(&entropy).unwrap());
+    /// assert!(SecretKey::from_entropy(&entropy[..31]).is_err());
+    ///
+    /// ```
+    pub fn from_entropy(entropy: &[u8]) -> Result<Self, WotsError> {
+        if entropy.len() < 32 {
+            return Err(WotsError::LengthMismatch {
+                expected: 32,
+                actual: entropy.len(),
+            });
+        }
+
+        let mut seed = hmac_sha256(b"wots-rs entropy", entropy);
+        let secret_key = SecretKey::from_seed(seed);
+        wipe(&mut seed);
+
+        Ok(secret_key)
+    }
+
+    /// Derive the next one-time `SecretKey` from this one, consuming it.
+    ///
+    /// Chain `i` of the next key is `sha256(chain_i || "rotate")`. The
+    /// step is one-way, so compromising the current key doesn't reveal the
+    /// keys it was rotated from, which makes the sequence forward-secure.
+    /// The 38-byte input keeps rotation apart from the 32-byte hash steps of
+    /// the signing chains, so no signature reveals the next key. The
+    /// consumed key is wiped.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use wots_rs::{PublicKey, SecretKey};
+    ///
+    /// let secret_key = SecretKey::from_seed(&[7u8; 32]);
+    /// let first = PublicKey::from(&secret_key);
+    ///
+    /// let secret_key = secret_key.rotate();
+    /// assert!(PublicKey::from(&secret_key) != first);
+    ///
+    /// ```
+    pub fn rotate(mut self) -> SecretKey {
+        self.rotate_in_place()
+    }
+
+    /// Overwrite the chains of this key with zeros, with or without the
+    /// `zeroize` feature.
+    pub(crate) fn wipe(&mut self) {
+        wipe(self.0.as_flattened_mut());
+    }
+
+    /// Derive the next secret key and wipe this one.
+    fn rotate_in_place(&mut self) -> SecretKey {
+        let mut next = [[0u8; 32]; CHAINS];
+        let mut input = [0u8; 38];
+        input[32..].copy_from_slice(b"rotate");
+
+        for (key, chain) in next.iter_mut().zip(self.0.iter()) {
+            input[..32].copy_from_slice(chain);
+            *key = sha256(&input);
+        }
+
+        wipe(input.as_mut_slice());
+        wipe(self.0.as_flattened_mut());
+
+        SecretKey(next)
+    }
+
     // Sign a `message` with this `SecretKey` using the
     /// WOTS algorithm.
     ///
@@ -64,34 +327,354 @@ impl SecretKey {
     ///
     /// ```
     pub fn sign(&self, message: &[u8]) -> Signature {
-        let secret_key = self.0;
+        self.sign_digits(&sha256(message))
+    }
 
-        let mut signature = [[0u8; 32]; 32];
-        let hash = sha256(message);
+    /// Sign a message read from `reader`, hashing it incrementally instead
+    /// of buffering it.
+    ///
+    /// The signature is identical to signing the whole message with
+    /// [`sign`](Self::sign).
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use wots_rs::SecretKey;
+    ///
+    /// let secret_key = SecretKey::from_seed(&[7u8; 32]);
+    ///
+    /// let file: &[u8] = b"hello";
+    /// let signature = secret_key.sign_stream(file).unwrap();
+    ///
+    /// assert!(signature == secret_key.sign(b"hello"));
+    ///
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// Returns any error from reading `reader`.
+    #[cfg(feature = "std")]
+    pub fn sign_stream(&self, reader: impl std::io::Read) -> std::io::Result<Signature> {
+        Ok(self.sign_digits(&sha256_reader(reader)?))
+    }
 
-        for (i, s) in signature.iter_mut().enumerate() {
-            let mut key = secret_key[i];
-            let n = hash[i];
+    /// Sign a `message` bound to an application `context`.
+    ///
+    /// The context is hashed in front of the message with its length and a
+    /// domain tag, so a signature made under one context doesn't verify
+    /// under another or as a plain signature. See
+    /// [`Keypair::sign_with_context`](crate::Keypair::sign_with_context).
+    pub fn sign_with_context(&self, message: &[u8], context: &[u8]) -> Signature {
+        self.sign_digits(&context_digest(message, context))
+    }
 
-            for _ in 0..256 - n as usize {
-                key = sha256(&key);
-            }
+    /// Sign a message given by its SHA-256 `digest`, skipping the internal
+    /// message hash.
+    ///
+    /// The signature is the same as [`sign`](Self::sign) on a message
+    /// whose SHA-256 digest is `digest`. The caller is responsible for the
+    /// digest: it must come from a collision-resistant 32-byte hash of the
+    /// whole message, because anyone who finds a second message with the
+    /// same digest can reuse the signature.
+    pub fn sign_prehashed(&self, digest: &[u8; 32]) -> Signature {
+        self.sign_digits(digest)
+    }
 
-            *s = key;
-        }
+    /// Sign an explicit array of base-`w` `digits`, one per message chain,
+    /// bypassing the message hash.
+    ///
+    /// With `w = 256` every byte is a valid digit. The checksum chains are
+    /// signed as usual.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use rand::rngs::OsRng;
+    /// use wots_rs::{PublicKey, SecretKey};
+    ///
+    /// let mut csprng = OsRng{};
+    /// let secret_key: SecretKey = SecretKey::generate(&mut csprng);
+    /// let public_key: PublicKey = PublicKey::from(&secret_key);
+    ///
+    /// let digits = [7u8; 32];
+    /// let signature = secret_key.sign_digits(&digits);
+    ///
+    /// assert!(public_key.verify_digits(&digits, &signature));
+    ///
+    /// ```
+    pub fn sign_digits(&self, digits: &[u8; MESSAGE_CHAINS]) -> Signature {
+        let digits = with_checksum(digits);
+        let mut signature = [[0u8; 32]; CHAINS];
+
+        iterate_chains(
+            &mut signature,
+            |i| self.0[i],
+            |i| CHAIN_LENGTH - steps(digits[i]),
+        );
 
         Signature::from(signature)
     }
 
+    /// Sign a `message` with this `SecretKey`, writing the signature
+    /// directly into `out` in its flat byte representation.
+    ///
+    /// The contents of `out` are identical to
+    /// `self.sign(message).to_flat_bytes()`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use rand::rngs::OsRng;
+    /// use wots_rs::{SecretKey, SIGNATURE_LENGTH};
+    ///
+    /// let mut csprng = OsRng{};
+    /// let secret_key: SecretKey = SecretKey::generate(&mut csprng);
+    ///
+    /// let message = b"hello";
+    /// let mut signature = [0u8; SIGNATURE_LENGTH];
+    /// secret_key.sign_into(message, &mut signature);
+    ///
+    /// ```
+    pub fn sign_into(&self, message: &[u8], out: &mut [u8; SIGNATURE_LENGTH]) {
+        let digits = with_checksum(&sha256(message));
+
+        for (i, s) in out.chunks_exact_mut(32).enumerate() {
+            self.sign_chain(i, digits[i], s.try_into().unwrap());
+        }
+    }
+
+    /// Hash chain `i` of this secret key for the digit `n` into `out`.
+    ///
+    /// The chain is advanced in `out` itself, so no copy of the secret
+    /// value is left behind on the stack.
+    fn sign_chain(&self, i: usize, n: u8, out: &mut [u8; 32]) {
+        *out = self.0[i];
+        iterate_in_place(out, CHAIN_LENGTH - steps(n));
+    }
+
     /// Convert this secret key to a byte array.
-    pub fn to_bytes(&self) -> [[u8; 32]; 32] {
+    pub fn to_bytes(&self) -> [[u8; 32]; CHAINS] {
         self.0
     }
+
+    /// Convert this secret key to a flat byte array, chain by chain.
+    pub fn to_flat_bytes(&self) -> [u8; SIGNATURE_LENGTH] {
+        let mut bytes = [0u8; SIGNATURE_LENGTH];
+        bytes.copy_from_slice(self.0.as_flattened());
+
+        bytes
+    }
+
+    /// View this secret key as a flat byte slice, chain by chain.
+    pub fn as_bytes(&self) -> &[u8] {
+        self.0.as_flattened()
+    }
+
+    /// Construct a `SecretKey` from a flat byte slice, chain by chain.
+    ///
+    /// # Returns
+    ///
+    /// Returns `WotsError::LengthMismatch` if `bytes` isn't exactly
+    /// [`SIGNATURE_LENGTH`](crate::SIGNATURE_LENGTH) bytes long.
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self, WotsError> {
+        chains_from_bytes(bytes).map(SecretKey)
+    }
+
+    /// Encode this secret key as lowercase hex of its flat bytes.
+    pub fn to_hex(&self) -> String {
+        Encoding::Hex.encode(self.0.as_flattened())
+    }
+
+    /// Decode a `SecretKey` from the hex of its flat bytes.
+    ///
+    /// # Returns
+    ///
+    /// Returns `WotsError::InvalidEncoding` if `hex` contains a non-hex
+    /// character or has an odd length, or `WotsError::LengthMismatch` if it
+    /// doesn't decode to exactly [`SIGNATURE_LENGTH`](crate::SIGNATURE_LENGTH)
+    /// bytes.
+    pub fn from_hex(hex: &str) -> Result<Self, WotsError> {
+        SecretKey::from_bytes(&Encoding::Hex.decode(hex)?)
+    }
+
+    /// Encode this secret key as base64 of its flat bytes.
+    #[cfg(feature = "base64")]
+    pub fn to_base64(&self) -> String {
+        Encoding::Base64.encode(self.0.as_flattened())
+    }
+
+    /// Decode a `SecretKey` from the base64 of its flat bytes.
+    ///
+    /// # Returns
+    ///
+    /// Returns `WotsError::InvalidEncoding` if `base64` is not valid padded
+    /// base64, or `WotsError::LengthMismatch` if it doesn't decode to
+    /// exactly [`SIGNATURE_LENGTH`](crate::SIGNATURE_LENGTH) bytes.
+    #[cfg(feature = "base64")]
+    pub fn from_base64(base64: &str) -> Result<Self, WotsError> {
+        SecretKey::from_bytes(&Encoding::Base64.decode(base64)?)
+    }
+}
+
+/// Construct a `SecretKey` from a flat byte slice, checking its length.
+impl TryFrom<&[u8]> for SecretKey {
+    type Error = WotsError;
+
+    fn try_from(value: &[u8]) -> Result<Self, Self::Error> {
+        SecretKey::from_bytes(value)
+    }
 }
 
 /// Construct a `SecretKey` from a bytes.
-impl From<[[u8; 32]; 32]> for SecretKey {
-    fn from(value: [[u8; 32]; 32]) -> Self {
+impl<const N: usize, const L: usize> From<[[u8; N]; L]> for SecretKey<N, L> {
+    fn from(value: [[u8; N]; L]) -> Self {
         Self(value)
     }
 }
+
+/// Format a `SecretKey` without revealing its contents.
+impl<const N: usize, const L: usize> fmt::Debug for SecretKey<N, L> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("SecretKey(***)")
+    }
+}
+
+/// Compare two secret keys in constant time.
+impl<const N: usize, const L: usize> ConstantTimeEq for SecretKey<N, L> {
+    fn ct_eq(&self, other: &Self) -> Choice {
+        self.0.as_flattened().ct_eq(other.0.as_flattened())
+    }
+}
+
+/// Compare two secret keys in constant time.
+impl<const N: usize, const L: usize> PartialEq for SecretKey<N, L> {
+    fn eq(&self, other: &Self) -> bool {
+        self.ct_eq(other).into()
+    }
+}
+
+impl<const N: usize, const L: usize> Eq for SecretKey<N, L> {}
+
+/// Overwrite the chains of a `SecretKey` with zeros.
+#[cfg(feature = "zeroize")]
+impl<const N: usize, const L: usize> Zeroize for SecretKey<N, L> {
+    fn zeroize(&mut self) {
+        self.0.zeroize();
+    }
+}
+
+/// Wipe a `SecretKey` when it goes out of scope.
+#[cfg(feature = "zeroize")]
+impl<const N: usize, const L: usize> Drop for SecretKey<N, L> {
+    fn drop(&mut self) {
+        self.0.zeroize();
+    }
+}
+
+#[cfg(feature = "zeroize")]
+impl<const N: usize, const L: usize> ZeroizeOnDrop for SecretKey<N, L> {}
+
+/// View a `SecretKey` as a reference to its underlying bytes.
+impl<const N: usize, const L: usize> AsRef<[[u8; N]; L]> for SecretKey<N, L> {
+    fn as_ref(&self) -> &[[u8; N]; L] {
+        &self.0
+    }
+}
+
+/// Overwrite secret `bytes` with zeros in a way the compiler won't elide.
+pub(crate) fn wipe(bytes: &mut [u8]) {
+    for byte in bytes.iter_mut() {
+        // SAFETY: `byte` is a valid, exclusive reference to a `u8`.
+        unsafe { core::ptr::write_volatile(byte, 0) };
+    }
+
+    core::sync::atomic::compiler_fence(core::sync::atomic::Ordering::SeqCst);
+}
+
+/// Sign each of `messages` with the secret key at the same position of `keys`.
+///
+/// With the `rayon` feature enabled, the signatures are computed in parallel.
+///
+/// # Returns
+///
+/// The signatures in the order of `messages`, or `WotsError::LengthMismatch`
+/// if `keys` and `messages` have different lengths.
+///
+/// # Example
+///
+/// ```
+/// use rand::rngs::OsRng;
+/// use wots_rs::{sign_batch, SecretKey};
+///
+/// let mut csprng = OsRng{};
+/// let keys = [SecretKey::generate(&mut csprng), SecretKey::generate(&mut csprng)];
+///
+/// let signatures = sign_batch(&keys, &[b"hello", b"world"]).unwrap();
+/// assert_eq!(signatures.len(), 2);
+///
+/// ```
+pub fn sign_batch(keys: &[SecretKey], messages: &[&[u8]]) -> Result<Vec<Signature>, WotsError> {
+    if keys.len() != messages.len() {
+        return Err(WotsError::LengthMismatch {
+            expected: keys.len(),
+            actual: messages.len(),
+        });
+    }
+
+    #[cfg(feature = "rayon")]
+    {
+        use rayon::prelude::*;
+
+        Ok(keys
+            .par_iter()
+            .zip(messages.par_iter())
+            .map(|(key, message)| key.sign(message))
+            .collect())
+    }
+
+    #[cfg(not(feature = "rayon"))]
+    Ok(keys
+        .iter()
+        .zip(messages.iter())
+        .map(|(key, message)| key.sign(message))
+        .collect())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::PublicKey;
+
+    #[test]
+    fn rotate_derives_valid_key_and_wipes_old() {
+        let message = b"hello";
+
+        let mut secret_key = SecretKey::from_seed([42u8; 32]);
+        let first = PublicKey::from(&secret_key);
+
+        let next = secret_key.rotate_in_place();
+        assert!(secret_key.to_bytes() == [[0u8; 32]; CHAINS]);
+
+        let public_key = PublicKey::from(&next);
+        assert!(public_key != first);
+        assert!(public_key.verify(message, next.sign(message)));
+
+        assert!(SecretKey::from_seed([42u8; 32]).rotate() == next);
+    }
+
+    #[cfg(feature = "zeroize")]
+    #[test]
+    fn zeroize_clears_secret_chains() {
+        use crate::Keypair;
+
+        let mut secret_key = SecretKey::from_seed([42u8; 32]);
+        secret_key.zeroize();
+        assert!(secret_key.to_bytes() == [[0u8; 32]; CHAINS]);
+
+        let mut keypair = Keypair::from_seed([42u8; 32]);
+        let public = keypair.public;
+        keypair.zeroize();
+        assert!(keypair.secret.to_bytes() == [[0u8; 32]; CHAINS]);
+        assert!(keypair.public == public);
+    }
+}
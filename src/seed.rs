@@ -0,0 +1,157 @@
+//! WOTS secret keys stored as their 32-byte seed.
+use crate::{digest32::Seed, public::PublicKey, secret::SecretKey, signature::Signature};
+use core::fmt;
+use rand::{CryptoRng, RngCore};
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+use subtle::{Choice, ConstantTimeEq};
+#[cfg(feature = "zeroize")]
+use zeroize::{Zeroize, ZeroizeOnDrop};
+
+/// An WOTS secret key held as the 32-byte seed it is derived from.
+///
+/// A [`SecretKey`] stores all 1088 bytes of its chain starts. A
+/// `SeedSecretKey` stores only the seed and derives the chains with
+/// [`SecretKey::from_seed`] whenever it signs, so it is the same key in
+/// 32 bytes. The seed can be exported with [`seed`](Self::seed) for backup.
+///
+/// Like [`SecretKey`], it compares in constant time and is wiped on drop
+/// with the `zeroize` feature enabled.
+#[derive(Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct SeedSecretKey([u8; 32]);
+
+impl SeedSecretKey {
+    /// Generate a `SeedSecretKey` from a `csprng`.
+    pub fn generate<R>(csprng: &mut R) -> Self
+    where
+        R: CryptoRng + RngCore,
+    {
+        let mut seed = [0u8; 32];
+        csprng.fill_bytes(&mut seed);
+
+        SeedSecretKey(seed)
+    }
+
+    /// Wrap a 32-byte `seed`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use wots_rs::{SecretKey, SeedSecretKey};
+    ///
+    /// let seed = [7u8; 32];
+    /// let secret_key = SeedSecretKey::from_seed(seed);
+    ///
+    /// assert!(secret_key.expand() == SecretKey::from_seed(&seed));
+    /// assert!(secret_key.sign(b"hello") == SecretKey::from_seed(&seed).sign(b"hello"));
+    ///
+    /// ```
+    pub fn from_seed(seed: impl Into<Seed>) -> Self {
+        SeedSecretKey(seed.into().into())
+    }
+
+    /// The seed of this key, for backup.
+    pub fn seed(&self) -> &[u8; 32] {
+        &self.0
+    }
+
+    /// Derive the full `SecretKey` from the seed.
+    pub fn expand(&self) -> SecretKey {
+        SecretKey::from_seed(self.0)
+    }
+
+    /// The public key of this key.
+    pub fn public_key(&self) -> PublicKey {
+        PublicKey::from(&self.expand())
+    }
+
+    /// Sign a `message`, deriving the chains for the duration of the call.
+    pub fn sign(&self, message: &[u8]) -> Signature {
+        self.expand().sign(message)
+    }
+}
+
+/// Wrap a 32-byte seed.
+impl From<[u8; 32]> for SeedSecretKey {
+    fn from(value: [u8; 32]) -> Self {
+        SeedSecretKey(value)
+    }
+}
+
+/// Derive the full `SecretKey` of a seed.
+impl From<&SeedSecretKey> for SecretKey {
+    fn from(value: &SeedSecretKey) -> Self {
+        value.expand()
+    }
+}
+
+/// Format a `SeedSecretKey` without revealing its seed.
+impl fmt::Debug for SeedSecretKey {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("SeedSecretKey(***)")
+    }
+}
+
+/// Compare two seeds in constant time.
+impl ConstantTimeEq for SeedSecretKey {
+    fn ct_eq(&self, other: &Self) -> Choice {
+        self.0.ct_eq(&other.0)
+    }
+}
+
+/// Compare two seeds in constant time.
+impl PartialEq for SeedSecretKey {
+    fn eq(&self, other: &Self) -> bool {
+        self.ct_eq(other).into()
+    }
+}
+
+impl Eq for SeedSecretKey {}
+
+/// Overwrite the seed of a `SeedSecretKey` with zeros.
+#[cfg(feature = "zeroize")]
+impl Zeroize for SeedSecretKey {
+    fn zeroize(&mut self) {
+        self.0.zeroize();
+    }
+}
+
+/// Wipe a `SeedSecretKey` when it goes out of scope.
+#[cfg(feature = "zeroize")]
+impl Drop for SeedSecretKey {
+    fn drop(&mut self) {
+        self.zeroize();
+    }
+}
+
+#[cfg(feature = "zeroize")]
+impl ZeroizeOnDrop for SeedSecretKey {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::keypair::Keypair;
+
+    #[test]
+    fn matches_keypair_from_seed() {
+        let keypair = Keypair::from_seed([7u8; 32]);
+        let secret_key = SeedSecretKey::from([7u8; 32]);
+
+        assert!(secret_key.public_key() == keypair.public);
+
+        let signature = secret_key.sign(b"hello");
+        assert!(signature == keypair.sign_unchecked(b"hello"));
+        assert!(keypair.verify(b"hello", signature));
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn serializes_as_seed() {
+        let secret_key = SeedSecretKey::from_seed([7u8; 32]);
+
+        let bytes = bincode::serialize(&secret_key).unwrap();
+        assert_eq!(bytes, [7u8; 32]);
+        assert!(bincode::deserialize::<SeedSecretKey>(&bytes).unwrap() == secret_key);
+    }
+}
@@ -0,0 +1,74 @@
+//! Serde support for WOTS chain arrays.
+//!
+//! Chains are serialized as one flat byte string rather than nested arrays,
+//! keeping the encoding compact; deserialization validates its length.
+use core::fmt;
+use serde::{
+    de::{self, SeqAccess, Visitor},
+    Deserializer, Serializer,
+};
+
+/// Serialize chain values as one flat byte string.
+pub(crate) fn serialize<S, const N: usize, const L: usize>(
+    chains: &[[u8; N]; L],
+    serializer: S,
+) -> Result<S::Ok, S::Error>
+where
+    S: Serializer,
+{
+    serializer.serialize_bytes(chains.as_flattened())
+}
+
+/// Deserialize chain values from a flat byte string.
+pub(crate) fn deserialize<'de, D, const N: usize, const L: usize>(
+    deserializer: D,
+) -> Result<[[u8; N]; L], D::Error>
+where
+    D: Deserializer<'de>,
+{
+    deserializer.deserialize_bytes(ChainsVisitor)
+}
+
+/// Visits `L` chain values of `N` bytes each.
+struct ChainsVisitor<const N: usize, const L: usize>;
+
+impl<'de, const N: usize, const L: usize> Visitor<'de> for ChainsVisitor<N, L> {
+    type Value = [[u8; N]; L];
+
+    fn expecting(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} bytes of chain values", L * N)
+    }
+
+    fn visit_bytes<E>(self, v: &[u8]) -> Result<Self::Value, E>
+    where
+        E: de::Error,
+    {
+        if v.len() != L * N {
+            return Err(E::invalid_length(v.len(), &self));
+        }
+
+        let mut chains = [[0u8; N]; L];
+        chains.as_flattened_mut().copy_from_slice(v);
+
+        Ok(chains)
+    }
+
+    fn visit_seq<A>(self, mut seq: A) -> Result<Self::Value, A::Error>
+    where
+        A: SeqAccess<'de>,
+    {
+        let mut chains = [[0u8; N]; L];
+
+        for (i, byte) in chains.as_flattened_mut().iter_mut().enumerate() {
+            *byte = seq
+                .next_element()?
+                .ok_or_else(|| de::Error::invalid_length(i, &self))?;
+        }
+
+        if seq.next_element::<u8>()?.is_some() {
+            return Err(de::Error::invalid_length(L * N + 1, &self));
+        }
+
+        Ok(chains)
+    }
+}
@@ -1,19 +1,429 @@
 //! WOTS signature.
+use crate::{
+    constants::{CHAINS, SIGNATURE_LENGTH, SIGNATURE_MAGIC},
+    encoding::Encoding,
+    error::WotsError,
+    hash::sha256,
+    hex::{self, chains_debug_string},
+    public::PublicKey,
+};
+use alloc::string::String;
+#[cfg(feature = "bitcoin-interop")]
+use alloc::vec::Vec;
+use core::{
+    cmp::Ordering,
+    fmt,
+    hash::{Hash, Hasher},
+    ops::ControlFlow,
+    str::FromStr,
+};
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+use subtle::{Choice, ConstantTimeEq};
 
 /// An WOTS signature.
-#[derive(Eq, PartialEq)]
-pub struct Signature([[u8; 32]; 32]);
+///
+/// Comparing signatures with `==` is variable-time. Use
+/// [`ConstantTimeEq::ct_eq`] when the comparison must not leak timing.
+#[derive(Clone, Copy, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct Signature<const N: usize = 32, const L: usize = CHAINS>(
+    #[cfg_attr(feature = "serde", serde(with = "crate::serialization"))] [[u8; N]; L],
+);
 
 impl Signature {
+    /// Check whether this signature on a `message` was created by the secret
+    /// half of `public_key`.
+    ///
+    /// This is equivalent to `public_key.verify(message, signature)`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use rand::rngs::OsRng;
+    /// use wots_rs::Keypair;
+    ///
+    /// let mut csprng = OsRng{};
+    /// let keypair: Keypair = Keypair::generate(&mut csprng);
+    ///
+    /// let message = b"hello";
+    /// let signature = keypair.sign_unchecked(message);
+    ///
+    /// assert!(signature.matches(message, &keypair.public));
+    ///
+    /// ```
+    pub fn matches(&self, message: &[u8], public_key: &PublicKey) -> bool {
+        public_key
+            .verify_digest(&sha256(message), self)
+            .unwrap_or(false)
+    }
+
+    /// Verify this signature on a `message` for use in short-circuiting
+    /// combinators such as `try_for_each` and `try_fold`.
+    ///
+    /// # Returns
+    ///
+    /// Returns `ControlFlow::Continue(())` if the signature is valid, or
+    /// `ControlFlow::Break(())` otherwise.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use rand::rngs::OsRng;
+    /// use wots_rs::Keypair;
+    ///
+    /// let mut csprng = OsRng{};
+    /// let keypair: Keypair = Keypair::generate(&mut csprng);
+    ///
+    /// let hello = keypair.sign_unchecked(b"hello");
+    /// let world = keypair.sign_unchecked(b"world");
+    /// let items: [(&[u8], _); 2] = [(b"hello", &hello), (b"world", &world)];
+    ///
+    /// assert!(items
+    ///     .iter()
+    ///     .try_for_each(|(message, signature)| signature.verify_control(message, &keypair.public))
+    ///     .is_continue());
+    ///
+    /// ```
+    pub fn verify_control(&self, message: &[u8], public_key: &PublicKey) -> ControlFlow<()> {
+        if self.matches(message, public_key) {
+            ControlFlow::Continue(())
+        } else {
+            ControlFlow::Break(())
+        }
+    }
+
+    /// Recover the public key this signature on a `message` verifies
+    /// against, by advancing each chain to its end.
+    ///
+    /// The same as [`PublicKey::recover`]. Every signature recovers some
+    /// public key, so the result must be compared against the expected key
+    /// or its stored commitment.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use wots_rs::Keypair;
+    ///
+    /// let keypair = Keypair::from_seed(&[7u8; 32]);
+    /// let commitment = keypair.public.commitment();
+    ///
+    /// let signature = keypair.sign_unchecked(b"hello");
+    ///
+    /// assert!(signature.recover_public_key(b"hello").commitment() == commitment);
+    /// assert!(signature.recover_public_key(b"world").commitment() != commitment);
+    ///
+    /// ```
+    pub fn recover_public_key(&self, message: &[u8]) -> PublicKey {
+        PublicKey::recover(message, self)
+    }
+
+    /// Render this signature for debugging, one `chain[i]: <hex>` line per
+    /// chain.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use wots_rs::Keypair;
+    ///
+    /// let keypair = Keypair::from_seed(&[7u8; 32]);
+    ///
+    /// println!("{}", keypair.sign_unchecked(b"hello").to_debug_string());
+    ///
+    /// ```
+    pub fn to_debug_string(&self) -> String {
+        chains_debug_string(&self.0)
+    }
+
     /// Convert this signature to a byte array.
-    pub fn to_bytes(&self) -> [[u8; 32]; 32] {
+    pub fn to_bytes(&self) -> [[u8; 32]; CHAINS] {
         self.0
     }
+
+    /// Convert this signature to a flat byte array, chain by chain.
+    pub fn to_flat_bytes(&self) -> [u8; SIGNATURE_LENGTH] {
+        let mut bytes = [0u8; SIGNATURE_LENGTH];
+
+        for (chunk, chain) in bytes.chunks_exact_mut(32).zip(self.0.iter()) {
+            chunk.copy_from_slice(chain);
+        }
+
+        bytes
+    }
+
+    /// View this signature as a flat byte slice, chain by chain.
+    pub fn as_bytes(&self) -> &[u8] {
+        self.0.as_flattened()
+    }
+
+    /// Construct a `Signature` from its flat byte representation, as
+    /// produced by [`to_flat_bytes`](Self::to_flat_bytes).
+    ///
+    /// The layout is the concatenation of the 32-byte chain values in chain
+    /// order: the [`MESSAGE_CHAINS`](crate::MESSAGE_CHAINS) chains signing digest bytes `0..32`,
+    /// followed by the [`CHECKSUM_CHAINS`](crate::CHECKSUM_CHAINS) chains signing the big-endian
+    /// checksum. Chain values are raw hash outputs, so no byte order applies
+    /// within a chain.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use wots_rs::{Keypair, Signature};
+    ///
+    /// let keypair = Keypair::from_seed(&[7u8; 32]);
+    /// let signature = keypair.sign_unchecked(b"hello");
+    ///
+    /// assert!(Signature::from_flat_bytes(&signature.to_flat_bytes()) == signature);
+    ///
+    /// ```
+    pub fn from_flat_bytes(bytes: &[u8; SIGNATURE_LENGTH]) -> Self {
+        let mut signature = [[0u8; 32]; CHAINS];
+        signature.as_flattened_mut().copy_from_slice(bytes);
+
+        Signature(signature)
+    }
+
+    /// Construct a `Signature` from a flat byte slice, chain by chain.
+    ///
+    /// # Returns
+    ///
+    /// Returns `WotsError::LengthMismatch` if `bytes` isn't exactly
+    /// [`SIGNATURE_LENGTH`] bytes long.
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self, WotsError> {
+        chains_from_bytes(bytes).map(Signature)
+    }
+
+    /// Encode this signature as lowercase hex of its flat bytes.
+    pub fn to_hex(&self) -> String {
+        Encoding::Hex.encode(self.0.as_flattened())
+    }
+
+    /// Decode a `Signature` from the hex of its flat bytes.
+    ///
+    /// # Returns
+    ///
+    /// Returns `WotsError::InvalidEncoding` if `hex` contains a non-hex
+    /// character or has an odd length, or `WotsError::LengthMismatch` if it
+    /// doesn't decode to exactly [`SIGNATURE_LENGTH`](crate::SIGNATURE_LENGTH)
+    /// bytes.
+    pub fn from_hex(hex: &str) -> Result<Self, WotsError> {
+        Signature::from_bytes(&Encoding::Hex.decode(hex)?)
+    }
+
+    /// Encode this signature as base64 of its flat bytes.
+    #[cfg(feature = "base64")]
+    pub fn to_base64(&self) -> String {
+        Encoding::Base64.encode(self.0.as_flattened())
+    }
+
+    /// Decode a `Signature` from the base64 of its flat bytes.
+    ///
+    /// # Returns
+    ///
+    /// Returns `WotsError::InvalidEncoding` if `base64` is not valid padded
+    /// base64, or `WotsError::LengthMismatch` if it doesn't decode to
+    /// exactly [`SIGNATURE_LENGTH`](crate::SIGNATURE_LENGTH) bytes.
+    #[cfg(feature = "base64")]
+    pub fn from_base64(base64: &str) -> Result<Self, WotsError> {
+        Signature::from_bytes(&Encoding::Base64.decode(base64)?)
+    }
+
+    /// Convert this signature to its flat bytes prefixed by the
+    /// [`SIGNATURE_MAGIC`] header.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use wots_rs::{Keypair, Signature};
+    ///
+    /// let keypair = Keypair::from_seed(&[7u8; 32]);
+    /// let signature = keypair.sign_unchecked(b"hello");
+    ///
+    /// let bytes = signature.to_magic_bytes();
+    /// assert_eq!(&bytes[..4], b"WOTS");
+    ///
+    /// assert!(Signature::from_magic_bytes(&bytes).unwrap() == signature);
+    ///
+    /// ```
+    pub fn to_magic_bytes(&self) -> [u8; SIGNATURE_MAGIC.len() + SIGNATURE_LENGTH] {
+        let mut bytes = [0u8; SIGNATURE_MAGIC.len() + SIGNATURE_LENGTH];
+        bytes[..SIGNATURE_MAGIC.len()].copy_from_slice(&SIGNATURE_MAGIC);
+        bytes[SIGNATURE_MAGIC.len()..].copy_from_slice(self.0.as_flattened());
+
+        bytes
+    }
+
+    /// Construct a `Signature` from flat bytes prefixed by the
+    /// [`SIGNATURE_MAGIC`] header.
+    ///
+    /// # Returns
+    ///
+    /// Returns `WotsError::BadMagic` if `buf` doesn't start with the header,
+    /// or `WotsError::LengthMismatch` if the rest isn't exactly
+    /// [`SIGNATURE_LENGTH`] bytes.
+    pub fn from_magic_bytes(buf: &[u8]) -> Result<Self, WotsError> {
+        let body = buf
+            .strip_prefix(&SIGNATURE_MAGIC)
+            .ok_or(WotsError::BadMagic)?;
+
+        Signature::try_from(body)
+    }
+
+    /// Convert this signature to script pushes, one 32-byte push per chain.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use wots_rs::{Keypair, Signature, CHAINS};
+    ///
+    /// let keypair = Keypair::from_seed(&[7u8; 32]);
+    /// let signature = keypair.sign_unchecked(b"hello");
+    ///
+    /// let pushes = signature.to_script_pushes();
+    /// assert_eq!(pushes.len(), CHAINS);
+    ///
+    /// assert!(Signature::from_script_pushes(&pushes).unwrap() == signature);
+    ///
+    /// ```
+    #[cfg(feature = "bitcoin-interop")]
+    pub fn to_script_pushes(&self) -> Vec<Vec<u8>> {
+        self.0.iter().map(|chain| chain.to_vec()).collect()
+    }
+
+    /// Construct a `Signature` from script pushes, one 32-byte push per chain.
+    ///
+    /// # Returns
+    ///
+    /// Returns `WotsError::LengthMismatch` if there are not exactly
+    /// [`CHAINS`] pushes, or if a push is not exactly 32 bytes long.
+    #[cfg(feature = "bitcoin-interop")]
+    pub fn from_script_pushes(pushes: &[Vec<u8>]) -> Result<Self, WotsError> {
+        if pushes.len() != CHAINS {
+            return Err(WotsError::LengthMismatch {
+                expected: CHAINS,
+                actual: pushes.len(),
+            });
+        }
+
+        let mut signature = [[0u8; 32]; CHAINS];
+
+        for (chain, push) in signature.iter_mut().zip(pushes) {
+            *chain = push
+                .as_slice()
+                .try_into()
+                .map_err(|_| WotsError::LengthMismatch {
+                    expected: 32,
+                    actual: push.len(),
+                })?;
+        }
+
+        Ok(Signature(signature))
+    }
 }
 
 /// Construct a `Signature` from a bytes.
-impl From<[[u8; 32]; 32]> for Signature {
-    fn from(value: [[u8; 32]; 32]) -> Self {
+impl<const N: usize, const L: usize> From<[[u8; N]; L]> for Signature<N, L> {
+    fn from(value: [[u8; N]; L]) -> Self {
         Self(value)
     }
 }
+
+/// Construct a `Signature` from a flat byte slice, checking its length.
+impl TryFrom<&[u8]> for Signature {
+    type Error = WotsError;
+
+    fn try_from(value: &[u8]) -> Result<Self, Self::Error> {
+        Signature::from_bytes(value)
+    }
+}
+
+/// Convert a `Signature` into its flat byte representation.
+impl From<Signature> for [u8; SIGNATURE_LENGTH] {
+    fn from(value: Signature) -> Self {
+        value.to_flat_bytes()
+    }
+}
+
+/// Split a flat byte slice into chain values, checking its length.
+pub(crate) fn chains_from_bytes(bytes: &[u8]) -> Result<[[u8; 32]; CHAINS], WotsError> {
+    if bytes.len() != SIGNATURE_LENGTH {
+        return Err(WotsError::LengthMismatch {
+            expected: SIGNATURE_LENGTH,
+            actual: bytes.len(),
+        });
+    }
+
+    let mut chains = [[0u8; 32]; CHAINS];
+    chains.as_flattened_mut().copy_from_slice(bytes);
+
+    Ok(chains)
+}
+
+/// Hash a `Signature` by its flat bytes, consistently with its `Eq`.
+impl<const N: usize, const L: usize> Hash for Signature<N, L> {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.0.as_flattened().hash(state);
+    }
+}
+
+/// Order signatures lexicographically by their flat bytes, consistently with
+/// their `Eq`.
+impl<const N: usize, const L: usize> Ord for Signature<N, L> {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.0.as_flattened().cmp(other.0.as_flattened())
+    }
+}
+
+impl<const N: usize, const L: usize> PartialOrd for Signature<N, L> {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+/// Compare two signatures in constant time.
+impl<const N: usize, const L: usize> ConstantTimeEq for Signature<N, L> {
+    fn ct_eq(&self, other: &Self) -> Choice {
+        self.0.as_flattened().ct_eq(other.0.as_flattened())
+    }
+}
+
+/// Format a `Signature` as the hex of its first bytes.
+impl<const N: usize, const L: usize> fmt::Debug for Signature<N, L> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "Signature({}...)", hex::encode(&self.0[0][..8]))
+    }
+}
+
+/// Format a `Signature` as lowercase hex of its flat bytes, like [`Signature::to_hex`].
+impl<const N: usize, const L: usize> fmt::Display for Signature<N, L> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::LowerHex::fmt(self, f)
+    }
+}
+
+/// Format a `Signature` as lowercase hex of its flat bytes.
+impl<const N: usize, const L: usize> fmt::LowerHex for Signature<N, L> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for byte in self.0.as_flattened() {
+            write!(f, "{:02x}", byte)?;
+        }
+
+        Ok(())
+    }
+}
+
+/// Parse a `Signature` from the hex of its flat bytes, like [`Signature::from_hex`].
+impl FromStr for Signature {
+    type Err = WotsError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Signature::from_hex(s)
+    }
+}
+
+/// View a `Signature` as a reference to its underlying bytes.
+impl<const N: usize, const L: usize> AsRef<[[u8; N]; L]> for Signature<N, L> {
+    fn as_ref(&self) -> &[[u8; N]; L] {
+        &self.0
+    }
+}
@@ -0,0 +1,123 @@
+//! High-level signing and verification of text-encoded signatures.
+use crate::{
+    encoding::Encoding, error::WotsError, keypair::Keypair, public::PublicKey, signature::Signature,
+};
+use alloc::string::String;
+
+/// Signs messages with a keypair and encodes the signatures as text.
+///
+/// Every signature reveals part of the secret key, so a `Signer` must still
+/// only be used for a single message.
+pub struct Signer {
+    keypair: Keypair,
+    encoding: Encoding,
+}
+
+impl Signer {
+    /// Construct a `Signer` from a `keypair` and the `encoding` of the
+    /// signatures it produces.
+    pub fn new(keypair: Keypair, encoding: Encoding) -> Self {
+        Signer { keypair, encoding }
+    }
+
+    /// The keypair signing messages.
+    pub fn keypair(&self) -> &Keypair {
+        &self.keypair
+    }
+
+    /// A `Verifier` for the signatures of this `Signer`.
+    pub fn verifier(&self) -> Verifier {
+        Verifier::new(
+            PublicKey::from(self.keypair.public.to_bytes()),
+            self.encoding,
+        )
+    }
+
+    /// Sign a `message` and encode the signature.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use rand::rngs::OsRng;
+    /// use wots_rs::{Encoding, Keypair, Signer};
+    ///
+    /// let mut csprng = OsRng{};
+    /// let signer = Signer::new(Keypair::generate(&mut csprng), Encoding::Base64);
+    /// let verifier = signer.verifier();
+    ///
+    /// let signature = signer.sign_encoded(b"hello");
+    ///
+    /// assert_eq!(verifier.verify_encoded(b"hello", &signature), Ok(true));
+    ///
+    /// ```
+    pub fn sign_encoded(&self, message: &[u8]) -> String {
+        self.encoding
+            .encode(&self.keypair.sign_unchecked(message).to_flat_bytes())
+    }
+}
+
+/// Decodes text-encoded signatures and verifies them with a public key.
+pub struct Verifier {
+    public_key: PublicKey,
+    encoding: Encoding,
+}
+
+impl Verifier {
+    /// Construct a `Verifier` from a `public_key` and the `encoding` of the
+    /// signatures it accepts.
+    pub fn new(public_key: PublicKey, encoding: Encoding) -> Self {
+        Verifier {
+            public_key,
+            encoding,
+        }
+    }
+
+    /// The public key verifying signatures.
+    pub fn public_key(&self) -> &PublicKey {
+        &self.public_key
+    }
+
+    /// Decode an encoded `signature` and verify it on a `message`.
+    ///
+    /// # Returns
+    ///
+    /// Returns whether the signature is valid, `WotsError::InvalidEncoding`
+    /// if it can't be decoded, or `WotsError::LengthMismatch` if it doesn't
+    /// decode to exactly [`SIGNATURE_LENGTH`](crate::SIGNATURE_LENGTH) bytes.
+    pub fn verify_encoded(&self, message: &[u8], signature: &str) -> Result<bool, WotsError> {
+        let bytes = self.encoding.decode(signature)?;
+        let signature = Signature::try_from(bytes.as_slice())?;
+
+        Ok(self.public_key.verify(message, signature))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn encoded_round_trip() {
+        for encoding in [Encoding::Hex, Encoding::Base64] {
+            let signer = Signer::new(Keypair::from_seed([7u8; 32]), encoding);
+            let verifier = signer.verifier();
+
+            let signature = signer.sign_encoded(b"hello");
+            assert_eq!(encoding.decode(&signature).unwrap().len(), 1088);
+
+            assert_eq!(verifier.verify_encoded(b"hello", &signature), Ok(true));
+            assert_eq!(verifier.verify_encoded(b"world", &signature), Ok(false));
+            assert_eq!(
+                verifier.verify_encoded(b"hello", "not encoded"),
+                Err(WotsError::InvalidEncoding)
+            );
+            assert_eq!(
+                verifier.verify_encoded(b"hello", &encoding.encode(&[0u8; 32])),
+                Err(WotsError::LengthMismatch {
+                    expected: 1088,
+                    actual: 32
+                })
+            );
+        }
+    }
+}
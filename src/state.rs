@@ -0,0 +1,254 @@
+//! Persistent records of spent one-time key indices.
+//!
+//! A stateful signer must never hand out the same one-time key twice, even
+//! across a crash. A [`StateStore`] reserves each index durably before the
+//! signature is made, so the worst a crash can do is skip an index.
+use crate::{
+    error::WotsError,
+    merkle::{MerkleKeypair, MerkleSignature},
+};
+
+/// A durable counter of the one-time key indices a signer has used.
+pub trait StateStore {
+    /// The error raised when the state can't be read or written.
+    type Error;
+
+    /// Reserve the next unused index.
+    ///
+    /// The built-in stores return `WotsError::StateExhausted` once every
+    /// `u64` index has been reserved.
+    ///
+    /// The reservation must be persisted before this returns, so that the
+    /// index is never returned again, even if the caller crashes before it
+    /// releases a signature.
+    fn reserve(&mut self) -> Result<u64, Self::Error>;
+}
+
+/// A [`StateStore`] kept in memory, for tests and signers whose state is
+/// persisted by other means.
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub struct MemoryStore {
+    next: u64,
+}
+
+impl MemoryStore {
+    /// A store whose next reserved index is `next`.
+    pub fn new(next: u64) -> Self {
+        MemoryStore { next }
+    }
+
+    /// The index the next reservation will return.
+    pub fn next(&self) -> u64 {
+        self.next
+    }
+}
+
+impl StateStore for MemoryStore {
+    type Error = WotsError;
+
+    fn reserve(&mut self) -> Result<u64, WotsError> {
+        let index = self.next;
+        self.next = index
+            .checked_add(1)
+            .ok_or(WotsError::StateExhausted { next: index })?;
+
+        Ok(index)
+    }
+}
+
+/// A [`StateStore`] backed by a file holding the next unused index.
+///
+/// Each reservation writes the new index to a temporary file beside the
+/// state file, syncs it to disk and renames it over the state file, so the
+/// state file always holds either the old or the new index. A missing
+/// state file starts from index 0.
+///
+/// The store does not lock the file: only one process may sign with a key
+/// at a time.
+#[cfg(feature = "std")]
+#[derive(Clone, Debug)]
+pub struct FileStore {
+    path: std::path::PathBuf,
+}
+
+#[cfg(feature = "std")]
+impl FileStore {
+    /// A store keeping its state in the file at `path`.
+    pub fn new(path: impl Into<std::path::PathBuf>) -> Self {
+        FileStore { path: path.into() }
+    }
+
+    /// The path of the state file.
+    pub fn path(&self) -> &std::path::Path {
+        &self.path
+    }
+
+    /// Read the next unused index from the state file.
+    pub fn next(&self) -> std::io::Result<u64> {
+        match std::fs::read(&self.path) {
+            Ok(bytes) => {
+                let bytes: [u8; 8] = bytes.as_slice().try_into().map_err(|_| {
+                    std::io::Error::new(
+                        std::io::ErrorKind::InvalidData,
+                        WotsError::LengthMismatch {
+                            expected: 8,
+                            actual: bytes.len(),
+                        },
+                    )
+                })?;
+
+                Ok(u64::from_be_bytes(bytes))
+            }
+            Err(error) if error.kind() == std::io::ErrorKind::NotFound => Ok(0),
+            Err(error) => Err(error),
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl StateStore for FileStore {
+    type Error = std::io::Error;
+
+    fn reserve(&mut self) -> std::io::Result<u64> {
+        use std::io::Write;
+
+        let index = self.next()?;
+        let next = index
+            .checked_add(1)
+            .ok_or(WotsError::StateExhausted { next: index })?;
+
+        let mut tmp = self.path.clone().into_os_string();
+        tmp.push(".tmp");
+        let mut file = std::fs::File::create(&tmp)?;
+        file.write_all(&next.to_be_bytes())?;
+        file.sync_all()?;
+        std::fs::rename(&tmp, &self.path)?;
+
+        #[cfg(unix)]
+        if let Some(dir) = self.path.parent() {
+            let dir = if dir.as_os_str().is_empty() {
+                std::path::Path::new(".")
+            } else {
+                dir
+            };
+            std::fs::File::open(dir)?.sync_all()?;
+        }
+
+        Ok(index)
+    }
+}
+
+/// Report a WOTS error, such as running out of keys, as invalid input.
+#[cfg(feature = "std")]
+impl From<WotsError> for std::io::Error {
+    fn from(value: WotsError) -> Self {
+        std::io::Error::new(std::io::ErrorKind::InvalidInput, value)
+    }
+}
+
+impl MerkleKeypair {
+    /// Sign a `message` with the next keypair reserved from `store`.
+    ///
+    /// The index is reserved before signing, so a failed or interrupted
+    /// call burns an index rather than risking its reuse.
+    ///
+    /// # Errors
+    ///
+    /// Returns the error of the `store`, or `WotsError::IndexOutOfRange`
+    /// converted into it once every keypair of this tree has been used.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use rand::rngs::OsRng;
+    /// use wots_rs::{MemoryStore, MerkleKeypair};
+    ///
+    /// let mut csprng = OsRng{};
+    /// let tree = MerkleKeypair::generate(&mut csprng, 1);
+    /// let mut store = MemoryStore::default();
+    ///
+    /// let first = tree.sign_with_state(&mut store, b"hello").unwrap();
+    /// let second = tree.sign_with_state(&mut store, b"world").unwrap();
+    ///
+    /// assert_eq!((first.index, second.index), (0, 1));
+    /// assert!(tree.sign_with_state(&mut store, b"again").is_err());
+    ///
+    /// ```
+    pub fn sign_with_state<S>(
+        &self,
+        store: &mut S,
+        message: &[u8],
+    ) -> Result<MerkleSignature, S::Error>
+    where
+        S: StateStore,
+        S::Error: From<WotsError>,
+    {
+        let index = store.reserve()?;
+        let index = usize::try_from(index).map_err(|_| WotsError::IndexOutOfRange {
+            index: usize::MAX,
+            len: self.len(),
+        })?;
+
+        Ok(self.sign(index, message)?)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::keypair::Keypair;
+
+    fn tree() -> MerkleKeypair {
+        (0..2u8)
+            .map(|seed| Keypair::from_seed([seed; 32]))
+            .collect()
+    }
+
+    #[test]
+    fn memory_store_hands_out_each_index_once() {
+        let tree = tree();
+        let mut store = MemoryStore::new(1);
+
+        let signature = tree.sign_with_state(&mut store, b"hello").unwrap();
+        assert_eq!(signature.index, 1);
+        assert!(tree.public_key().verify(b"hello", &signature));
+
+        assert_eq!(
+            tree.sign_with_state(&mut store, b"world").err(),
+            Some(WotsError::IndexOutOfRange { index: 2, len: 2 })
+        );
+        assert_eq!(store.next(), 3);
+    }
+
+    #[test]
+    fn memory_store_reports_exhaustion() {
+        let mut store = MemoryStore::new(u64::MAX);
+
+        assert_eq!(
+            store.reserve(),
+            Err(WotsError::StateExhausted { next: u64::MAX })
+        );
+        assert_eq!(store.next(), u64::MAX);
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn file_store_survives_reopening() {
+        let path = std::env::temp_dir().join(format!("wots-state-{}", std::process::id()));
+        let _ = std::fs::remove_file(&path);
+
+        let tree = tree();
+        let signature = tree
+            .sign_with_state(&mut FileStore::new(&path), b"hello")
+            .unwrap();
+        assert_eq!(signature.index, 0);
+
+        let mut store = FileStore::new(&path);
+        assert_eq!(store.next().unwrap(), 1);
+        assert_eq!(tree.sign_with_state(&mut store, b"world").unwrap().index, 1);
+        assert!(tree.sign_with_state(&mut store, b"again").is_err());
+        assert_eq!(store.next().unwrap(), 3);
+
+        std::fs::remove_file(&path).unwrap();
+    }
+}
@@ -0,0 +1,146 @@
+//! WOTS verification of signatures arriving in fragments.
+use crate::{
+    checksum::with_checksum,
+    constants::{CHAINS, SIGNATURE_LENGTH},
+    error::WotsError,
+    hash::sha256,
+    public::{finish_chain, PublicKey},
+};
+use subtle::ConstantTimeEq;
+
+/// A verifier that hashes signature chains as their bytes arrive.
+///
+/// Each 32-byte chain is checked against the public key as soon as it is
+/// complete, so verification work overlaps with receiving the signature.
+pub struct StreamingVerifier<'a> {
+    public_key: &'a PublicKey,
+    digits: [u8; CHAINS],
+    chain: [u8; 32],
+    received: usize,
+    valid: bool,
+}
+
+impl<'a> StreamingVerifier<'a> {
+    /// Start verifying a signature on `message` against `public_key`.
+    pub fn new(public_key: &'a PublicKey, message: &[u8]) -> Self {
+        StreamingVerifier {
+            public_key,
+            digits: with_checksum(&sha256(message)),
+            chain: [0u8; 32],
+            received: 0,
+            valid: true,
+        }
+    }
+
+    /// Feed the next `bytes` of the flat signature.
+    ///
+    /// Bytes beyond the signature length are counted but otherwise ignored,
+    /// and make [`finish`](Self::finish) fail.
+    pub fn feed(&mut self, bytes: &[u8]) {
+        for &byte in bytes {
+            if self.received < SIGNATURE_LENGTH {
+                self.chain[self.received % 32] = byte;
+
+                if self.received % 32 == 31 {
+                    self.check_chain(self.received / 32);
+                }
+            }
+
+            self.received += 1;
+        }
+    }
+
+    /// Finish verification.
+    ///
+    /// # Returns
+    ///
+    /// Returns `Ok(true)` if the fed bytes form a valid signature on the
+    /// message, or `WotsError::LengthMismatch` if exactly
+    /// [`SIGNATURE_LENGTH`] bytes were not fed.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use rand::rngs::OsRng;
+    /// use wots_rs::{Keypair, StreamingVerifier};
+    ///
+    /// let mut csprng = OsRng{};
+    /// let keypair: Keypair = Keypair::generate(&mut csprng);
+    ///
+    /// let message = b"hello";
+    /// let signature = keypair.sign_unchecked(message).to_flat_bytes();
+    ///
+    /// let mut verifier = StreamingVerifier::new(&keypair.public, message);
+    /// for fragment in signature.chunks(100) {
+    ///     verifier.feed(fragment);
+    /// }
+    ///
+    /// assert_eq!(verifier.finish(), Ok(true));
+    ///
+    /// ```
+    pub fn finish(self) -> Result<bool, WotsError> {
+        if self.received != SIGNATURE_LENGTH {
+            return Err(WotsError::LengthMismatch {
+                expected: SIGNATURE_LENGTH,
+                actual: self.received,
+            });
+        }
+
+        Ok(self.valid)
+    }
+
+    /// Check the completed chain `i` against the public key.
+    fn check_chain(&mut self, i: usize) {
+        let chain = finish_chain(&self.chain, self.digits[i]);
+        self.valid &= bool::from(self.public_key.as_ref()[i].ct_eq(&chain));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Keypair;
+
+    #[test]
+    fn fragments_match_verify() {
+        let message = b"hello";
+
+        let keypair = Keypair::from_seed([42u8; 32]);
+        let signature = keypair.sign_unchecked(message).to_flat_bytes();
+
+        for size in [1, 7, 32, 33, 500, SIGNATURE_LENGTH] {
+            let mut verifier = StreamingVerifier::new(&keypair.public, message);
+            for fragment in signature.chunks(size) {
+                verifier.feed(fragment);
+            }
+            assert_eq!(verifier.finish(), Ok(true));
+        }
+
+        let mut verifier = StreamingVerifier::new(&keypair.public, b"world");
+        verifier.feed(&signature);
+        assert_eq!(verifier.finish(), Ok(false));
+    }
+
+    #[test]
+    fn wrong_length_is_an_error() {
+        let message = b"hello";
+
+        let keypair = Keypair::from_seed([42u8; 32]);
+        let signature = keypair.sign_unchecked(message).to_flat_bytes();
+
+        let mut verifier = StreamingVerifier::new(&keypair.public, message);
+        verifier.feed(&signature[..1000]);
+        assert_eq!(
+            verifier.finish(),
+            Err(WotsError::LengthMismatch {
+                expected: SIGNATURE_LENGTH,
+                actual: 1000
+            })
+        );
+
+        let mut verifier = StreamingVerifier::new(&keypair.public, message);
+        verifier.feed(&signature);
+        verifier.feed(&[0]);
+        assert!(verifier.finish().is_err());
+    }
+}
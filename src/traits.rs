@@ -0,0 +1,65 @@
+//! Implementations of the [`signature`](::signature) crate's traits.
+//!
+//! The crate's `signature` module shadows the dependency of the same name,
+//! so its traits are named by absolute path.
+use crate::{
+    constants::SIGNATURE_LENGTH, keypair::Keypair, public::PublicKey, secret::SecretKey,
+    signature::Signature,
+};
+
+/// Sign messages with a `Keypair` through [`signature::Signer`](::signature::Signer).
+impl ::signature::Signer<Signature> for Keypair {
+    fn try_sign(&self, msg: &[u8]) -> Result<Signature, ::signature::Error> {
+        Ok(self.secret.sign(msg))
+    }
+}
+
+/// Sign messages with a `SecretKey` through [`signature::Signer`](::signature::Signer).
+impl ::signature::Signer<Signature> for SecretKey {
+    fn try_sign(&self, msg: &[u8]) -> Result<Signature, ::signature::Error> {
+        Ok(SecretKey::sign(self, msg))
+    }
+}
+
+/// Verify signatures with a `PublicKey` through
+/// [`signature::Verifier`](::signature::Verifier).
+impl ::signature::Verifier<Signature> for PublicKey {
+    fn verify(&self, msg: &[u8], signature: &Signature) -> Result<(), ::signature::Error> {
+        self.verify_strict(msg, signature)
+            .map_err(|_| ::signature::Error::new())
+    }
+}
+
+/// Encode a `Signature` as its flat bytes.
+impl ::signature::SignatureEncoding for Signature {
+    type Repr = [u8; SIGNATURE_LENGTH];
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ::signature::{SignatureEncoding, Signer, Verifier};
+
+    fn sign_generic<S: Signer<Signature>>(signer: &S, msg: &[u8]) -> Signature {
+        signer.sign(msg)
+    }
+
+    fn verify_generic<V: Verifier<Signature>>(verifier: &V, msg: &[u8], sig: &Signature) -> bool {
+        verifier.verify(msg, sig).is_ok()
+    }
+
+    #[test]
+    fn traits_agree_with_inherent_methods() {
+        let keypair = Keypair::from_seed([42u8; 32]);
+        let signature = sign_generic(&keypair, b"hello");
+
+        assert!(signature == keypair.sign_unchecked(b"hello"));
+        assert!(sign_generic(&keypair.secret, b"hello") == signature);
+        assert!(verify_generic(&keypair.public, b"hello", &signature));
+        assert!(!verify_generic(&keypair.public, b"world", &signature));
+
+        let encoded = SignatureEncoding::to_bytes(&signature);
+        assert_eq!(encoded, signature.to_flat_bytes());
+        assert_eq!(signature.encoded_len(), SIGNATURE_LENGTH);
+    }
+}
@@ -0,0 +1,86 @@
+//! WebAssembly bindings through `wasm-bindgen`.
+//!
+//! Keys are generated with `getrandom`, which uses the browser's
+//! `crypto.getRandomValues`. Keys and signatures cross into JavaScript as
+//! their flat [`SIGNATURE_LENGTH`](crate::SIGNATURE_LENGTH)-byte encoding, and malformed input is
+//! thrown as a JavaScript exception.
+use crate::{error::WotsError, keypair::Keypair, public::PublicKey, signature::Signature};
+use alloc::vec::Vec;
+use rand::rngs::OsRng;
+use wasm_bindgen::prelude::*;
+
+/// An WOTS keypair for use from JavaScript.
+#[wasm_bindgen(js_name = Keypair)]
+pub struct WasmKeypair(Keypair);
+
+#[wasm_bindgen(js_class = Keypair)]
+impl WasmKeypair {
+    /// Generate a keypair from the platform's random number generator.
+    #[wasm_bindgen(constructor)]
+    pub fn generate() -> WasmKeypair {
+        WasmKeypair(Keypair::generate(&mut OsRng {}))
+    }
+
+    /// Derive a keypair from a 32-byte `seed`, see
+    /// [`Keypair::from_seed`].
+    ///
+    /// Throws if `seed` is not exactly 32 bytes long.
+    #[wasm_bindgen(js_name = fromSeed)]
+    pub fn from_seed(seed: &[u8]) -> Result<WasmKeypair, JsError> {
+        let seed: &[u8; 32] = seed.try_into().map_err(|_| WotsError::LengthMismatch {
+            expected: 32,
+            actual: seed.len(),
+        })?;
+
+        Ok(WasmKeypair(Keypair::from_seed(seed)))
+    }
+
+    /// The flat bytes of the public key.
+    #[wasm_bindgen(js_name = publicKey)]
+    pub fn public_key(&self) -> Vec<u8> {
+        self.0.public.to_bytes().as_flattened().to_vec()
+    }
+
+    /// Sign a `message`, returning the flat bytes of the signature.
+    pub fn sign(&self, message: &[u8]) -> Vec<u8> {
+        self.0.sign_unchecked(message).to_flat_bytes().to_vec()
+    }
+}
+
+/// Verify the flat `signature` bytes on a `message` against the flat
+/// `public_key` bytes.
+///
+/// Throws if either is not exactly
+/// [`SIGNATURE_LENGTH`](crate::SIGNATURE_LENGTH) bytes long.
+#[wasm_bindgen]
+pub fn verify(public_key: &[u8], message: &[u8], signature: &[u8]) -> Result<bool, JsError> {
+    let public_key = PublicKey::from_bytes(public_key)?;
+    let signature = Signature::from_bytes(signature)?;
+
+    Ok(public_key.verify(message, signature))
+}
+
+#[cfg(all(test, target_arch = "wasm32"))]
+mod tests {
+    use super::*;
+    use crate::SIGNATURE_LENGTH;
+    use wasm_bindgen_test::wasm_bindgen_test;
+
+    #[wasm_bindgen_test]
+    fn sign_and_verify() {
+        let keypair = WasmKeypair::generate();
+        let signature = keypair.sign(b"hello");
+        assert_eq!(signature.len(), SIGNATURE_LENGTH);
+
+        assert!(verify(&keypair.public_key(), b"hello", &signature).unwrap());
+        assert!(!verify(&keypair.public_key(), b"world", &signature).unwrap());
+        assert!(verify(&keypair.public_key(), b"hello", &signature[1..]).is_err());
+
+        let keypair = WasmKeypair::from_seed(&[7u8; 32]).unwrap();
+        assert_eq!(
+            keypair.public_key(),
+            Keypair::from_seed(&[7u8; 32]).public.as_bytes()
+        );
+        assert!(WasmKeypair::from_seed(&[7u8; 31]).is_err());
+    }
+}
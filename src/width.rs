@@ -0,0 +1,352 @@
+//! WOTS over chain values of other lengths than 32 bytes.
+//!
+//! [`Keypair`], [`PublicKey`], [`SecretKey`] and [`Signature`] are generic
+//! over the length `N` of their chain values and their number of chains
+//! `L`, defaulting to the 32-byte SHA-256 layout of 34 chains. With
+//! `N = 64` the message digest and every chain step use SHA-512: the digest
+//! signs 64 chains and its checksum two more, so a signature is
+//! [`SHA512_CHAINS`] chains, 4224 bytes. The [`Sha512Keypair`] family of
+//! aliases names these types.
+//!
+//! The default types keep their inherent constructors and methods. For
+//! other lengths, and for code generic over the length, they come from the
+//! [`Generate`], [`Sign`] and [`Verify`] traits:
+//!
+//! ```
+//! use rand::rngs::OsRng;
+//! use wots_rs::{Generate, Sha512Keypair, Sign, Verify};
+//!
+//! let keypair = Sha512Keypair::generate(&mut OsRng);
+//! let signature = keypair.sign(b"hello");
+//!
+//! assert_eq!(signature.as_ref()[0].len(), 64);
+//! assert!(keypair.verify(b"hello", signature));
+//! assert!(!keypair.verify(b"world", signature));
+//! ```
+use crate::{
+    checksum::append_checksum,
+    constants::{steps, CHAIN_LENGTH, CHECKSUM_CHAINS},
+    hash::{digest, iterate_digest_chains},
+    keypair::Keypair,
+    public::PublicKey,
+    secret::{wipe, SecretKey},
+    signature::Signature,
+};
+use rand::{CryptoRng, RngCore};
+use sha2::Sha512;
+use subtle::ConstantTimeEq;
+
+/// The number of chains in a key or signature with SHA-512 chains.
+pub const SHA512_CHAINS: usize = 64 + CHECKSUM_CHAINS;
+
+/// An WOTS keypair with 64-byte SHA-512 chains.
+pub type Sha512Keypair = Keypair<64, SHA512_CHAINS>;
+
+/// An WOTS secret key with 64-byte SHA-512 chains.
+pub type Sha512SecretKey = SecretKey<64, SHA512_CHAINS>;
+
+/// An WOTS public key with 64-byte SHA-512 chains.
+pub type Sha512PublicKey = PublicKey<64, SHA512_CHAINS>;
+
+/// An WOTS signature with 64-byte SHA-512 chains.
+pub type Sha512Signature = Signature<64, SHA512_CHAINS>;
+
+/// Construction of secret keys and keypairs.
+pub trait Generate: Sized {
+    /// The seed a key is expanded from, one chain value long.
+    type Seed;
+
+    /// Generate a key from a `csprng`.
+    fn generate<R>(csprng: &mut R) -> Self
+    where
+        R: CryptoRng + RngCore;
+
+    /// Expand a key deterministically from a `seed`.
+    fn from_seed(seed: &Self::Seed) -> Self;
+}
+
+/// Signing of messages with secret keys and keypairs.
+pub trait Sign {
+    /// The signatures produced.
+    type Signature;
+
+    /// Sign a `message`.
+    ///
+    /// The key must sign only this one message.
+    fn sign(&self, message: &[u8]) -> Self::Signature;
+}
+
+/// Verification of signatures with public keys and keypairs.
+pub trait Verify {
+    /// The signatures verified.
+    type Signature;
+
+    /// Verify a `signature` on a `message`, comparing the recovered public
+    /// key in constant time.
+    fn verify(&self, message: &[u8], signature: Self::Signature) -> bool;
+}
+
+impl Generate for SecretKey {
+    type Seed = [u8; 32];
+
+    fn generate<R>(csprng: &mut R) -> Self
+    where
+        R: CryptoRng + RngCore,
+    {
+        SecretKey::generate(csprng)
+    }
+
+    fn from_seed(seed: &[u8; 32]) -> Self {
+        SecretKey::from_seed(seed)
+    }
+}
+
+impl Generate for Keypair {
+    type Seed = [u8; 32];
+
+    fn generate<R>(csprng: &mut R) -> Self
+    where
+        R: CryptoRng + RngCore,
+    {
+        Keypair::generate(csprng)
+    }
+
+    fn from_seed(seed: &[u8; 32]) -> Self {
+        Keypair::from_seed(seed)
+    }
+}
+
+impl Sign for SecretKey {
+    type Signature = Signature;
+
+    fn sign(&self, message: &[u8]) -> Signature {
+        SecretKey::sign(self, message)
+    }
+}
+
+impl Sign for Keypair {
+    type Signature = Signature;
+
+    fn sign(&self, message: &[u8]) -> Signature {
+        Keypair::sign_unchecked(self, message)
+    }
+}
+
+impl Verify for PublicKey {
+    type Signature = Signature;
+
+    fn verify(&self, message: &[u8], signature: Signature) -> bool {
+        PublicKey::verify(self, message, signature)
+    }
+}
+
+impl Verify for Keypair {
+    type Signature = Signature;
+
+    fn verify(&self, message: &[u8], signature: Signature) -> bool {
+        Keypair::verify(self, message, signature)
+    }
+}
+
+impl Generate for Sha512SecretKey {
+    type Seed = [u8; 64];
+
+    fn generate<R>(csprng: &mut R) -> Self
+    where
+        R: CryptoRng + RngCore,
+    {
+        let mut seed = [0u8; 64];
+        csprng.fill_bytes(&mut seed);
+        let secret_key = Self::from_seed(&seed);
+        wipe(&mut seed);
+
+        secret_key
+    }
+
+    /// Chain `i` starts at `SHA-512(seed || i)`, with `i` as a big-endian
+    /// `u32`, like the SHA-256 chains of [`SecretKey::from_seed`].
+    fn from_seed(seed: &[u8; 64]) -> Self {
+        SecretKey::from(core::array::from_fn(|i| {
+            digest::<Sha512, 64>(&[seed, &(i as u32).to_be_bytes()])
+        }))
+    }
+}
+
+impl Generate for Sha512Keypair {
+    type Seed = [u8; 64];
+
+    fn generate<R>(csprng: &mut R) -> Self
+    where
+        R: CryptoRng + RngCore,
+    {
+        Keypair::from(Sha512SecretKey::generate(csprng))
+    }
+
+    fn from_seed(seed: &[u8; 64]) -> Self {
+        Keypair::from(Sha512SecretKey::from_seed(seed))
+    }
+}
+
+impl Sign for Sha512SecretKey {
+    type Signature = Sha512Signature;
+
+    fn sign(&self, message: &[u8]) -> Sha512Signature {
+        let digits = digits(message);
+        let chains = self.as_ref();
+
+        let mut signature = [[0u8; 64]; SHA512_CHAINS];
+        iterate_digest_chains::<Sha512, 64, _, _>(
+            &mut signature,
+            |i| chains[i],
+            |i| CHAIN_LENGTH - steps(digits[i]),
+        );
+
+        Signature::from(signature)
+    }
+}
+
+impl Sign for Sha512Keypair {
+    type Signature = Sha512Signature;
+
+    fn sign(&self, message: &[u8]) -> Sha512Signature {
+        self.secret.sign(message)
+    }
+}
+
+impl Verify for Sha512PublicKey {
+    type Signature = Sha512Signature;
+
+    fn verify(&self, message: &[u8], signature: Sha512Signature) -> bool {
+        let digits = digits(message);
+        let chains = signature.as_ref();
+
+        let mut recovered = [[0u8; 64]; SHA512_CHAINS];
+        iterate_digest_chains::<Sha512, 64, _, _>(
+            &mut recovered,
+            |i| chains[i],
+            |i| steps(digits[i]),
+        );
+
+        self.as_ref()
+            .as_flattened()
+            .ct_eq(recovered.as_flattened())
+            .into()
+    }
+}
+
+impl Verify for Sha512Keypair {
+    type Signature = Sha512Signature;
+
+    fn verify(&self, message: &[u8], signature: Sha512Signature) -> bool {
+        self.public.verify(message, signature)
+    }
+}
+
+/// Compute the public half of a `Sha512SecretKey`, walking every chain to
+/// its end.
+impl From<&Sha512SecretKey> for Sha512PublicKey {
+    fn from(value: &Sha512SecretKey) -> Self {
+        let chains = value.as_ref();
+
+        let mut public = [[0u8; 64]; SHA512_CHAINS];
+        iterate_digest_chains::<Sha512, 64, _, _>(&mut public, |i| chains[i], |_| CHAIN_LENGTH);
+
+        PublicKey::from(public)
+    }
+}
+
+/// Construct a `Sha512Keypair` from a `Sha512SecretKey`.
+impl From<Sha512SecretKey> for Sha512Keypair {
+    fn from(value: Sha512SecretKey) -> Self {
+        let public = PublicKey::from(&value);
+
+        Keypair {
+            secret: value,
+            public,
+        }
+    }
+}
+
+/// The digits signed for a `message`: the bytes of its SHA-512 digest
+/// followed by their big-endian checksum.
+fn digits(message: &[u8]) -> [u8; SHA512_CHAINS] {
+    append_checksum(&digest::<Sha512, 64>(&[message]))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn sha512_signs_and_verifies() {
+        let keypair = Sha512Keypair::from_seed(&[7u8; 64]);
+        let signature = keypair.sign(b"hello");
+
+        assert!(keypair.verify(b"hello", signature));
+        assert!(!keypair.verify(b"world", signature));
+        assert!(keypair.public == PublicKey::from(&keypair.secret));
+        assert!(keypair.secret == Sha512SecretKey::from_seed(&[7u8; 64]));
+        assert!(keypair.public != Sha512Keypair::from_seed(&[8u8; 64]).public);
+
+        let mut forged = *signature.as_ref();
+        forged[0] = digest::<Sha512, 64>(&[&forged[0]]);
+        assert!(!keypair.verify(b"hello", Signature::from(forged)));
+    }
+
+    #[test]
+    fn checksum_digits() {
+        let digits = digits(b"hello");
+        let checksum: u16 = digits[..64].iter().map(|&d| 255 - u16::from(d)).sum();
+
+        assert_eq!(digits[..64], digest::<Sha512, 64>(&[b"hello"]));
+        assert_eq!(digits[64..], checksum.to_be_bytes());
+    }
+
+    #[test]
+    fn sha512_chains_are_counted_and_faulted() {
+        use crate::hash::{count_hashes, with_fault_hash};
+
+        let secret = Sha512SecretKey::from_seed(&[7u8; 64]);
+        let (public, hashes) = count_hashes(|| PublicKey::from(&secret));
+        assert_eq!(hashes, SHA512_CHAINS * CHAIN_LENGTH);
+
+        // Signing and verifying walk each chain to its end between them,
+        // after hashing the message once each.
+        let (signature, signing) = count_hashes(|| secret.sign(b"hello"));
+        let (valid, verifying) = count_hashes(|| public.verify(b"hello", signature));
+        assert!(valid);
+        assert_eq!(signing + verifying, 2 + SHA512_CHAINS * CHAIN_LENGTH);
+
+        assert!(!with_fault_hash(
+            |_| [0u8; 32],
+            || public.verify(b"hello", signature)
+        ));
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn sha512_serde_round_trip() {
+        let keypair = Sha512Keypair::from_seed(&[7u8; 64]);
+
+        let bytes = bincode::serialize(&keypair).unwrap();
+        assert_eq!(bytes.len(), 2 * (8 + SHA512_CHAINS * 64));
+
+        let decoded: Sha512Keypair = bincode::deserialize(&bytes).unwrap();
+        assert!(decoded.secret == keypair.secret && decoded.public == keypair.public);
+        assert!(bincode::deserialize::<Keypair>(&bytes).is_err());
+    }
+
+    #[test]
+    fn traits_agree_with_inherent_methods() {
+        fn sign_generic<K: Sign>(key: &K, message: &[u8]) -> K::Signature {
+            key.sign(message)
+        }
+
+        let keypair = <Keypair as Generate>::from_seed(&[7u8; 32]);
+        let signature = sign_generic(&keypair, b"hello");
+
+        assert!(signature == keypair.sign_unchecked(b"hello"));
+        assert!(sign_generic(&keypair.secret, b"hello") == signature);
+        assert!(Verify::verify(&keypair.public, b"hello", signature));
+    }
+}
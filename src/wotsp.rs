@@ -0,0 +1,151 @@
+//! WOTS+ keys and signatures over arbitrary messages.
+//!
+//! These types wrap the [`rfc8391`] primitives: messages are hashed with
+//! SHA-256 and signed with the RFC's bitmasked chains. The bitmasks make
+//! the chains rely on second-preimage rather than collision resistance, but
+//! the message hash is unkeyed, so a SHA-256 collision between two messages
+//! lets a signature on one pass for the other. [`xmss`](crate::xmss) keys
+//! its message hash with a per-signature randomizer instead. A public key
+//! carries the public seed and address it was computed under.
+use crate::{
+    hash::sha256,
+    rfc8391::{self, Address, LEN},
+};
+use rand::{CryptoRng, RngCore};
+
+/// A WOTS+ secret key, expanded from a secret seed.
+#[derive(Clone)]
+pub struct SecretKey([[u8; 32]; LEN]);
+
+/// A WOTS+ public key together with the seed and address it belongs to.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct PublicKey {
+    chains: [[u8; 32]; LEN],
+    pub_seed: [u8; 32],
+    adrs: Address,
+}
+
+/// A WOTS+ signature.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct Signature(pub [[u8; 32]; LEN]);
+
+/// A WOTS+ keypair.
+pub struct Keypair {
+    /// The secret half of this keypair.
+    pub secret: SecretKey,
+    /// The public half of this keypair.
+    pub public: PublicKey,
+}
+
+impl Keypair {
+    /// Generate a WOTS+ keypair at the all-zero address.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use rand::rngs::OsRng;
+    /// use wots_rs::wotsp::Keypair;
+    ///
+    /// let keypair = Keypair::generate(&mut OsRng);
+    ///
+    /// let signature = keypair.sign(b"hello");
+    /// assert!(keypair.public.verify(b"hello", &signature));
+    ///
+    /// ```
+    pub fn generate<R>(csprng: &mut R) -> Self
+    where
+        R: CryptoRng + RngCore,
+    {
+        let mut sk_seed = [0u8; 32];
+        let mut pub_seed = [0u8; 32];
+        csprng.fill_bytes(&mut sk_seed);
+        csprng.fill_bytes(&mut pub_seed);
+
+        Self::from_seeds(&sk_seed, pub_seed, Address::new())
+    }
+
+    /// Derive a WOTS+ keypair from `sk_seed`, under `pub_seed` and `adrs`.
+    pub fn from_seeds(sk_seed: &[u8; 32], pub_seed: [u8; 32], adrs: Address) -> Self {
+        let secret = SecretKey(rfc8391::gen_sk(sk_seed));
+        let public = PublicKey {
+            chains: rfc8391::gen_pk(&secret.0, &pub_seed, &adrs),
+            pub_seed,
+            adrs,
+        };
+
+        Keypair { secret, public }
+    }
+
+    /// Sign the SHA-256 hash of `message`.
+    ///
+    /// The hash is unkeyed, so the signature is equally valid for any
+    /// message with the same SHA-256 digest.
+    pub fn sign(&self, message: &[u8]) -> Signature {
+        Signature(rfc8391::sign(
+            &sha256(message),
+            &self.secret.0,
+            &self.public.pub_seed,
+            &self.public.adrs,
+        ))
+    }
+}
+
+impl PublicKey {
+    /// The chains of this public key.
+    pub fn chains(&self) -> &[[u8; 32]; LEN] {
+        &self.chains
+    }
+
+    /// The public seed this key was computed under.
+    pub fn pub_seed(&self) -> &[u8; 32] {
+        &self.pub_seed
+    }
+
+    /// The hash address this key was computed under.
+    pub fn address(&self) -> &Address {
+        &self.adrs
+    }
+
+    /// Verify a `signature` on `message` with this public key.
+    pub fn verify(&self, message: &[u8], signature: &Signature) -> bool {
+        rfc8391::verify(
+            &signature.0,
+            &sha256(message),
+            &self.chains,
+            &self.pub_seed,
+            &self.adrs,
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn matches_rfc8391_primitives() {
+        let mut adrs = Address::new();
+        adrs.set_ots(5);
+        let keypair = Keypair::from_seeds(&[1u8; 32], [2u8; 32], adrs);
+
+        let m = sha256(b"hello");
+        let signature = keypair.sign(b"hello");
+
+        assert_eq!(
+            signature.0,
+            rfc8391::sign(&m, &rfc8391::gen_sk(&[1u8; 32]), &[2u8; 32], &adrs)
+        );
+        assert!(keypair.public.verify(b"hello", &signature));
+        assert!(!keypair.public.verify(b"world", &signature));
+    }
+
+    #[test]
+    fn public_key_is_bound_to_its_address() {
+        let keypair = Keypair::from_seeds(&[1u8; 32], [2u8; 32], Address::new());
+        let signature = keypair.sign(b"hello");
+
+        let mut other = keypair.public;
+        other.adrs.set_ots(1);
+        assert!(!other.verify(b"hello", &signature));
+    }
+}
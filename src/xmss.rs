@@ -0,0 +1,711 @@
+//! XMSS as specified by RFC 8391, on top of the crate's WOTS+ chains.
+//!
+//! This implements single-tree XMSS with the SHA2-256 parameter sets
+//! XMSS-SHA2_10_256, XMSS-SHA2_16_256 and XMSS-SHA2_20_256: L-trees compress
+//! each [`rfc8391`] public key into a leaf, and signatures
+//! and public keys use the RFC's wire format, so they interoperate with other
+//! implementations. Generating a key hashes every leaf once; after that the
+//! key keeps a logarithmic traversal of the tree, so each signature computes
+//! at most `height` further leaves.
+//!
+//! The WOTS+ secret key of leaf `i` is expanded from
+//! `PRF(SK_SEED, toByte(i, 32))`. The RFC leaves this choice to the
+//! implementation, as it never leaves the signer.
+use crate::{
+    error::WotsError,
+    hash::sha256,
+    merkle::{Traversal, TreeHasher},
+    rfc8391::{self, prf, Address, LEN},
+};
+use alloc::vec::Vec;
+use rand::{CryptoRng, RngCore};
+use subtle::ConstantTimeEq;
+
+/// The address type of WOTS+ hashes.
+const OTS: u32 = 0;
+
+/// The address type of L-tree hashes.
+const LTREE: u32 = 1;
+
+/// The address type of hash tree hashes.
+const HASH_TREE: u32 = 2;
+
+/// An XMSS parameter set.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct Params {
+    oid: u32,
+    height: u32,
+}
+
+impl Params {
+    /// XMSS-SHA2_10_256, with 2^10 signatures per key.
+    pub const XMSS_SHA2_10_256: Params = Params {
+        oid: 0x0000_0001,
+        height: 10,
+    };
+
+    /// XMSS-SHA2_16_256, with 2^16 signatures per key.
+    pub const XMSS_SHA2_16_256: Params = Params {
+        oid: 0x0000_0002,
+        height: 16,
+    };
+
+    /// XMSS-SHA2_20_256, with 2^20 signatures per key.
+    pub const XMSS_SHA2_20_256: Params = Params {
+        oid: 0x0000_0003,
+        height: 20,
+    };
+
+    /// Look up the parameter set registered under `oid`.
+    pub fn from_oid(oid: u32) -> Option<Params> {
+        [
+            Params::XMSS_SHA2_10_256,
+            Params::XMSS_SHA2_16_256,
+            Params::XMSS_SHA2_20_256,
+        ]
+        .into_iter()
+        .find(|params| params.oid == oid)
+    }
+
+    /// The OID identifying this parameter set.
+    pub fn oid(&self) -> u32 {
+        self.oid
+    }
+
+    /// The height of the tree.
+    pub fn height(&self) -> u32 {
+        self.height
+    }
+
+    /// The number of signatures a key can make.
+    pub fn capacity(&self) -> u64 {
+        1 << self.height
+    }
+
+    /// The length of a serialized signature in bytes.
+    pub fn signature_length(&self) -> usize {
+        4 + 32 + 32 * LEN + 32 * self.height as usize
+    }
+
+    /// The length of a serialized public key in bytes.
+    pub fn public_key_length(&self) -> usize {
+        4 + 32 + 32
+    }
+}
+
+/// The tree hash `H`.
+fn h(key: &[u8; 32], left: &[u8; 32], right: &[u8; 32]) -> [u8; 32] {
+    let mut input = [0u8; 128];
+    input[31] = 1;
+    input[32..64].copy_from_slice(key);
+    input[64..96].copy_from_slice(left);
+    input[96..].copy_from_slice(right);
+
+    sha256(&input)
+}
+
+/// The message hash `H_msg`, keyed with `r || root || toByte(index, 32)`.
+fn h_msg(r: &[u8; 32], root: &[u8; 32], index: u32, message: &[u8]) -> [u8; 32] {
+    let mut input = Vec::with_capacity(128 + message.len());
+    input.extend_from_slice(&[0u8; 31]);
+    input.push(2);
+    input.extend_from_slice(r);
+    input.extend_from_slice(root);
+    input.extend_from_slice(&[0u8; 28]);
+    input.extend_from_slice(&index.to_be_bytes());
+    input.extend_from_slice(message);
+
+    sha256(&input)
+}
+
+/// Hash two nodes into their parent, `RAND_HASH` in the RFC.
+fn rand_hash(left: &[u8; 32], right: &[u8; 32], pub_seed: &[u8; 32], adrs: &Address) -> [u8; 32] {
+    let mut adrs = *adrs;
+    let mut masked = [[0u8; 32]; 2];
+
+    adrs.set_key_and_mask(0);
+    let key = prf(pub_seed, &adrs.to_bytes());
+    for (i, (node, masked)) in [left, right].into_iter().zip(masked.iter_mut()).enumerate() {
+        adrs.set_key_and_mask(i as u32 + 1);
+        let mask = prf(pub_seed, &adrs.to_bytes());
+        for ((masked, node), mask) in masked.iter_mut().zip(node.iter()).zip(mask.iter()) {
+            *masked = node ^ mask;
+        }
+    }
+
+    h(&key, &masked[0], &masked[1])
+}
+
+/// Compress a WOTS+ public key into a single node.
+fn ltree(pk: &[[u8; 32]; LEN], pub_seed: &[u8; 32], index: u32) -> [u8; 32] {
+    let mut adrs = Address::new();
+    adrs.set_type(LTREE);
+    adrs.set_ltree(index);
+
+    let mut nodes = pk.to_vec();
+    let mut height = 0;
+    while nodes.len() > 1 {
+        adrs.set_tree_height(height);
+        let mut next = Vec::with_capacity(nodes.len().div_ceil(2));
+        for (i, pair) in nodes.chunks(2).enumerate() {
+            match pair {
+                [left, right] => {
+                    adrs.set_tree_index(i as u32);
+                    next.push(rand_hash(left, right, pub_seed, &adrs));
+                }
+                [odd] => next.push(*odd),
+                _ => unreachable!("chunks of two"),
+            }
+        }
+
+        nodes = next;
+        height += 1;
+    }
+
+    nodes[0]
+}
+
+/// Recompute the root from a leaf node and its authentication path.
+fn root_from_leaf(
+    leaf: [u8; 32],
+    index: u32,
+    auth_path: &[[u8; 32]],
+    pub_seed: &[u8; 32],
+) -> [u8; 32] {
+    let mut adrs = Address::new();
+    adrs.set_type(HASH_TREE);
+
+    auth_path
+        .iter()
+        .enumerate()
+        .fold(leaf, |node, (height, sibling)| {
+            adrs.set_tree_height(height as u32);
+            adrs.set_tree_index(index >> (height + 1));
+            if (index >> height) & 1 == 0 {
+                rand_hash(&node, sibling, pub_seed, &adrs)
+            } else {
+                rand_hash(sibling, &node, pub_seed, &adrs)
+            }
+        })
+}
+
+/// The seeds that determine the leaves of an XMSS tree.
+struct Tree {
+    sk_seed: [u8; 32],
+    pub_seed: [u8; 32],
+}
+
+impl Tree {
+    /// The WOTS+ secret key of leaf `index`.
+    fn wots_sk(&self, index: u32) -> [[u8; 32]; LEN] {
+        let mut position = [0u8; 32];
+        position[28..].copy_from_slice(&index.to_be_bytes());
+
+        rfc8391::gen_sk(&prf(&self.sk_seed, &position))
+    }
+}
+
+impl TreeHasher for Tree {
+    fn leaf(&self, index: usize) -> [u8; 32] {
+        let index = index as u32;
+        let pk = rfc8391::gen_pk(&self.wots_sk(index), &self.pub_seed, &ots_address(index));
+
+        ltree(&pk, &self.pub_seed, index)
+    }
+
+    fn parent(&self, height: u32, index: usize, left: &[u8; 32], right: &[u8; 32]) -> [u8; 32] {
+        let mut adrs = Address::new();
+        adrs.set_type(HASH_TREE);
+        adrs.set_tree_height(height - 1);
+        adrs.set_tree_index(index as u32);
+
+        rand_hash(left, right, &self.pub_seed, &adrs)
+    }
+}
+
+/// An XMSS secret key.
+///
+/// Signing advances the key to its next leaf. The key must be persisted
+/// with [`to_bytes`](Self::to_bytes) after every signature, before the
+/// signature is released, and restored with
+/// [`from_bytes`](Self::from_bytes); rebuilding it from its seeds starts
+/// over at leaf 0 and reuses one-time keys.
+pub struct SecretKey {
+    params: Params,
+    sk_prf: [u8; 32],
+    tree: Tree,
+    traversal: Traversal,
+}
+
+impl SecretKey {
+    /// The length of a serialized secret key in bytes.
+    pub const LENGTH: usize = 4 + 4 + 4 * 32;
+
+    /// Generate an XMSS secret key for `params`.
+    ///
+    /// This computes every leaf of the tree, which for the larger parameter
+    /// sets takes a while.
+    pub fn generate<R>(csprng: &mut R, params: Params) -> Self
+    where
+        R: CryptoRng + RngCore,
+    {
+        let mut seeds = [[0u8; 32]; 3];
+        for seed in seeds.iter_mut() {
+            csprng.fill_bytes(seed);
+        }
+
+        Self::from_seeds(params, seeds[0], seeds[1], seeds[2])
+    }
+
+    /// Derive an XMSS secret key for `params` from its three seeds.
+    ///
+    /// The key starts at leaf 0, so this is only for new keys; restore a
+    /// key that has signed with [`from_bytes`](Self::from_bytes).
+    pub fn from_seeds(
+        params: Params,
+        sk_seed: [u8; 32],
+        sk_prf: [u8; 32],
+        pub_seed: [u8; 32],
+    ) -> Self {
+        Self::at_index(params, sk_seed, sk_prf, pub_seed, 0)
+    }
+
+    /// Derive the key from its seeds, with leaf `index` the next to sign.
+    fn at_index(
+        params: Params,
+        sk_seed: [u8; 32],
+        sk_prf: [u8; 32],
+        pub_seed: [u8; 32],
+        index: u32,
+    ) -> Self {
+        let tree = Tree { sk_seed, pub_seed };
+        let traversal = Traversal::new(&tree, params.height, index as usize);
+
+        SecretKey {
+            params,
+            sk_prf,
+            tree,
+            traversal,
+        }
+    }
+
+    /// The parameter set of this key.
+    pub fn params(&self) -> Params {
+        self.params
+    }
+
+    /// The index of the next leaf to sign with.
+    pub fn index(&self) -> u32 {
+        self.traversal.index() as u32
+    }
+
+    /// The public key of this secret key.
+    pub fn public_key(&self) -> PublicKey {
+        PublicKey {
+            params: self.params,
+            root: self.traversal.root(),
+            pub_seed: self.tree.pub_seed,
+        }
+    }
+
+    /// Sign a `message` with the next unused leaf.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use rand::rngs::OsRng;
+    /// use wots_rs::xmss::{Params, SecretKey};
+    ///
+    /// let mut secret_key = SecretKey::generate(&mut OsRng, Params::XMSS_SHA2_10_256);
+    /// let public_key = secret_key.public_key();
+    ///
+    /// let signature = secret_key.sign(b"hello").unwrap();
+    /// let state = secret_key.to_bytes();
+    /// // Write `state` to durable storage before releasing `signature`.
+    /// assert!(public_key.verify(b"hello", &signature));
+    ///
+    /// let secret_key = SecretKey::from_bytes(&state).unwrap();
+    /// assert_eq!(secret_key.index(), 1);
+    ///
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// Returns `WotsError::IndexOutOfRange` once every leaf has been used.
+    pub fn sign(&mut self, message: &[u8]) -> Result<Signature, WotsError> {
+        let index = self.index();
+        if u64::from(index) >= self.params.capacity() {
+            return Err(WotsError::IndexOutOfRange {
+                index: index as usize,
+                len: self.params.capacity() as usize,
+            });
+        }
+
+        let mut position = [0u8; 32];
+        position[28..].copy_from_slice(&index.to_be_bytes());
+        let r = prf(&self.sk_prf, &position);
+        let digest = h_msg(&r, &self.traversal.root(), index, message);
+
+        let wots = rfc8391::sign(
+            &digest,
+            &self.tree.wots_sk(index),
+            &self.tree.pub_seed,
+            &ots_address(index),
+        );
+        let auth_path = self.traversal.auth_path().to_vec();
+        self.traversal.advance(&self.tree);
+
+        Ok(Signature {
+            index,
+            r,
+            wots,
+            auth_path,
+        })
+    }
+
+    /// Serialize this secret key as
+    /// `OID || idx || SK_SEED || SK_PRF || root || SEED`.
+    ///
+    /// The bytes are as secret as the key and hold its next leaf index.
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let mut bytes = Vec::with_capacity(Self::LENGTH);
+        bytes.extend_from_slice(&self.params.oid.to_be_bytes());
+        bytes.extend_from_slice(&self.index().to_be_bytes());
+        bytes.extend_from_slice(&self.tree.sk_seed);
+        bytes.extend_from_slice(&self.sk_prf);
+        bytes.extend_from_slice(&self.traversal.root());
+        bytes.extend_from_slice(&self.tree.pub_seed);
+
+        bytes
+    }
+
+    /// Restore a secret key serialized by [`to_bytes`](Self::to_bytes),
+    /// resuming at its next leaf.
+    ///
+    /// Like generation, this computes every leaf of the tree.
+    ///
+    /// # Errors
+    ///
+    /// Returns `WotsError::LengthMismatch` if `bytes` has the wrong length,
+    /// `WotsError::BadMagic` if its OID names no supported parameter set,
+    /// `WotsError::IndexOutOfRange` if its index is beyond the capacity of
+    /// the tree and `WotsError::InvalidEncoding` if its root does not match
+    /// its seeds.
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self, WotsError> {
+        if bytes.len() != Self::LENGTH {
+            return Err(WotsError::LengthMismatch {
+                expected: Self::LENGTH,
+                actual: bytes.len(),
+            });
+        }
+
+        let oid = u32::from_be_bytes(bytes[..4].try_into().unwrap());
+        let params = Params::from_oid(oid).ok_or(WotsError::BadMagic)?;
+
+        Self::restore(params, &bytes[4..])
+    }
+
+    /// Restore a key for `params` from its serialization after the OID.
+    fn restore(params: Params, bytes: &[u8]) -> Result<Self, WotsError> {
+        let seed = |offset: usize| -> [u8; 32] { bytes[offset..offset + 32].try_into().unwrap() };
+
+        let index = u32::from_be_bytes(bytes[..4].try_into().unwrap());
+        if u64::from(index) > params.capacity() {
+            return Err(WotsError::IndexOutOfRange {
+                index: index as usize,
+                len: params.capacity() as usize,
+            });
+        }
+
+        let secret_key = Self::at_index(params, seed(4), seed(36), seed(100), index);
+        if !bool::from(secret_key.traversal.root().ct_eq(&seed(68))) {
+            return Err(WotsError::InvalidEncoding);
+        }
+
+        Ok(secret_key)
+    }
+}
+
+/// The address of the WOTS+ key of leaf `index`.
+fn ots_address(index: u32) -> Address {
+    let mut adrs = Address::new();
+    adrs.set_type(OTS);
+    adrs.set_ots(index);
+
+    adrs
+}
+
+/// An XMSS public key.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct PublicKey {
+    params: Params,
+    root: [u8; 32],
+    pub_seed: [u8; 32],
+}
+
+impl PublicKey {
+    /// The parameter set of this key.
+    pub fn params(&self) -> Params {
+        self.params
+    }
+
+    /// The root of the tree.
+    pub fn root(&self) -> [u8; 32] {
+        self.root
+    }
+
+    /// Verify a `signature` on a `message` with this public key.
+    pub fn verify(&self, message: &[u8], signature: &Signature) -> bool {
+        let index = signature.index;
+        if u64::from(index) >= self.params.capacity()
+            || signature.auth_path.len() != self.params.height as usize
+        {
+            return false;
+        }
+
+        let digest = h_msg(&signature.r, &self.root, index, message);
+        let pk = rfc8391::pk_from_sig(
+            &signature.wots,
+            &digest,
+            &self.pub_seed,
+            &ots_address(index),
+        );
+        let leaf = ltree(&pk, &self.pub_seed, index);
+        let root = root_from_leaf(leaf, index, &signature.auth_path, &self.pub_seed);
+
+        root.ct_eq(&self.root).into()
+    }
+
+    /// Serialize this public key as `OID || root || SEED`.
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let mut bytes = Vec::with_capacity(self.params.public_key_length());
+        bytes.extend_from_slice(&self.params.oid.to_be_bytes());
+        bytes.extend_from_slice(&self.root);
+        bytes.extend_from_slice(&self.pub_seed);
+
+        bytes
+    }
+
+    /// Parse a public key serialized by [`to_bytes`](Self::to_bytes).
+    ///
+    /// # Errors
+    ///
+    /// Returns `WotsError::LengthMismatch` if `bytes` has the wrong length
+    /// and `WotsError::BadMagic` if its OID names no supported parameter set.
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self, WotsError> {
+        if bytes.len() != 68 {
+            return Err(WotsError::LengthMismatch {
+                expected: 68,
+                actual: bytes.len(),
+            });
+        }
+
+        let oid = u32::from_be_bytes(bytes[..4].try_into().unwrap());
+        let params = Params::from_oid(oid).ok_or(WotsError::BadMagic)?;
+
+        Ok(PublicKey {
+            params,
+            root: bytes[4..36].try_into().unwrap(),
+            pub_seed: bytes[36..].try_into().unwrap(),
+        })
+    }
+}
+
+/// An XMSS signature.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Signature {
+    /// The index of the leaf that made this signature.
+    pub index: u32,
+    /// The randomness of the message hash.
+    pub r: [u8; 32],
+    /// The WOTS+ signature of the message hash.
+    pub wots: [[u8; 32]; LEN],
+    /// The authentication path of the leaf, from the bottom up.
+    pub auth_path: Vec<[u8; 32]>,
+}
+
+impl Signature {
+    /// Serialize this signature as `idx_sig || r || sig_ots || auth`.
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let mut bytes = Vec::with_capacity(4 + 32 * (1 + LEN + self.auth_path.len()));
+        bytes.extend_from_slice(&self.index.to_be_bytes());
+        bytes.extend_from_slice(&self.r);
+        bytes.extend_from_slice(self.wots.as_flattened());
+        bytes.extend_from_slice(self.auth_path.as_flattened());
+
+        bytes
+    }
+
+    /// Parse a signature for `params` serialized by [`to_bytes`](Self::to_bytes).
+    ///
+    /// # Errors
+    ///
+    /// Returns `WotsError::LengthMismatch` if `bytes` isn't
+    /// `params.signature_length()` bytes long.
+    pub fn from_bytes(params: Params, bytes: &[u8]) -> Result<Self, WotsError> {
+        if bytes.len() != params.signature_length() {
+            return Err(WotsError::LengthMismatch {
+                expected: params.signature_length(),
+                actual: bytes.len(),
+            });
+        }
+
+        let mut chunks = bytes[4..]
+            .chunks_exact(32)
+            .map(|chunk| <[u8; 32]>::try_from(chunk).unwrap());
+
+        let r = chunks.next().unwrap();
+        let mut wots = [[0u8; 32]; LEN];
+        for (chain, chunk) in wots.iter_mut().zip(chunks.by_ref()) {
+            *chain = chunk;
+        }
+
+        Ok(Signature {
+            index: u32::from_be_bytes(bytes[..4].try_into().unwrap()),
+            r,
+            wots,
+            auth_path: chunks.collect(),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// A tree small enough to build in tests.
+    const SMALL: Params = Params { oid: 0, height: 3 };
+
+    fn secret_key() -> SecretKey {
+        SecretKey::from_seeds(SMALL, [1u8; 32], [2u8; 32], [3u8; 32])
+    }
+
+    #[test]
+    fn sign_and_verify_every_leaf() {
+        let mut secret_key = secret_key();
+        let public_key = secret_key.public_key();
+
+        for index in 0..8 {
+            let signature = secret_key.sign(b"hello").unwrap();
+            assert_eq!(signature.index, index);
+            assert!(public_key.verify(b"hello", &signature));
+            assert!(!public_key.verify(b"world", &signature));
+        }
+
+        assert_eq!(
+            secret_key.sign(b"hello").err(),
+            Some(WotsError::IndexOutOfRange { index: 8, len: 8 })
+        );
+    }
+
+    // Computed with an independent implementation of the RFC 8391
+    // pseudocode.
+    #[test]
+    fn matches_reference_vector() {
+        let mut secret_key = secret_key();
+        let hash = |signature: Signature| crate::hex::encode(&sha256(&signature.to_bytes()));
+
+        assert_eq!(
+            crate::hex::encode(&secret_key.traversal.root()),
+            "6797147df085e311fce35bed9ed62fc4e4f0e7e6fbca2948afb6f394da6dc07c"
+        );
+        assert_eq!(
+            hash(secret_key.sign(b"hello").unwrap()),
+            "3293a1936d87d81cae5b97ea5afdb198b392d0862ed4dd9c358c1d1b1cf63e97"
+        );
+        for _ in 0..3 {
+            secret_key.sign(b"hello").unwrap();
+        }
+        assert_eq!(
+            hash(secret_key.sign(b"hello").unwrap()),
+            "860ed54c08a6b9f22887e91eb7cf6cbbc4250e94cf2e099c2b8beb7c35639ae7"
+        );
+    }
+
+    #[test]
+    fn bytes_round_trip() {
+        let mut secret_key = secret_key();
+        let signature = secret_key.sign(b"hello").unwrap();
+
+        let bytes = signature.to_bytes();
+        assert_eq!(bytes.len(), SMALL.signature_length());
+        assert_eq!(Signature::from_bytes(SMALL, &bytes), Ok(signature));
+        assert_eq!(
+            Signature::from_bytes(SMALL, &bytes[1..]).err(),
+            Some(WotsError::LengthMismatch {
+                expected: SMALL.signature_length(),
+                actual: SMALL.signature_length() - 1,
+            })
+        );
+
+        let public_key = PublicKey {
+            params: Params::XMSS_SHA2_10_256,
+            ..secret_key.public_key()
+        };
+        let bytes = public_key.to_bytes();
+        assert_eq!(&bytes[..4], &[0, 0, 0, 1]);
+        assert_eq!(PublicKey::from_bytes(&bytes), Ok(public_key));
+        assert_eq!(
+            PublicKey::from_bytes(&secret_key.public_key().to_bytes()),
+            Err(WotsError::BadMagic)
+        );
+    }
+
+    #[test]
+    fn secret_key_resumes_from_bytes() {
+        let mut secret_key = secret_key();
+        let public_key = secret_key.public_key();
+        for _ in 0..3 {
+            secret_key.sign(b"hello").unwrap();
+        }
+
+        let bytes = secret_key.to_bytes();
+        assert_eq!(bytes.len(), SecretKey::LENGTH);
+        assert_eq!(&bytes[..8], &[0, 0, 0, 0, 0, 0, 0, 3]);
+
+        let mut restored = SecretKey::restore(SMALL, &bytes[4..]).unwrap();
+        assert_eq!(restored.index(), 3);
+        assert_eq!(restored.public_key(), public_key);
+        for _ in 3..8 {
+            let signature = restored.sign(b"hello").unwrap();
+            assert_eq!(signature, secret_key.sign(b"hello").unwrap());
+            assert!(public_key.verify(b"hello", &signature));
+        }
+        let exhausted = restored.to_bytes();
+        assert!(SecretKey::restore(SMALL, &exhausted[4..])
+            .unwrap()
+            .sign(b"hello")
+            .is_err());
+
+        let mut corrupted = bytes.clone();
+        corrupted[72] ^= 1;
+        assert_eq!(
+            SecretKey::restore(SMALL, &corrupted[4..]).err(),
+            Some(WotsError::InvalidEncoding)
+        );
+        corrupted[4..8].copy_from_slice(&9u32.to_be_bytes());
+        assert_eq!(
+            SecretKey::restore(SMALL, &corrupted[4..]).err(),
+            Some(WotsError::IndexOutOfRange { index: 9, len: 8 })
+        );
+        assert_eq!(
+            SecretKey::from_bytes(&bytes).err(),
+            Some(WotsError::BadMagic)
+        );
+        assert_eq!(
+            SecretKey::from_bytes(&bytes[1..]).err(),
+            Some(WotsError::LengthMismatch {
+                expected: SecretKey::LENGTH,
+                actual: SecretKey::LENGTH - 1,
+            })
+        );
+    }
+
+    #[test]
+    fn standard_parameter_sets() {
+        assert_eq!(Params::from_oid(2), Some(Params::XMSS_SHA2_16_256));
+        assert_eq!(Params::from_oid(0), None);
+        assert_eq!(Params::XMSS_SHA2_10_256.signature_length(), 2500);
+        assert_eq!(Params::XMSS_SHA2_20_256.capacity(), 1 << 20);
+    }
+}
@@ -0,0 +1,46 @@
+//! Generate, sign and verify from a `no_std` crate with its own RNG.
+//!
+//! Run with `cargo test --no-default-features` to check the crate without
+//! `std`; [`rand::rngs::OsRng`] and the `std` APIs aren't used here.
+#![no_std]
+
+use rand::{CryptoRng, RngCore};
+use wots_rs::Keypair;
+
+/// A counter standing in for a hardware RNG. Predictable, so only for tests.
+struct CounterRng(u64);
+
+impl RngCore for CounterRng {
+    fn next_u32(&mut self) -> u32 {
+        self.next_u64() as u32
+    }
+
+    fn next_u64(&mut self) -> u64 {
+        self.0 = self.0.wrapping_add(0x9e37_79b9_7f4a_7c15);
+        self.0
+    }
+
+    fn fill_bytes(&mut self, dest: &mut [u8]) {
+        for chunk in dest.chunks_mut(8) {
+            chunk.copy_from_slice(&self.next_u64().to_le_bytes()[..chunk.len()]);
+        }
+    }
+
+    fn try_fill_bytes(&mut self, dest: &mut [u8]) -> Result<(), rand::Error> {
+        self.fill_bytes(dest);
+        Ok(())
+    }
+}
+
+impl CryptoRng for CounterRng {}
+
+#[test]
+fn generates_signs_and_verifies_without_std() {
+    let mut csprng = CounterRng(0);
+    let keypair = Keypair::generate(&mut csprng);
+    let signature = keypair.sign_unchecked(b"hello");
+
+    assert!(keypair.verify(b"hello", signature));
+    assert!(!keypair.verify(b"world", signature));
+    assert!(keypair.public != Keypair::generate(&mut csprng).public);
+}